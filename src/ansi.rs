@@ -1,3 +1,50 @@
+use std::{fmt::Write, path::Path};
+use unicode_width::UnicodeWidthChar;
+
+/// The string terminator that ends an OSC, i.e. operating system command, escape sequence.
+const ST: &str = "\u{1b}\\";
+
+/// Wraps `text` in an OSC 8 escape sequence so that terminals which support it render it as a
+/// hyperlink to the file at `path`, which is expected to be absolute.
+pub fn hyperlink(text: &str, path: &Path) -> String {
+    let url = file_url(path);
+    format!("\u{1b}]8;;{url}{ST}{text}\u{1b}]8;;{ST}")
+}
+
+/// Percent-encodes `path` into a `file://` URL. Unreserved characters and path separators are
+/// left as they are.
+fn file_url(path: &Path) -> String {
+    let path = path.to_string_lossy();
+
+    #[cfg(windows)]
+    let path = format!("/{}", path.trim_start_matches(r"\\?\").replace('\\', "/"));
+
+    let mut url = String::from("file://");
+
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                url.push(char::from(byte));
+            },
+            #[cfg(windows)]
+            b':' => url.push(':'),
+            _ => {
+                let _ = write!(url, "%{byte:02X}");
+            },
+        }
+    }
+
+    url
+}
+
+/// If `rest`, which immediately follows an escape character, begins an OSC sequence, returns its
+/// length up to and including the string terminator.
+fn osc_len(rest: &str) -> Option<usize> {
+    let osc = rest.strip_prefix(']')?;
+    let len = osc.find(ST).map_or(osc.len(), |i| i + ST.len());
+    Some(len + 1)
+}
+
 /// Trait that provides functionality to ANSI escaped strings to be truncated in a manner that
 /// preserves the ANSI color/style escape sequences. Consider the following:
 ///
@@ -10,22 +57,36 @@
 ///
 /// `"\u{1b}[1;31mHello\u{1b}[0m"`
 ///
+/// Lengths are measured in terminal columns rather than `char`s so that wide characters, e.g. CJK,
+/// occupy the two columns they're actually rendered with and never cause output to overflow.
+///
 /// NOTE: This is being used for a very particular use-case and isn't comprehensive enough to
-/// handle all types of ANSI escaped sequences, only color/style related ones. It also makes some
-/// assumptions that are valid only for this program, namely that all relevant grapheme clusters
-/// are at most sized to a single `char`, so truncating to any arbitrary length will always result
-/// in a coherent output.
+/// handle all types of ANSI escaped sequences, only color/style related ones and OSC 8 hyperlinks.
+/// It also makes some assumptions that are valid only for this program, namely that all relevant
+/// grapheme clusters are at most sized to a single `char`, so truncating to any arbitrary length
+/// will always result in a coherent output.
 pub trait Escaped: AsRef<str> {
     fn truncate(&self, new_len: usize) -> String {
         let mut open_sequence = false;
+        let mut open_link = false;
         let mut resultant = String::new();
-        let mut char_count = 0;
+        let mut width = 0;
         let mut chars = self.as_ref().chars();
 
         'outer: while let Some(ch) = chars.next() {
-            resultant.push(ch);
-
             if ch == '\u{1b}' {
+                resultant.push(ch);
+
+                let rest = chars.as_str();
+
+                if let Some(len) = osc_len(rest) {
+                    let osc = &rest[..len];
+                    open_link = osc.starts_with("]8;") && !osc.starts_with("]8;;\u{1b}");
+                    resultant.push_str(osc);
+                    chars = rest[len..].chars();
+                    continue;
+                }
+
                 for code in chars.by_ref() {
                     resultant.push(code);
 
@@ -35,19 +96,67 @@ pub trait Escaped: AsRef<str> {
                     }
                 }
             }
-            char_count += 1;
 
-            if char_count == new_len {
+            let ch_width = ch.width().unwrap_or_default();
+
+            if width + ch_width > new_len {
                 break;
             }
+
+            resultant.push(ch);
+            width += ch_width;
         }
 
         if open_sequence {
             resultant.push_str("\u{1b}[0m");
         }
 
+        if open_link {
+            resultant.push_str("\u{1b}]8;;");
+            resultant.push_str(ST);
+        }
+
         resultant
     }
+
+    /// Same as [`Escaped::truncate`] except that an ellipsis is appended to indicate that
+    /// truncation took place. The ellipsis counts towards `new_len`. Strings that already fit are
+    /// left as they are.
+    fn ellipsize(&self, new_len: usize) -> String {
+        if self.display_width() <= new_len {
+            return self.as_ref().to_owned();
+        }
+
+        if new_len == 0 {
+            return String::new();
+        }
+
+        format!("{}…", self.truncate(new_len - 1))
+    }
+
+    /// The amount of terminal columns needed to display the string, ignoring ANSI escape
+    /// sequences.
+    fn display_width(&self) -> usize {
+        let mut width = 0;
+        let mut chars = self.as_ref().chars();
+
+        while let Some(ch) = chars.next() {
+            if ch == '\u{1b}' {
+                let rest = chars.as_str();
+
+                match osc_len(rest) {
+                    Some(len) => chars = rest[len..].chars(),
+                    None => _ = chars.by_ref().find(|code| *code == 'm'),
+                }
+
+                continue;
+            }
+
+            width += ch.width().unwrap_or_default();
+        }
+
+        width
+    }
 }
 
 impl Escaped for str {}
@@ -62,3 +171,33 @@ fn truncate() {
 
     assert_eq!(control, trunc);
 }
+
+#[test]
+fn truncate_wide() {
+    let trunc = <str as Escaped>::truncate("漢字.txt", 3);
+
+    assert_eq!(trunc, "漢");
+}
+
+#[test]
+fn ellipsize() {
+    use ansi_term::Color::Red;
+
+    let base = format!("{}.txt", Red.paint("nylarlathotep"));
+    let control = format!("{}…", Red.paint("nylar"));
+
+    assert_eq!(<str as Escaped>::ellipsize(&base, 6), control);
+    assert_eq!(<str as Escaped>::ellipsize(&base, 17), base);
+    assert_eq!(<str as Escaped>::display_width("漢字.txt"), 8);
+}
+
+#[test]
+fn truncate_hyperlink() {
+    let path = Path::new("/tmp/my notes.txt");
+    let base = hyperlink("my notes.txt", path);
+
+    assert!(base.starts_with("\u{1b}]8;;file:///tmp/my%20notes.txt\u{1b}\\my notes"));
+    assert_eq!(<str as Escaped>::display_width(&base), 12);
+    assert_eq!(<str as Escaped>::truncate(&base, 12), base);
+    assert_eq!(<str as Escaped>::truncate(&base, 2), hyperlink("my", path));
+}
@@ -0,0 +1,56 @@
+use std::time::{Duration, SystemTime};
+
+/// Parses a duration such as `90s`, `30m`, `24h`, `7d`, or `2w`. A unit is always required and
+/// only whole numbers are accepted.
+pub fn parse_duration(input: &str) -> Result<Duration, String> {
+    let input = input.trim();
+
+    let split_at = input
+        .find(|ch: char| !ch.is_ascii_digit())
+        .unwrap_or(input.len());
+
+    let (num, unit) = input.split_at(split_at);
+
+    let value = num
+        .parse::<u64>()
+        .map_err(|_e| format!("invalid duration '{input}'"))?;
+
+    let seconds = match unit.to_ascii_lowercase().as_str() {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 60 * 60 * 24,
+        "w" => 60 * 60 * 24 * 7,
+        "" => return Err(format!("missing unit in duration '{input}', e.g. 7d")),
+        _ => return Err(format!("invalid unit in duration '{input}'")),
+    };
+
+    value
+        .checked_mul(seconds)
+        .map(Duration::from_secs)
+        .ok_or_else(|| format!("duration '{input}' is too large"))
+}
+
+/// How long ago `time` was. Times in the future are treated as having just occurred.
+pub fn elapsed(time: SystemTime) -> Duration {
+    time.elapsed().unwrap_or_default()
+}
+
+#[test]
+fn test_parse_duration() {
+    assert_eq!(parse_duration("90s"), Ok(Duration::from_secs(90)));
+    assert_eq!(parse_duration("30m"), Ok(Duration::from_secs(30 * 60)));
+    assert_eq!(parse_duration("24H"), Ok(Duration::from_secs(24 * 60 * 60)));
+    assert_eq!(
+        parse_duration("7d"),
+        Ok(Duration::from_secs(7 * 24 * 60 * 60))
+    );
+    assert_eq!(
+        parse_duration("2w"),
+        Ok(Duration::from_secs(14 * 24 * 60 * 60))
+    );
+    assert!(parse_duration("7").is_err());
+    assert!(parse_duration("d").is_err());
+    assert!(parse_duration("1.5h").is_err());
+    assert!(parse_duration("7y").is_err());
+}
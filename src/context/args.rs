@@ -56,9 +56,9 @@ pub trait Reconciler: CommandFactory + FromArgMatches {
             let arg_id = arg.get_id();
             let id_str = arg_id.as_str();
 
-            if id_str == "dir" {
-                if let Some(dir) = user_args.try_get_one::<PathBuf>(id_str)? {
-                    final_args.push(OsString::from(dir));
+            if id_str == "dirs" {
+                if let Some(dirs) = user_args.try_get_many::<PathBuf>(id_str)? {
+                    final_args.extend(dirs.map(OsString::from));
                 }
                 continue;
             }
@@ -87,12 +87,30 @@ pub trait Reconciler: CommandFactory + FromArgMatches {
                     };
                 },
                 ArgAction::SetFalse => continue,
+                ArgAction::Append => {
+                    let Ok(Some(raw)) = argument_source.try_get_raw(id_str) else {
+                        continue;
+                    };
+
+                    for value in raw {
+                        final_args.push(key.clone());
+                        final_args.push(value.to_os_string());
+                    }
+                },
                 _ => {
                     let Ok(Some(raw)) = argument_source.try_get_raw(id_str) else {
                         continue;
                     };
                     final_args.push(key);
-                    final_args.extend(raw.map(OsStr::to_os_string));
+
+                    // Values that were split on a delimiter are rejoined so that they're parsed
+                    // as a single occurrence.
+                    if let Some(delimiter) = arg.get_value_delimiter() {
+                        let values = raw.map(OsStr::to_string_lossy).collect::<Vec<_>>();
+                        final_args.push(OsString::from(values.join(&delimiter.to_string())));
+                    } else {
+                        final_args.extend(raw.map(OsStr::to_os_string));
+                    }
                 },
             }
         }
@@ -103,6 +121,84 @@ pub trait Reconciler: CommandFactory + FromArgMatches {
 
 impl Reconciler for Context {}
 
+/// Reconstructs the invocation that is equivalent to the provided [`ArgMatches`], which may have
+/// been reconciled with the config file, e.g. `erd --sort size --level 2 src`. Arguments whose
+/// values are the same as their defaults are omitted.
+pub fn effective_command(matches: &ArgMatches) -> String {
+    let cmd = Context::command();
+    let mut tokens = vec![String::from("erd")];
+    let mut positionals = vec![];
+
+    for arg in cmd.get_arguments() {
+        let id_str = arg.get_id().as_str();
+
+        if id_str == "print_command"
+            || !matches!(matches.value_source(id_str), Some(ValueSource::CommandLine))
+        {
+            continue;
+        }
+
+        let raw = matches
+            .get_raw(id_str)
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        // Arguments reconciled with the config file are always explicit, even defaults.
+        if !arg.get_default_values().is_empty() && raw == arg.get_default_values() {
+            continue;
+        }
+
+        let values = raw
+            .into_iter()
+            .map(|value| shell_quote(&value.to_string_lossy()));
+
+        let Some(long) = arg.get_long() else {
+            positionals.extend(values);
+            continue;
+        };
+
+        match arg.get_action() {
+            ArgAction::SetTrue => tokens.push(format!("--{long}")),
+            ArgAction::SetFalse => {},
+            ArgAction::Append => {
+                for value in values {
+                    tokens.push(format!("--{long}"));
+                    tokens.push(value);
+                }
+            },
+            _ => {
+                tokens.push(format!("--{long}"));
+
+                match arg.get_value_delimiter() {
+                    Some(delimiter) => {
+                        let values = values.collect::<Vec<_>>();
+                        tokens.push(values.join(&delimiter.to_string()));
+                    },
+                    None => tokens.extend(values),
+                }
+            },
+        }
+    }
+
+    tokens.extend(positionals);
+    tokens.join(" ")
+}
+
+/// Wraps `value` in single-quotes if it contains characters that a shell would interpret.
+fn shell_quote(value: &str) -> String {
+    let is_plain = !value.is_empty()
+        && value.chars().all(|c| {
+            c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | '/' | ',' | ':' | '=' | '+')
+        });
+
+    if is_plain {
+        return String::from(value);
+    }
+
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
 /// Creates a properly formatted `Vec<OsString>` that [`clap::Command`] would understand.
 #[inline]
 fn init_empty_args() -> Vec<OsString> {
@@ -1,25 +1,62 @@
 use clap::ValueEnum;
 use once_cell::sync::OnceCell;
-use std::{env, ffi::OsString};
+use std::{
+    env,
+    ffi::{OsStr, OsString},
+};
 
 pub static NO_COLOR: OnceCell<Option<OsString>> = OnceCell::new();
 
-/// Reads in the `NO_COLOR` environment variable to determine whether or not to display color in
-/// the output.
+pub static CLICOLOR_FORCE: OnceCell<Option<OsString>> = OnceCell::new();
+
+/// Reads in the `NO_COLOR` and `CLICOLOR_FORCE` environment variables which determine whether or
+/// not to display color in the output when the [Coloring] is [`Coloring::Auto`].
 pub fn no_color_env() {
     let _ = NO_COLOR.set(env::var_os("NO_COLOR"));
+    let _ = CLICOLOR_FORCE.set(env::var_os("CLICOLOR_FORCE"));
+}
+
+/// Whether `NO_COLOR` is set to a non-empty value.
+pub fn no_color_set() -> bool {
+    NO_COLOR
+        .get()
+        .and_then(Option::as_ref)
+        .is_some_and(|var| !var.is_empty())
+}
+
+/// Whether `CLICOLOR_FORCE` is set to a value that forces color.
+pub fn clicolor_force_set() -> bool {
+    CLICOLOR_FORCE
+        .get()
+        .and_then(Option::as_deref)
+        .is_some_and(forces_color)
+}
+
+/// Any value of `CLICOLOR_FORCE` other than an empty one or `0` forces color.
+fn forces_color(var: &OsStr) -> bool {
+    !var.is_empty() && var != "0"
 }
 
 /// Enum to determine how the output should be colorized.
 #[derive(Clone, Copy, Debug, ValueEnum, PartialEq, Eq, Default)]
 pub enum Coloring {
-    /// Print plainly without ANSI escapes
+    /// Print plainly without ANSI escapes (alias: never)
+    #[value(alias("never"))]
     None,
 
-    /// Attempt to colorize output
+    /// Colorize output if stdout is a tty unless the environment says otherwise
     #[default]
     Auto,
 
-    /// Turn on colorization always
+    /// Turn on colorization always, even when piping (alias: always)
+    #[value(alias("always"))]
     Force,
 }
+
+#[test]
+fn test_forces_color() {
+    assert!(forces_color(OsStr::new("1")));
+    assert!(forces_color(OsStr::new("yes")));
+    assert!(!forces_color(OsStr::new("0")));
+    assert!(!forces_color(OsStr::new("")));
+}
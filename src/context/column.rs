@@ -5,7 +5,14 @@ use std::convert::From;
 /// measured as the number of columns of the tty's window.
 pub struct Properties {
     pub max_size_width: usize,
+    pub max_other_size_width: usize,
+    pub max_count_width: usize,
     pub max_size_unit_width: usize,
+    pub max_ext_width: usize,
+
+    /// The largest disk usage of any node other than the root. Unlike the other properties this
+    /// isn't a width.
+    pub max_file_size: u64,
 
     #[cfg(unix)]
     pub max_nlink_width: usize,
@@ -21,6 +28,9 @@ pub struct Properties {
 
     #[cfg(unix)]
     pub max_group_width: usize,
+
+    #[cfg(unix)]
+    pub max_time_width: usize,
 }
 
 impl From<&Context> for Properties {
@@ -33,7 +43,11 @@ impl From<&Context> for Properties {
 
         Self {
             max_size_width: 0,
+            max_other_size_width: 0,
+            max_count_width: 0,
             max_size_unit_width: unit_width,
+            max_ext_width: 0,
+            max_file_size: 0,
             #[cfg(unix)]
             max_nlink_width: 0,
             #[cfg(unix)]
@@ -44,6 +58,8 @@ impl From<&Context> for Properties {
             max_owner_width: 0,
             #[cfg(unix)]
             max_group_width: 0,
+            #[cfg(unix)]
+            max_time_width: 0,
         }
     }
 }
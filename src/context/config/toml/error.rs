@@ -1,3 +1,4 @@
+use crate::{icons::Error as IconsError, styles::theme::Error as ThemeError};
 use config::ConfigError;
 
 #[derive(Debug, thiserror::Error)]
@@ -16,4 +17,10 @@ pub enum Error {
 
     #[error("'#{0}' has a type that is invalid")]
     InvalidArgument(String),
+
+    #[error("{0}")]
+    Theme(#[from] ThemeError),
+
+    #[error("{0}")]
+    Icons(#[from] IconsError),
 }
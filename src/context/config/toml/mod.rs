@@ -1,7 +1,14 @@
+use crate::{icons::Overrides, styles::theme::Theme};
 use config::{Config, File, Value, ValueKind};
 use error::Error;
 use std::{env, ffi::OsString};
 
+/// Name of the table in `.erdtree.toml` that holds the user's [`Theme`].
+const THEME_TABLE: &str = "theme";
+
+/// Name of the table in `.erdtree.toml` that holds the user's icon [`Overrides`].
+const ICON_OVERRIDES_TABLE: &str = "icon_overrides";
+
 /// Errors associated with loading and parsing the toml config file.
 pub mod error;
 
@@ -18,6 +25,10 @@ enum ArgInstructions {
     /// Used for arguments such as `--threads 10`.
     PushKeyValue { parsed_value: OsString },
 
+    /// Used for arguments that may be specified multiple times such as `--exclude-pattern`. The
+    /// key is pushed before each of the values.
+    PushKeyValues { parsed_values: Vec<OsString> },
+
     /// If a bool field is set to false in `.erdtree.toml` (e.g. `icons = false`) then we want to
     /// completely omit the key-value pair from the arguments that we ultimately use.
     Pass,
@@ -53,6 +64,14 @@ pub fn parse(config: Config, named_table: Option<&str>) -> Result<Vec<OsString>,
                 parsed_args.push(parsed_value);
             },
 
+            ArgInstructions::PushKeyValues { parsed_values } => {
+                for parsed_value in parsed_values {
+                    let fmt_key = process_key(k);
+                    parsed_args.push(fmt_key);
+                    parsed_args.push(parsed_value);
+                }
+            },
+
             ArgInstructions::PushKeyOnly => {
                 let fmt_key = process_key(k);
                 parsed_args.push(fmt_key);
@@ -65,6 +84,66 @@ pub fn parse(config: Config, named_table: Option<&str>) -> Result<Vec<OsString>,
     Ok(parsed_args)
 }
 
+/// Reads the `[theme]` table of `.erdtree.toml` into a [`Theme`]. Values are style
+/// specifications such as `"bold red"`. An empty [`Theme`] is returned if there is no config file
+/// or no such table.
+pub fn load_theme() -> Result<Theme, Error> {
+    let entries = load_table_entries(THEME_TABLE)?;
+
+    Ok(Theme::try_from_entries(entries)?)
+}
+
+/// Reads the `[icon_overrides]` table of `.erdtree.toml` into [`Overrides`]. Values are the icons
+/// to use for the extensions, file names, or globs that key them. Empty [`Overrides`] are returned
+/// if there is no config file or no such table.
+pub fn load_icon_overrides() -> Result<Overrides, Error> {
+    let entries = load_table_entries(ICON_OVERRIDES_TABLE)?;
+
+    Ok(Overrides::try_from_entries(entries)?)
+}
+
+/// Collects the string values of the table named `name` in `.erdtree.toml`, with nested tables
+/// flattened into dotted keys relative to it e.g. `size.kib`.
+fn load_table_entries(name: &str) -> Result<Vec<(String, String)>, Error> {
+    let mut entries = vec![];
+
+    let Ok(config) = load() else {
+        return Ok(entries);
+    };
+
+    if let Some(table) = config.cache.into_table()?.remove(name) {
+        flatten_table(name, String::new(), table, &mut entries)?;
+    }
+
+    Ok(entries)
+}
+
+/// Collects the string values of `value` into `entries` keyed by their dotted path from the
+/// table named `name`.
+fn flatten_table(
+    name: &str,
+    key: String,
+    value: Value,
+    entries: &mut Vec<(String, String)>,
+) -> Result<(), Error> {
+    match value.kind {
+        ValueKind::Table(table) => {
+            for (k, v) in table {
+                let key = if key.is_empty() {
+                    k
+                } else {
+                    format!("{key}.{k}")
+                };
+                flatten_table(name, key, v, entries)?;
+            }
+        },
+        ValueKind::String(val) => entries.push((key, val)),
+        _ => return Err(Error::InvalidArgument(format!("{name}.{key}"))),
+    }
+
+    Ok(())
+}
+
 /// Reads in `.erdtree.toml` file.
 pub fn load() -> Result<Config, Error> {
     #[cfg(windows)]
@@ -118,6 +197,17 @@ fn parse_argument(keyword: &str, arg: &Value) -> Result<ArgInstructions, Error>
         ValueKind::I128(val) => try_parse_num!(*val),
         ValueKind::U64(val) => try_parse_num!(*val),
         ValueKind::U128(val) => try_parse_num!(*val),
+        ValueKind::Array(vals) => {
+            let parsed_values = vals
+                .iter()
+                .map(|val| match &val.kind {
+                    ValueKind::String(s) => Ok(OsString::from(s)),
+                    _ => Err(Error::InvalidArgument(keyword.to_owned())),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            Ok(ArgInstructions::PushKeyValues { parsed_values })
+        },
         _ => Err(Error::InvalidArgument(keyword.to_owned())),
     }
 }
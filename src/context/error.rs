@@ -2,7 +2,7 @@ use super::config::toml::error::Error as TomlError;
 use clap::{parser::MatchesError, Error as ClapError};
 use ignore::Error as IgnoreError;
 use regex::Error as RegexError;
-use std::convert::From;
+use std::{convert::From, io::Error as IoError};
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -15,12 +15,48 @@ pub enum Error {
     #[error("No glob was provided")]
     EmptyGlob,
 
+    #[error("Failed to read exclusion patterns from '{0}': {1}")]
+    ExcludePatternFile(String, IoError),
+
     #[error("{0}")]
     IgnoreError(#[from] IgnoreError),
 
+    #[error("Failed to read ignore file '{0}': {1}")]
+    IgnoreFile(String, IoError),
+
+    #[error("Invalid pattern on line {1} of ignore file '{0}': {2}")]
+    InvalidIgnoreFilePattern(String, usize, String),
+
+    #[error("'--interactive' requires stdout to be a terminal")]
+    InteractiveNotTty,
+
+    #[error("'--watch' requires stdout to be a terminal")]
+    WatchNotTty,
+
+    #[error("'--stdin' requires paths to be piped into stdin")]
+    StdinIsTty,
+
+    #[error("Failed to read paths from stdin: {0}")]
+    Stdin(IoError),
+
+    #[error("'--heat age' can't be used with '--size-gradient'")]
+    HeatAgeSizeGradient,
+
+    #[error(
+        "Multiple directories can't be used with '--json', '--csv', '--html', '--ncdu', \
+         '--markdown', '--snapshot', '--diff', '--interactive', or '--watch'"
+    )]
+    MultipleRoots,
+
+    #[error("Invalid pattern '{0}': {1}")]
+    InvalidPattern(String, String),
+
     #[error("{0}")]
     InvalidRegularExpression(#[from] RegexError),
 
+    #[error("Failed to write output to '{0}': {1}")]
+    Output(String, IoError),
+
     #[error("Missing '--pattern' argument")]
     PatternNotProvided,
 
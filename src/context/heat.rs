@@ -0,0 +1,26 @@
+use clap::ValueEnum;
+
+/// The metric by which `--heat` colors entries.
+#[derive(Clone, Copy, Debug, ValueEnum, PartialEq, Eq, Default)]
+pub enum Metric {
+    /// Disk usage as reported by `--disk-usage`
+    #[default]
+    Size,
+
+    /// Time since last modification; directories are as recent as their newest entry
+    Age,
+}
+
+/// What each entry's disk usage is measured against when coloring it on a gradient.
+#[derive(Clone, Copy, Debug, ValueEnum, PartialEq, Eq, Default)]
+pub enum Scope {
+    /// Relative to the largest, or oldest with --heat age, entry in the tree other than the root
+    #[default]
+    Largest,
+
+    /// Relative to the largest, or oldest with --heat age, entry within the same directory
+    Siblings,
+
+    /// Relative to the total disk usage of the root; same as largest with --heat age
+    Total,
+}
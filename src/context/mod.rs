@@ -1,20 +1,28 @@
-use super::disk_usage::{file_size::DiskUsage, units::PrefixKind};
-use crate::tty;
+use super::disk_usage::{
+    file_size::DiskUsage,
+    units::{self, PrefixKind},
+};
+use crate::{icons, styles::theme::Theme, tty};
 use args::Reconciler;
-use clap::{FromArgMatches, Parser};
+use clap::{ArgAction, FromArgMatches, Parser, ValueHint};
 use color::Coloring;
 use error::Error;
 use ignore::{
     overrides::{Override, OverrideBuilder},
     DirEntry,
 };
-use regex::Regex;
+use regex::{Regex, RegexBuilder};
 use std::{
     borrow::Borrow,
+    collections::HashSet,
     convert::From,
+    ffi::OsStr,
+    fs,
     num::NonZeroUsize,
     path::{Path, PathBuf},
+    sync::Arc,
     thread::available_parallelism,
+    time::{Duration, SystemTime},
 };
 
 /// Concerned with figuring out how to reconcile arguments provided via the command-line with
@@ -42,25 +50,42 @@ pub mod layout;
 /// Utilities to print output.
 pub mod column;
 
+/// For determining how paths are displayed in flat layouts.
+pub mod path;
+
+/// For determining where disk usage is displayed relative to file names.
+pub mod size;
+
 /// Printing order kinds.
 pub mod sort;
 
+/// Reading in the paths to print via `--stdin`.
+pub mod stdin;
+
+/// Durations used to filter files by when they were last modified.
+pub mod age;
+
+/// Options for coloring entries on a gradient with `--heat`.
+pub mod heat;
+
 /// Different types of timestamps available in long view.
 #[cfg(unix)]
 pub mod time;
 
 /// Defines the CLI.
-#[derive(Parser, Debug)]
+#[derive(Parser, Debug, Clone)]
 #[command(name = "erdtree")]
 #[command(author = "Benjamin Nguyen. <benjamin.van.nguyen@gmail.com>")]
 #[command(version = "3.1.0")]
 #[command(about = "erdtree (erd) is a cross-platform, multi-threaded, and general purpose filesystem and disk usage utility.", long_about = None)]
 pub struct Context {
-    /// Directory to traverse; defaults to current working directory
-    dir: Option<PathBuf>,
+    /// Directories to traverse, each rendered as its own tree; defaults to current working
+    /// directory
+    #[arg(value_name = "DIR", value_hint = ValueHint::DirPath)]
+    dirs: Vec<PathBuf>,
 
     /// Use configuration of named table rather than the top-level table in .erdtree.toml
-    #[arg(short = 'c', long)]
+    #[arg(short = 'c', long, value_hint = ValueHint::Other)]
     pub config: Option<String>,
 
     /// Mode of coloring output
@@ -75,18 +100,46 @@ pub struct Context {
     #[arg(short = 'f', long)]
     pub follow: bool,
 
+    /// Annotate followed symlinks to directories with their canonical target path
+    #[arg(long, requires = "follow")]
+    pub resolve_links: bool,
+
     /// Print disk usage in human-readable format
     #[arg(short = 'H', long)]
     pub human: bool,
 
-    /// Do not respect .gitignore files
+    /// Print disk usage in bytes as plain integers without a unit
+    #[arg(long, conflicts_with = "human")]
+    pub raw: bool,
+
+    /// Separate every three digits of disk usage in plain bytes with a comma
+    #[arg(long)]
+    pub group_digits: bool,
+
+    /// Do not respect .gitignore files, .git/info/exclude, or the global gitignore
     #[arg(short = 'i', long)]
     pub no_ignore: bool,
 
+    /// Do not respect the global gitignore i.e. core.excludesFile
+    #[arg(long)]
+    pub no_global_ignore: bool,
+
     /// Display file icons
     #[arg(short = 'I', long)]
     pub icons: bool,
 
+    /// Colorize icons even when the rest of the output is printed without color
+    #[arg(long, requires = "icons")]
+    pub color_icons: bool,
+
+    /// Draw the branches of the tree using ASCII characters only; disables icons
+    #[arg(long)]
+    pub ascii: bool,
+
+    /// Make file names clickable links in terminals that support them
+    #[arg(long)]
+    pub hyperlink: bool,
+
     /// Show extended metadata and attributes
     #[cfg(unix)]
     #[arg(short, long)]
@@ -97,9 +150,8 @@ pub struct Context {
     #[arg(long)]
     pub group: bool,
 
-    /// Show each file's ino
-    #[cfg(unix)]
-    #[arg(long)]
+    /// Show each file's inode number, with or without '--long'
+    #[arg(long, visible_alias = "inode")]
     pub ino: bool,
 
     /// Show the total number of hardlinks to the underlying inode
@@ -118,17 +170,42 @@ pub struct Context {
     pub time: Option<time::Stamp>,
 
     /// Which format to use for the timestamp; default by default
+    ///
+    /// Possible values:
+    /// - iso:
+    ///   Timestamp formatted following the iso8601, with slight differences and the time-zone
+    ///   omitted
+    /// - iso-strict:
+    ///   Timestamp formatted following the exact iso8601 specifications
+    /// - short:
+    ///   Timestamp only shows date without time in YYYY-MM-DD format
+    /// - default:
+    ///   Timestamp is shown like `ls -l`; MMM DD HH:MM, or MMM DD YYYY if older than six months
+    /// - +FORMAT:
+    ///   Timestamp is shown using a strftime-style FORMAT, e.g. '+%Y-%m-%d %H:%M'
     #[cfg(unix)]
-    #[arg(long = "time-format", value_enum, requires = "long")]
+    #[arg(
+        long = "time-format",
+        value_name = "FORMAT",
+        value_parser = time::Format::parse,
+        requires = "long",
+        verbatim_doc_comment
+    )]
     pub time_format: Option<time::Format>,
 
     /// Maximum depth to display
-    #[arg(short = 'L', long, value_name = "NUM")]
+    #[arg(short = 'L', long, value_name = "NUM", value_hint = ValueHint::Other)]
     level: Option<usize>,
 
-    /// Regular expression (or glob if '--glob' or '--iglob' is used) used to match files
-    #[arg(short, long)]
-    pub pattern: Option<String>,
+    /// Minimum depth to display; shallower levels are hidden and deeper entries are listed
+    /// beneath the root by their path
+    #[arg(long, value_name = "NUM", value_hint = ValueHint::Other)]
+    pub min_depth: Option<usize>,
+
+    /// Regular expression (or glob if '--glob' or '--iglob' is used) used to match files; may be
+    /// specified multiple times to match files that match any of them
+    #[arg(short, long, value_hint = ValueHint::Other)]
+    pub pattern: Vec<String>,
 
     /// Enables glob based searching
     #[arg(group = "searching", long, requires = "pattern")]
@@ -138,27 +215,89 @@ pub struct Context {
     #[arg(group = "searching", long, requires = "pattern")]
     pub iglob: bool,
 
+    /// Enables case-insensitive regex based searching
+    #[arg(group = "searching", long, requires = "pattern")]
+    pub iregex: bool,
+
     /// Restrict regex or glob search to a particular file-type
     #[arg(short = 't', long, requires = "pattern", value_enum)]
     pub file_type: Option<file::Type>,
 
+    /// Only show files with the given extension, case-insensitively; may be specified multiple
+    /// times
+    #[arg(short = 'e', long, value_name = "EXT", value_hint = ValueHint::Other)]
+    pub extension: Vec<String>,
+
+    /// Apply the regex or glob to directory names as well, pruning directories that don't match and
+    /// keeping everything beneath those that do
+    #[arg(long, requires = "pattern")]
+    pub match_dirs: bool,
+
+    /// Only show entries that match the pattern along with the directories leading to them
+    #[arg(long, requires = "pattern")]
+    pub matches_only: bool,
+
+    /// Regular expression used to exclude files by name; may be specified multiple times
+    #[arg(long, value_name = "PATTERN", value_hint = ValueHint::Other)]
+    pub exclude_pattern: Vec<String>,
+
+    /// File containing regular expressions, one per line, used to exclude files by name
+    #[arg(long, value_name = "FILE", value_hint = ValueHint::FilePath)]
+    pub exclude_pattern_file: Option<PathBuf>,
+
+    /// Glob used to exclude files and directories, the latter of which won't be traversed; may be
+    /// specified multiple times
+    #[arg(long, value_name = "GLOB", value_hint = ValueHint::Other)]
+    pub exclude: Vec<String>,
+
+    /// Glob used to exclude directories, which won't be traversed, but not files of the same name;
+    /// may be specified multiple times
+    #[arg(long, value_name = "GLOB", value_hint = ValueHint::Other)]
+    pub exclude_dir: Vec<String>,
+
+    /// File containing globs, one per line, used to exclude files and directories regardless of
+    /// '--no-ignore'; may be specified multiple times
+    #[arg(long, value_name = "PATH", value_hint = ValueHint::FilePath)]
+    pub ignore_file: Vec<PathBuf>,
+
     /// Remove empty directories from output
     #[arg(short = 'P', long)]
     pub prune: bool,
 
-    /// How to sort entries
-    #[arg(short, long, value_enum, default_value_t)]
-    pub sort: sort::Type,
+    /// How to sort entries; additional comma-separated sort types break ties, e.g. size,name
+    #[arg(
+        short,
+        long,
+        value_enum,
+        value_delimiter = ',',
+        action = ArgAction::Set,
+        overrides_with = "sort",
+        default_values_t = [sort::Type::default()]
+    )]
+    pub sort: Vec<sort::Type>,
+
+    /// Reverse the order entries are sorted in
+    #[arg(short, long)]
+    pub reverse: bool,
 
     /// Sort directories before or after all other file types
     #[arg(long, value_enum, default_value_t)]
     pub dir_order: dir::Order,
 
+    /// Sort directories below files; shorthand for '--dir-order last'
+    #[arg(long)]
+    pub dirs_last: bool,
+
     /// Number of threads to use
-    #[arg(short = 'T', long, default_value_t = Context::num_threads())]
+    #[arg(
+        short = 'T',
+        long,
+        default_value_t = Context::num_threads(),
+        value_hint = ValueHint::Other
+    )]
     pub threads: usize,
 
-    /// Report disk usage in binary or SI units
+    /// Report disk usage in binary or SI units, or as plain byte counts
     #[arg(short, long, value_enum, default_value_t)]
     pub unit: PrefixKind,
 
@@ -166,10 +305,31 @@ pub struct Context {
     #[arg(short = 'x', long = "one-file-system")]
     pub same_fs: bool,
 
+    /// Annotate the mount points that --one-file-system doesn't descend into
+    #[arg(long, requires = "same_fs")]
+    pub show_mounts: bool,
+
     /// Which kind of layout to use when rendering the output
     #[arg(short = 'y', long, value_enum, default_value_t)]
     pub layout: layout::Type,
 
+    /// How paths are displayed when using a flat layout
+    #[arg(long, value_enum, default_value_t)]
+    pub paths: path::Type,
+
+    /// Whether disk usage is displayed to the left or to the right of the file name
+    #[arg(long, value_enum, default_value_t)]
+    pub size_position: size::Position,
+
+    /// Fixed width of the numeric part of disk usage rather than one that fits the largest value
+    #[arg(
+        long,
+        visible_alias = "du-width",
+        value_name = "NUM",
+        conflicts_with = "suppress_size"
+    )]
+    pub size_width: Option<usize>,
+
     /// Show hidden files
     #[arg(short = '.', long)]
     pub hidden: bool,
@@ -186,22 +346,241 @@ pub struct Context {
     #[arg(long)]
     pub dirs_only: bool,
 
+    /// Only print files, listed beneath the root by their path, hiding directories
+    #[arg(long, conflicts_with = "dirs_only")]
+    pub files_only: bool,
+
+    /// Only print symlinks whose targets don't exist along with their ancestors
+    #[arg(long, conflicts_with = "dirs_only")]
+    pub broken_only: bool,
+
+    /// Only print the paths read from stdin, separated by newlines or NUL bytes, e.g. from fd or
+    /// git ls-files, along with their ancestors
+    #[arg(long, conflicts_with = "interactive")]
+    pub stdin: bool,
+
+    /// Only print the first NUM entries of each directory followed by a line summarizing the rest
+    #[arg(
+        long,
+        value_name = "NUM",
+        conflicts_with_all = [
+            "json", "csv", "html", "ncdu", "markdown", "list", "top", "interactive"
+        ],
+        value_hint = ValueHint::Other
+    )]
+    pub limit: Option<NonZeroUsize>,
+
+    /// Collapse chains of directories that only contain a single directory into one line
+    #[arg(long)]
+    pub collapse: bool,
+
+    /// Remove directories whose total size is below the given threshold, e.g. 10K, 2MiB, 1GB
+    #[arg(
+        long,
+        visible_alias = "du-threshold",
+        value_name = "SIZE",
+        value_parser = units::parse_size
+    )]
+    pub min_dir_size: Option<u64>,
+
+    /// Only show files whose disk usage is at least the given size, e.g. 10K, 2MiB, 1GB
+    #[arg(long, value_name = "SIZE", value_parser = units::parse_size)]
+    pub min_size: Option<u64>,
+
+    /// Only show files whose disk usage is at most the given size, e.g. 10K, 2MiB, 1GB
+    #[arg(long, value_name = "SIZE", value_parser = units::parse_size)]
+    pub max_size: Option<u64>,
+
+    /// Only show files modified within the given duration, e.g. 30m, 24h, 7d, 2w
+    #[arg(long, value_name = "DURATION", value_parser = age::parse_duration)]
+    pub newer: Option<Duration>,
+
+    /// Only show files last modified longer ago than the given duration, e.g. 30m, 24h, 7d, 2w
+    #[arg(long, value_name = "DURATION", value_parser = age::parse_duration)]
+    pub older: Option<Duration>,
+
+    /// Annotate directories with the total number of files they contain along with their size
+    #[arg(long)]
+    pub dir_stats: bool,
+
+    /// Annotate directories at the cutoff of --level with what's beneath them
+    #[arg(long, requires = "level", conflicts_with = "dir_stats")]
+    pub summarize: bool,
+
+    /// Show the number of files within each directory in place of disk usage
+    #[arg(long, conflicts_with_all = ["suppress_size", "both_sizes"])]
+    pub count: bool,
+
     /// Don't read configuration file
     #[arg(long)]
     pub no_config: bool,
 
-    /// Hides the progress indicator
+    /// Hides the progress indicator which is otherwise shown on stderr if it's a tty
     #[arg(long)]
     pub no_progress: bool,
 
-    /// Omit disk usage from output
+    /// Omit the trailing file count summary from output
+    #[arg(long, visible_alias = "no-summary")]
+    pub no_count: bool,
+
+    /// Dim the names of deeply nested entries so that the hierarchy is easier to follow
+    #[arg(long)]
+    pub dim_depth: bool,
+
+    /// Show a bar representing each file's share of its parent directory's disk usage
+    #[arg(long, visible_alias = "bar", conflicts_with = "suppress_size")]
+    pub bars: bool,
+
+    /// Number of characters between the brackets of the bar from --bars
+    #[arg(
+        long,
+        value_name = "NUM",
+        default_value_t = NonZeroUsize::new(10).unwrap(),
+        value_hint = ValueHint::Other
+    )]
+    pub bar_width: NonZeroUsize,
+
+    /// Show the git status of each file, i.e. whether it's staged, modified, or untracked
+    #[arg(long)]
+    pub git_status: bool,
+
+    /// Save the size of every entry to FILE so that a later run can be compared against it
+    #[arg(long, value_name = "FILE", value_hint = ValueHint::FilePath, conflicts_with = "watch")]
+    pub snapshot: Option<PathBuf>,
+
+    /// Annotate entries with how their size changed since FILE was saved with '--snapshot'
+    #[arg(long, value_name = "FILE", value_hint = ValueHint::FilePath)]
+    pub diff: Option<PathBuf>,
+
+    /// Include the total disk usage in the trailing file count summary
+    #[arg(long, conflicts_with = "no_count")]
+    pub stats: bool,
+
+    /// Print how long traversal, aggregation, and rendering took to stderr along with the
+    /// throughput and the amount of errors encountered
+    #[arg(long, conflicts_with_all = ["interactive", "watch"])]
+    pub timings: bool,
+
+    /// Omit disk usage from output; metadata is then only queried if --long or colors need it
     #[arg(long)]
     pub suppress_size: bool,
 
-    /// Truncate output to fit terminal emulator window
+    /// Show both logical and physical disk usage, in that order
+    #[arg(long, conflicts_with = "suppress_size")]
+    pub both_sizes: bool,
+
+    /// Truncate output to fit terminal emulator window; enabled by default if stdout is a tty
     #[arg(long)]
     pub truncate: bool,
 
+    /// Don't truncate output to fit terminal emulator window
+    #[arg(long, conflicts_with = "truncate")]
+    pub no_truncate: bool,
+
+    /// Width to assume for the terminal emulator's window rather than detecting it
+    #[arg(long, value_name = "NUM", value_hint = ValueHint::Other)]
+    pub render_width: Option<usize>,
+
+    /// Show a column with each file's extension when using a flat layout
+    #[arg(long)]
+    pub ext_column: bool,
+
+    /// Color file names from green to red by disk usage relative to the largest entry
+    #[arg(long)]
+    pub size_gradient: bool,
+
+    /// Color file names by the given metric, from green to red for size or bright to dim for age
+    #[arg(long, value_enum, value_name = "METRIC")]
+    pub heat: Option<heat::Metric>,
+
+    /// What entries are measured against when coloring with --heat or --size-gradient
+    #[arg(long, value_enum, value_name = "SCOPE", default_value_t)]
+    pub heat_scope: heat::Scope,
+
+    /// List each hard-linked file only once when using a flat layout
+    #[arg(long)]
+    pub dedupe_hardlinks: bool,
+
+    /// Count every hard-link to the same file when totaling directory sizes
+    #[arg(long, visible_alias = "no-dedupe-links")]
+    pub count_hard_links: bool,
+
+    /// Print the command, including arguments from the config file, that produced the output
+    #[arg(long)]
+    pub print_command: bool,
+
+    /// Write output to the given file rather than to stdout; output is uncolored unless forced
+    #[arg(short = 'o', long, value_name = "PATH", value_hint = ValueHint::FilePath)]
+    pub output: Option<PathBuf>,
+
+    /// Print the tree as a JSON document rather than as a tree or flat layout
+    #[arg(long, conflicts_with_all = ["completions", "print_command"])]
+    pub json: bool,
+
+    /// Print one comma-separated row per file rather than a tree or flat layout
+    #[arg(long, conflicts_with_all = ["completions", "print_command", "json"])]
+    pub csv: bool,
+
+    /// Omit the header row from CSV output
+    #[arg(long, requires = "csv")]
+    pub no_header: bool,
+
+    /// Print nothing but the full path of each file, one per line
+    #[arg(long, conflicts_with_all = ["completions", "print_command", "json", "csv", "top"])]
+    pub list: bool,
+
+    /// Prefix each path printed by '--list' with its disk usage, separated by a tab
+    #[arg(long, requires = "list", conflicts_with = "suppress_size")]
+    pub list_size: bool,
+
+    /// Print the tree as a self-contained HTML document
+    #[arg(
+        long,
+        conflicts_with_all = ["completions", "print_command", "json", "csv", "list", "top"]
+    )]
+    pub html: bool,
+
+    /// Browse the tree in the terminal, expanding and collapsing directories
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "completions", "print_command", "json", "csv", "list", "top", "html", "output"
+        ]
+    )]
+    pub interactive: bool,
+
+    /// Print the tree in ncdu's JSON export format so that it can be browsed with 'ncdu -f'
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "completions", "print_command", "json", "csv", "list", "top", "html", "interactive"
+        ]
+    )]
+    pub ncdu: bool,
+
+    /// Print the tree as a nested Markdown list
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "completions", "print_command", "json", "csv", "list", "top", "html", "interactive",
+            "ncdu"
+        ]
+    )]
+    pub markdown: bool,
+
+    /// Keep running and re-render the tree whenever the contents of the directory change
+    #[arg(long, conflicts_with_all = ["completions", "interactive", "output"])]
+    pub watch: bool,
+
+    /// Only show the given number of largest files, ranked by disk usage, as a flat list
+    #[arg(
+        long,
+        value_name = "NUM",
+        conflicts_with_all = ["json", "csv", "suppress_size"],
+        value_hint = ValueHint::Other
+    )]
+    pub top: Option<usize>,
+
     //////////////////////////
     /* INTERNAL USAGE BELOW */
     //////////////////////////
@@ -213,14 +592,34 @@ pub struct Context {
     #[clap(skip = tty::stdout_is_tty())]
     pub stdout_is_tty: bool,
 
+    /// Is stderr in a tty?
+    #[clap(skip = tty::stderr_is_tty())]
+    pub stderr_is_tty: bool,
+
     /// Restricts column width of size not including units
     #[clap(skip = usize::default())]
     pub max_size_width: usize,
 
+    /// Restricts column width of the additional size from `--both-sizes` not including units
+    #[clap(skip = usize::default())]
+    pub max_other_size_width: usize,
+
+    /// Restricts column width of file counts from `--count`
+    #[clap(skip = usize::default())]
+    pub max_count_width: usize,
+
     /// Restricts column width of disk_usage units
     #[clap(skip = usize::default())]
     pub max_size_unit_width: usize,
 
+    /// Restricts column width of file extensions for flat layouts
+    #[clap(skip = usize::default())]
+    pub max_ext_width: usize,
+
+    /// The largest disk usage of any node other than the root, used for `--size-gradient`
+    #[clap(skip = u64::default())]
+    pub max_file_size: u64,
+
     /// Restricts column width of nlink for long view
     #[clap(skip = usize::default())]
     #[cfg(unix)]
@@ -246,9 +645,30 @@ pub struct Context {
     #[cfg(unix)]
     pub max_group_width: usize,
 
+    /// Restricts column width of timestamps for long view
+    #[clap(skip = usize::default())]
+    #[cfg(unix)]
+    pub max_time_width: usize,
+
     /// Width of the terminal emulator's window
     #[clap(skip)]
     pub window_width: Option<usize>,
+
+    /// The invocation that is equivalent to the reconciled command-line and config arguments
+    #[clap(skip)]
+    pub effective_command: Option<String>,
+
+    /// The paths read in via `--stdin`
+    #[clap(skip)]
+    pub stdin_paths: Arc<Vec<PathBuf>>,
+
+    /// Colors from the `[theme]` table of `.erdtree.toml`
+    #[clap(skip)]
+    pub theme: Theme,
+
+    /// Icons from the `[icon_overrides]` table of `.erdtree.toml`
+    #[clap(skip)]
+    pub icon_overrides: icons::Overrides,
 }
 
 type Predicate = Result<Box<dyn Fn(&DirEntry) -> bool + Send + Sync + 'static>, Error>;
@@ -258,30 +678,137 @@ impl Context {
     /// Arguments provided will take precedence over config.
     pub fn try_init() -> Result<Self, Error> {
         let args = Self::compute_args()?;
-        Self::from_arg_matches(&args).map_err(Error::Config)
+        let mut ctx = Self::from_arg_matches(&args).map_err(Error::Config)?;
+
+        if ctx.print_command {
+            ctx.effective_command = Some(args::effective_command(&args));
+        }
+
+        if !ctx.no_config {
+            ctx.theme = config::toml::load_theme()?;
+            ctx.icon_overrides = config::toml::load_icon_overrides()?;
+        }
+
+        // Most icons are non-ASCII.
+        if ctx.ascii {
+            ctx.icons = false;
+        }
+
+        // Both sizes are measured in bytes so other metrics don't apply.
+        if ctx.both_sizes && !ctx.byte_metric() {
+            ctx.disk_usage = DiskUsage::Physical;
+        }
+
+        if ctx.dirs_last {
+            ctx.dir_order = dir::Order::Last;
+        }
+
+        match ctx.heat {
+            Some(heat::Metric::Size) => ctx.size_gradient = true,
+            Some(heat::Metric::Age) if ctx.size_gradient => return Err(Error::HeatAgeSizeGradient),
+            Some(heat::Metric::Age) | None => (),
+        }
+
+        // Plain byte counts take precedence over human-readable output.
+        if matches!(ctx.unit, PrefixKind::Bytes) {
+            ctx.human = false;
+            ctx.raw = true;
+        }
+
+        if ctx.interactive && !ctx.stdout_is_tty {
+            return Err(Error::InteractiveNotTty);
+        }
+
+        if ctx.watch && !ctx.stdout_is_tty {
+            return Err(Error::WatchNotTty);
+        }
+
+        if ctx.stdin {
+            if ctx.stdin_is_tty {
+                return Err(Error::StdinIsTty);
+            }
+
+            ctx.stdin_paths = Arc::new(stdin::read_paths().map_err(Error::Stdin)?);
+        }
+
+        if ctx.has_multiple_roots()
+            && (ctx.json
+                || ctx.csv
+                || ctx.html
+                || ctx.ncdu
+                || ctx.markdown
+                || ctx.snapshot.is_some()
+                || ctx.diff.is_some()
+                || ctx.interactive
+                || ctx.watch)
+        {
+            return Err(Error::MultipleRoots);
+        }
+
+        Ok(ctx)
     }
 
     /// Determines whether or not it's appropriate to display color in output based on
-    /// the Coloring, and whether or not stdout is connected to a tty.
+    /// the Coloring, whether or not stdout is connected to a tty, and whether or not output is
+    /// being written to a file.
     ///
-    /// If Coloring is Force then this will always evaluate to `false`.
+    /// `NO_COLOR` and `CLICOLOR_FORCE` are only consulted when Coloring is Auto, with the former
+    /// taking precedence, so that an explicit `--color` always wins.
     pub fn no_color(&self) -> bool {
-        if let Some(Some(var)) = color::NO_COLOR.get() {
-            return !var.is_empty();
+        match self.color {
+            Coloring::None => true,
+            Coloring::Force => false,
+            Coloring::Auto if color::no_color_set() => true,
+            Coloring::Auto if color::clicolor_force_set() => false,
+            Coloring::Auto => !self.stdout_is_tty || self.output.is_some(),
         }
+    }
 
+    /// Determines whether or not `--html` output should carry `LS_COLORS` as inline CSS. Unlike
+    /// [`Self::no_color`] this doesn't depend on stdout being a tty as the output is a document.
+    pub fn html_color(&self) -> bool {
         match self.color {
-            Coloring::Auto if !self.stdout_is_tty => true,
-            Coloring::None => true,
-            Coloring::Auto | Coloring::Force => false,
+            Coloring::None => false,
+            Coloring::Force => self.html,
+            Coloring::Auto => self.html && !color::no_color_set(),
         }
     }
 
-    /// Returns [Path] of the root directory to be traversed.
+    /// Determines whether or not icons should be printed without color. Icons follow
+    /// [`Self::no_color`] unless `--color-icons` is specified.
+    pub fn no_icon_color(&self) -> bool {
+        self.no_color() && !self.color_icons
+    }
+
+    /// Returns [Path] of the root directory to be traversed. If multiple directories were
+    /// provided this is the first of them; see [`Self::roots`].
     pub fn dir(&self) -> &Path {
-        self.dir
-            .as_ref()
-            .map_or_else(|| Path::new("."), |pb| pb.as_path())
+        self.dirs
+            .first()
+            .map_or_else(|| Path::new("."), PathBuf::as_path)
+    }
+
+    /// Returns a [Context] for each of the directories to be traversed whose sole root is that
+    /// directory. Only the first carries the effective command so that it's printed only once.
+    pub fn roots(&self) -> Vec<Self> {
+        if self.dirs.len() <= 1 {
+            return vec![self.clone()];
+        }
+
+        self.dirs
+            .iter()
+            .enumerate()
+            .map(|(i, dir)| Self {
+                dirs: vec![dir.clone()],
+                effective_command: self.effective_command.clone().filter(|_| i == 0),
+                ..self.clone()
+            })
+            .collect()
+    }
+
+    /// Whether or not several directories are to be traversed.
+    pub fn has_multiple_roots(&self) -> bool {
+        self.dirs.len() > 1
     }
 
     /// Returns canonical [Path] of the root directory to be traversed.
@@ -289,12 +816,27 @@ impl Context {
         std::fs::canonicalize(self.dir()).unwrap_or_else(|_| self.dir().to_path_buf())
     }
 
+    /// If the root is a symlink, returns the name of the symlink so that it can be displayed
+    /// in place of the name of its target which is what actually gets traversed.
+    pub fn root_alias(&self) -> Option<&OsStr> {
+        let is_symlink =
+            fs::symlink_metadata(self.dir()).is_ok_and(|md| md.file_type().is_symlink());
+
+        is_symlink.then(|| self.dir().file_name()).flatten()
+    }
+
     /// The max depth to print. Note that all directories are fully traversed to compute file
     /// sizes; this just determines how much to print.
     pub fn level(&self) -> usize {
         self.level.unwrap_or(usize::MAX)
     }
 
+    /// How many levels of directories are expanded when `--interactive` starts. Only the root is
+    /// expanded unless `--level` is provided.
+    pub fn interactive_level(&self) -> usize {
+        self.level.unwrap_or(1)
+    }
+
     /// Which timestamp type to use for long view; defaults to modified.
     #[cfg(unix)]
     pub fn time(&self) -> time::Stamp {
@@ -303,8 +845,8 @@ impl Context {
 
     /// Which format to use for the timestamp; default by default
     #[cfg(unix)]
-    pub fn time_format(&self) -> time::Format {
-        self.time_format.unwrap_or_default()
+    pub fn time_format(&self) -> &time::Format {
+        self.time_format.as_ref().unwrap_or(&time::Format::Default)
     }
 
     /// Which `FileType` to filter on; defaults to regular file.
@@ -318,32 +860,53 @@ impl Context {
     ///
     /// [`Tree`]: crate::tree::Tree
     pub fn regex_predicate(&self) -> Predicate {
-        let Some(pattern) = self.pattern.as_ref() else {
-            return Err(Error::PatternNotProvided);
-        };
-
-        let re = Regex::new(pattern)?;
+        let regexes = self.regexes()?;
 
         let file_type = self.file_type();
 
+        let match_dirs = self.match_dirs;
+
+        let matches_only = self.matches_only;
+
+        let root = self.dir_canonical();
+
         Ok(match file_type {
             file::Type::Dir => Box::new(move |dir_entry| {
+                // Matches are found by [`Self::match_predicate`] after the fact so that matching
+                // directories nested beneath ones that don't match aren't missed and so that the
+                // sizes of matching directories account for all of their contents.
+                if matches_only {
+                    return true;
+                }
+
                 let is_dir = dir_entry.file_type().map_or(false, |ft| ft.is_dir());
                 if is_dir {
-                    return Self::ancestor_regex_match(dir_entry.path(), &re, 0);
+                    return Self::ancestor_regex_match(dir_entry.path(), &regexes, 0);
                 }
 
-                Self::ancestor_regex_match(dir_entry.path(), &re, 1)
+                Self::ancestor_regex_match(dir_entry.path(), &regexes, 1)
             }),
 
             _ => Box::new(move |dir_entry| {
                 let entry_type = dir_entry.file_type();
                 let is_dir = entry_type.map_or(false, |ft| ft.is_dir());
 
-                if is_dir {
+                let name_matches = |path: &Path| {
+                    path.file_name().is_some_and(|name| {
+                        let name = name.to_string_lossy();
+                        regexes.iter().any(|re| re.is_match(&name))
+                    })
+                };
+
+                // Everything beneath a matching directory is kept along with it.
+                if match_dirs && Self::ancestor_match(dir_entry.path(), &root, name_matches) {
                     return true;
                 }
 
+                if is_dir {
+                    return !match_dirs || name_matches(dir_entry.path());
+                }
+
                 match file_type {
                     file::Type::File if entry_type.map_or(true, |ft| !ft.is_file()) => {
                         return false
@@ -353,102 +916,336 @@ impl Context {
                     },
                     _ => {},
                 }
-                let file_name = dir_entry.file_name().to_string_lossy();
-                re.is_match(&file_name)
+                name_matches(dir_entry.path())
             }),
         })
     }
 
-    /// Predicate used for filtering via globs and file-types.
+    /// Predicate used for filtering via globs and file-types. Each glob is compiled into its own
+    /// [Override] alongside whether or not it was negated so that entries matching any of them are
+    /// included.
     pub fn glob_predicate(&self) -> Predicate {
-        let mut builder = OverrideBuilder::new(self.dir());
-
-        let mut negated_glob = false;
+        let overrides = self.glob_overrides()?;
 
-        let overrides = {
-            if self.iglob {
-                builder.case_insensitive(true)?;
-            }
-
-            if let Some(ref glob) = self.pattern {
-                let trim = glob.trim_start();
-                negated_glob = trim.starts_with('!');
+        let file_type = self.file_type();
 
-                if negated_glob {
-                    builder.add(trim.trim_start_matches('!'))?;
-                } else {
-                    builder.add(trim)?;
-                }
-            }
+        let match_dirs = self.match_dirs;
 
-            builder.build()?
-        };
+        let matches_only = self.matches_only;
 
-        let file_type = self.file_type();
+        let root = self.dir_canonical();
 
         match file_type {
             file::Type::Dir => Ok(Box::new(move |dir_entry| {
+                if matches_only {
+                    return true;
+                }
+
                 let is_dir = dir_entry.file_type().map_or(false, |ft| ft.is_dir());
 
-                if is_dir {
-                    if negated_glob {
-                        return !Self::ancestor_glob_match(dir_entry.path(), &overrides, 0);
-                    }
-                    return Self::ancestor_glob_match(dir_entry.path(), &overrides, 0);
-                }
-                let matched = Self::ancestor_glob_match(dir_entry.path(), &overrides, 1);
+                let skip = usize::from(!is_dir);
 
-                if negated_glob {
-                    !matched
-                } else {
-                    matched
-                }
+                overrides.iter().any(|(ovr, negated_glob)| {
+                    Self::ancestor_glob_match(dir_entry.path(), ovr, skip) != *negated_glob
+                })
             })),
 
             _ => Ok(Box::new(move |dir_entry| {
                 let entry_type = dir_entry.file_type();
                 let is_dir = entry_type.map_or(false, |ft| ft.is_dir());
 
-                if is_dir {
+                if is_dir && !match_dirs {
                     return true;
                 }
 
-                match file_type {
-                    file::Type::File if entry_type.map_or(true, |ft| !ft.is_file()) => {
-                        return false
-                    },
-                    file::Type::Link if entry_type.map_or(true, |ft| !ft.is_symlink()) => {
-                        return false
-                    },
-                    _ => {},
-                }
+                let path_matches = |path: &Path, is_dir: bool| {
+                    overrides.iter().any(|(ovr, negated_glob)| {
+                        ovr.matched(path, is_dir).is_whitelist() != *negated_glob
+                    })
+                };
 
-                let matched = overrides.matched(dir_entry.path(), false);
+                // Everything beneath a matching directory is kept along with it.
+                if match_dirs
+                    && Self::ancestor_match(dir_entry.path(), &root, |dir| path_matches(dir, true))
+                {
+                    return true;
+                }
 
-                if negated_glob {
-                    !matched.is_whitelist()
-                } else {
-                    matched.is_whitelist()
+                if !is_dir {
+                    match file_type {
+                        file::Type::File if entry_type.map_or(true, |ft| !ft.is_file()) => {
+                            return false
+                        },
+                        file::Type::Link if entry_type.map_or(true, |ft| !ft.is_symlink()) => {
+                            return false
+                        },
+                        _ => {},
+                    }
                 }
+
+                path_matches(dir_entry.path(), is_dir)
             })),
         }
     }
 
-    /// Special override to toggle the visibility of the git directory.
-    pub fn no_git_override(&self) -> Result<Override, Error> {
+    /// Predicate used by `--matches-only` to determine whether an entry is itself a match rather
+    /// than having been included on account of one of its ancestors or to bridge a match back to
+    /// the root. Only entries of the file-type being searched for can be matches.
+    pub fn match_predicate(&self) -> Predicate {
+        let file_type = self.file_type();
+
+        let is_file_type = move |dir_entry: &DirEntry| {
+            dir_entry.file_type().is_some_and(|ft| match file_type {
+                file::Type::File => ft.is_file(),
+                file::Type::Dir => ft.is_dir(),
+                file::Type::Link => ft.is_symlink(),
+            })
+        };
+
+        if self.glob || self.iglob {
+            let overrides = self.glob_overrides()?;
+
+            return Ok(Box::new(move |dir_entry| {
+                let is_dir = dir_entry.file_type().is_some_and(|ft| ft.is_dir());
+
+                is_file_type(dir_entry)
+                    && overrides.iter().any(|(ovr, negated_glob)| {
+                        ovr.matched(dir_entry.path(), is_dir).is_whitelist() != *negated_glob
+                    })
+            }));
+        }
+
+        let regexes = self.regexes()?;
+
+        Ok(Box::new(move |dir_entry| {
+            let file_name = dir_entry.file_name().to_string_lossy();
+            is_file_type(dir_entry) && regexes.iter().any(|re| re.is_match(&file_name))
+        }))
+    }
+
+    /// Compiles each of the provided patterns into a [Regex], respecting `--iregex`.
+    fn regexes(&self) -> Result<Vec<Regex>, Error> {
+        if self.pattern.is_empty() {
+            return Err(Error::PatternNotProvided);
+        }
+
+        self.pattern
+            .iter()
+            .map(|pattern| {
+                RegexBuilder::new(pattern)
+                    .case_insensitive(self.iregex)
+                    .build()
+                    .map_err(|e| Error::InvalidPattern(pattern.clone(), e.to_string()))
+            })
+            .collect()
+    }
+
+    /// Compiles each of the provided globs into its own [Override] alongside whether or not it was
+    /// negated.
+    fn glob_overrides(&self) -> Result<Vec<(Override, bool)>, Error> {
+        self.pattern
+            .iter()
+            .map(|glob| {
+                let trim = glob.trim_start();
+                let negated_glob = trim.starts_with('!');
+
+                self.glob_override(trim.trim_start_matches('!'))
+                    .map(|ovr| (ovr, negated_glob))
+                    .map_err(|e| Error::InvalidPattern(glob.clone(), e.to_string()))
+            })
+            .collect()
+    }
+
+    /// Compiles a single glob into an [Override], respecting `--iglob`.
+    fn glob_override(&self, glob: &str) -> Result<Override, ignore::Error> {
         let mut builder = OverrideBuilder::new(self.dir());
 
+        if self.iglob {
+            builder.case_insensitive(true)?;
+        }
+
+        builder.add(glob)?;
+        builder.build()
+    }
+
+    /// Predicate used to exclude files whose names match any of the regular expressions provided
+    /// via `--exclude-pattern` or `--exclude-pattern-file`. Directories are always included so
+    /// that the structure of the tree is preserved.
+    pub fn exclude_predicate(&self) -> Predicate {
+        let mut patterns = self.exclude_pattern.clone();
+
+        if let Some(ref path) = self.exclude_pattern_file {
+            let contents = fs::read_to_string(path)
+                .map_err(|e| Error::ExcludePatternFile(path.display().to_string(), e))?;
+
+            let from_file = contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(String::from);
+
+            patterns.extend(from_file);
+        }
+
+        let regexes = patterns
+            .iter()
+            .map(|pattern| Regex::new(pattern))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Box::new(move |dir_entry| {
+            let is_dir = dir_entry.file_type().is_some_and(|ft| ft.is_dir());
+
+            if is_dir {
+                return true;
+            }
+
+            let file_name = dir_entry.file_name().to_string_lossy();
+            !regexes.iter().any(|re| re.is_match(&file_name))
+        }))
+    }
+
+    /// Whether or not `size`, as measured by the disk usage metric in use, falls within the bounds
+    /// provided by `--min-size` and `--max-size`.
+    pub fn size_in_range(&self, size: u64) -> bool {
+        self.min_size.map_or(true, |min| size >= min)
+            && self.max_size.map_or(true, |max| size <= max)
+    }
+
+    /// Whether or not files are being filtered by size.
+    pub const fn has_size_filter(&self) -> bool {
+        self.min_size.is_some() || self.max_size.is_some()
+    }
+
+    /// Whether or not a file last modified at `modified` falls within the bounds provided by
+    /// `--newer` and `--older`. Files whose modification time can't be read are excluded.
+    pub fn modified_in_range(&self, modified: Option<SystemTime>) -> bool {
+        let Some(age) = modified.map(age::elapsed) else {
+            return false;
+        };
+
+        self.newer.map_or(true, |newer| age <= newer)
+            && self.older.map_or(true, |older| age >= older)
+    }
+
+    /// Whether or not files are being filtered by modification time.
+    pub const fn has_time_filter(&self) -> bool {
+        self.newer.is_some() || self.older.is_some()
+    }
+
+    /// Predicate used to only include files whose extension, compared case-insensitively, is one of
+    /// those provided via `--extension`. Directories are always included so that their contents
+    /// may be matched.
+    pub fn extension_predicate(&self) -> Box<dyn Fn(&DirEntry) -> bool + Send + Sync + 'static> {
+        let extensions = self
+            .extension
+            .iter()
+            .map(|ext| ext.trim_start_matches('.').to_lowercase())
+            .collect::<HashSet<_>>();
+
+        Box::new(move |dir_entry| {
+            let is_dir = dir_entry.file_type().is_some_and(|ft| ft.is_dir());
+
+            if is_dir {
+                return true;
+            }
+
+            dir_entry
+                .path()
+                .extension()
+                .is_some_and(|ext| extensions.contains(&ext.to_string_lossy().to_lowercase()))
+        })
+    }
+
+    /// Predicate used by `--broken-only` to only include symlinks whose targets don't exist.
+    /// Directories are always included so that their contents may be matched.
+    pub fn broken_predicate() -> Box<dyn Fn(&DirEntry) -> bool + Send + Sync + 'static> {
+        Box::new(|dir_entry| {
+            let is_dir = dir_entry.file_type().is_some_and(|ft| ft.is_dir());
+
+            if is_dir {
+                return true;
+            }
+
+            dir_entry.path_is_symlink() && fs::metadata(dir_entry.path()).is_err()
+        })
+    }
+
+    /// Predicate used by `--stdin` to only include the paths read from stdin along with the
+    /// directories leading up to them. Paths are compared relative to the root.
+    pub fn stdin_predicate(&self) -> Box<dyn Fn(&DirEntry) -> bool + Send + Sync + 'static> {
+        let paths = stdin::relative_to(&self.stdin_paths, self.dir());
+        let root = self.dir_canonical();
+
+        Box::new(move |dir_entry| {
+            dir_entry
+                .path()
+                .strip_prefix(&root)
+                .is_ok_and(|rel| paths.contains(rel))
+        })
+    }
+
+    /// Whether or not any exclusion patterns were provided.
+    pub fn has_exclusions(&self) -> bool {
+        !self.exclude_pattern.is_empty() || self.exclude_pattern_file.is_some()
+    }
+
+    /// Overrides used to hide the git directory when `--no-git` is enabled as well as anything
+    /// matching the globs provided via `--exclude`, `--exclude-dir`, or `--ignore-file`.
+    /// Directories that are excluded aren't traversed.
+    pub fn overrides(&self) -> Result<Override, Error> {
+        let mut builder = OverrideBuilder::new(self.dir_canonical());
+
         if self.no_git {
             builder.add("!.git")?;
         }
 
+        for glob in &self.exclude {
+            let glob = glob.trim_start_matches('!');
+
+            builder
+                .add(&format!("!{glob}"))
+                .map_err(|e| Error::InvalidPattern(glob.to_owned(), e.to_string()))?;
+        }
+
+        // A trailing slash restricts a glob to directories.
+        for glob in &self.exclude_dir {
+            let glob = glob.trim_start_matches('!').trim_end_matches('/');
+
+            builder
+                .add(&format!("!{glob}/"))
+                .map_err(|e| Error::InvalidPattern(glob.to_owned(), e.to_string()))?;
+        }
+
+        for path in &self.ignore_file {
+            let display = path.display().to_string();
+
+            let contents =
+                fs::read_to_string(path).map_err(|e| Error::IgnoreFile(display.clone(), e))?;
+
+            let globs = contents
+                .lines()
+                .enumerate()
+                .map(|(i, line)| (i + 1, line.trim()))
+                .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+            for (line_number, glob) in globs {
+                builder.add(&format!("!{glob}")).map_err(|e| {
+                    Error::InvalidIgnoreFilePattern(display.clone(), line_number, e.to_string())
+                })?;
+            }
+        }
+
         Ok(builder.build()?)
     }
 
     /// Update column width properties.
     pub fn update_column_properties(&mut self, col_props: &column::Properties) {
-        self.max_size_width = col_props.max_size_width;
+        self.max_size_width = self.size_width.unwrap_or(col_props.max_size_width);
+        self.max_other_size_width = self.size_width.unwrap_or(col_props.max_other_size_width);
+        self.max_count_width = self.size_width.unwrap_or(col_props.max_count_width);
         self.max_size_unit_width = col_props.max_size_unit_width;
+        self.max_ext_width = col_props.max_ext_width;
+        self.max_file_size = col_props.max_file_size;
 
         #[cfg(unix)]
         {
@@ -457,13 +1254,23 @@ impl Context {
             self.max_nlink_width = col_props.max_nlink_width;
             self.max_block_width = col_props.max_block_width;
             self.max_ino_width = col_props.max_ino_width;
+            self.max_time_width = col_props.max_time_width;
         }
     }
 
-    /// Setter for `window_width` which is set to the current terminal emulator's window width.
+    /// Whether or not file names should be truncated to fit the terminal emulator's window. This
+    /// is the default when printing directly to a tty unless `--no-truncate` is provided.
+    pub const fn should_truncate(&self) -> bool {
+        !self.no_truncate && (self.truncate || self.stdout_is_tty && self.output.is_none())
+    }
+
+    /// Setter for `window_width` which is set to the current terminal emulator's window width
+    /// unless overridden by `--render-width`.
     #[inline]
     pub fn set_window_width(&mut self) {
-        self.window_width = crate::tty::get_window_width(self.stdout_is_tty);
+        self.window_width = self
+            .render_width
+            .or_else(|| crate::tty::get_window_width(self.stdout_is_tty));
     }
 
     /// Answers whether disk usage is asked to be reported in bytes.
@@ -481,13 +1288,27 @@ impl Context {
             .any(|c| ovr.matched(c, false).is_whitelist())
     }
 
-    /// Like [`Self::ancestor_glob_match`] except uses [Regex] rather than [Override].
+    /// Like [`Self::ancestor_glob_match`] except uses [Regex] rather than [Override]; a component
+    /// matches if any of `regexes` matches it.
     #[inline]
-    fn ancestor_regex_match(path: &Path, re: &Regex, skip: usize) -> bool {
-        path.components()
-            .rev()
-            .skip(skip)
-            .any(|comp| re.is_match(comp.as_os_str().to_string_lossy().borrow()))
+    fn ancestor_regex_match(path: &Path, regexes: &[Regex], skip: usize) -> bool {
+        path.components().rev().skip(skip).any(|comp| {
+            let comp = comp.as_os_str().to_string_lossy();
+            regexes.iter().any(|re| re.is_match(comp.borrow()))
+        })
+    }
+
+    /// Does `is_match` hold for any of the directories between `root` and `path`, excluding both?
+    /// Used by `--match-dirs` to keep the contents of matching directories.
+    #[inline]
+    fn ancestor_match<F>(path: &Path, root: &Path, is_match: F) -> bool
+    where
+        F: Fn(&Path) -> bool,
+    {
+        path.ancestors()
+            .skip(1)
+            .take_while(|dir| *dir != root)
+            .any(is_match)
     }
 
     /// The default number of threads to use for disk-reads and parallel processing.
@@ -0,0 +1,12 @@
+use clap::ValueEnum;
+
+/// How paths should be displayed when using a flat layout.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Type {
+    /// Paths are displayed relative to the root directory
+    #[default]
+    Relative,
+
+    /// Paths are displayed as canonical absolute paths
+    Absolute,
+}
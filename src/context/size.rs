@@ -0,0 +1,12 @@
+use clap::ValueEnum;
+
+/// Where the disk usage column is placed relative to the file name or path.
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Position {
+    /// Disk usage is displayed in an aligned column to the left of the file name
+    #[default]
+    Left,
+
+    /// Disk usage is displayed in parentheses to the right of the file name
+    Right,
+}
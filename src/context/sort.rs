@@ -15,19 +15,22 @@ pub enum Type {
     /// Sort entries by size largest to smallest, bottom to top
     Rsize,
 
-    /// Sort entries by newer to older Accessing Date
+    /// Sort entries by newer to older Accessing Date (alias: atime)
+    #[value(alias("atime"))]
     Access,
 
     /// Sort entries by older to newer Accessing Date
     Raccess,
 
-    /// Sort entries by newer to older Creation Date
+    /// Sort entries by newer to older Creation Date (alias: btime)
+    #[value(alias("btime"))]
     Create,
 
     /// Sort entries by older to newer Creation Date
     Rcreate,
 
-    /// Sort entries by newer to older Alteration Date
+    /// Sort entries by newer to older Alteration Date (alias: mtime)
+    #[value(alias("mtime"))]
     Mod,
 
     /// Sort entries by older to newer Alteration Date
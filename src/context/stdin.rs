@@ -0,0 +1,77 @@
+use std::{
+    collections::HashSet,
+    env, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// Reads in the paths provided via `--stdin` which are separated by NUL bytes if any are present
+/// and by newlines otherwise. Relative paths are resolved against the current directory.
+pub fn read_paths() -> io::Result<Vec<PathBuf>> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+
+    let cwd = env::current_dir()?;
+
+    Ok(split(&input).map(|entry| cwd.join(entry)).collect())
+}
+
+/// Makes each of `paths` relative to `root` so that they can be compared to the entries yielded
+/// by the walker no matter which path the walker takes to reach them, e.g. by way of a symlinked
+/// root or of symlinks followed beneath it. Paths are compared to `root` as it was given, then to
+/// its canonical form, and lastly resolved. Each path is kept along with all of its ancestors so
+/// that the directories leading up to it are traversed; paths outside of `root` are dropped.
+pub fn relative_to(paths: &[PathBuf], root: &Path) -> HashSet<PathBuf> {
+    let root = env::current_dir().map_or_else(|_| root.to_path_buf(), |cwd| cwd.join(root));
+    let canonical_root = fs::canonicalize(&root).unwrap_or_else(|_| root.clone());
+
+    let mut relative = HashSet::new();
+
+    for path in paths {
+        let rel = path
+            .strip_prefix(&root)
+            .or_else(|_| path.strip_prefix(&canonical_root))
+            .map(Path::to_path_buf)
+            .or_else(|_| {
+                resolve(path)
+                    .strip_prefix(&canonical_root)
+                    .map(Path::to_path_buf)
+            });
+
+        if let Ok(rel) = rel {
+            relative.extend(rel.ancestors().map(Path::to_path_buf));
+        }
+    }
+
+    relative
+}
+
+/// Splits `input` on NUL bytes if there are any, e.g. from `fd -0`, and on newlines otherwise.
+fn split(input: &str) -> impl Iterator<Item = &str> {
+    let separator = if input.contains('\0') { '\0' } else { '\n' };
+
+    input
+        .split(separator)
+        .map(|entry| entry.trim_end_matches('\r'))
+        .filter(|entry| !entry.is_empty())
+}
+
+/// Only the parent of `path` is canonicalized so that symlinks, broken or not, aren't resolved
+/// which keeps `path` in line with the paths yielded by the walker.
+fn resolve(path: &Path) -> PathBuf {
+    let canonical = match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => fs::canonicalize(parent).map(|parent| parent.join(name)),
+        _ => fs::canonicalize(path),
+    };
+
+    canonical.unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[test]
+fn test_split() {
+    let newlines = split("a.txt\nb/c.txt\r\n\nd\n").collect::<Vec<_>>();
+    assert_eq!(newlines, ["a.txt", "b/c.txt", "d"]);
+
+    let nul = split("a b.txt\0line\nbreak\0").collect::<Vec<_>>();
+    assert_eq!(nul, ["a b.txt", "line\nbreak"]);
+}
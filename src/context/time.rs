@@ -1,3 +1,7 @@
+use chrono::{
+    format::{Item, StrftimeItems},
+    DateTime, Local,
+};
 use clap::ValueEnum;
 
 /// Different types of timestamps available in long-view.
@@ -18,7 +22,7 @@ pub enum Stamp {
 }
 
 /// Different formatting options for timestamps
-#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub enum Format {
     /// Timestamp formatted following the iso8601, with slight differences and the time-zone omitted
     Iso,
@@ -29,7 +33,86 @@ pub enum Format {
     /// Timestamp only shows date without time in YYYY-MM-DD format
     Short,
 
-    /// Timestamp is shown in DD MMM HH:MM format
+    /// Timestamp is shown like `ls -l`: MMM DD HH:MM for recent files and MMM DD YYYY for files
+    /// older than six months
     #[default]
     Default,
+
+    /// Timestamp is shown using a strftime-style format string
+    Custom(String),
+}
+
+impl Format {
+    /// Parses one of the named formats or a strftime-style format string prefixed with `+`,
+    /// e.g. `+%Y-%m-%d %H:%M`, following the conventions of `ls --time-style`.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        if let Some(custom) = s.strip_prefix('+') {
+            let is_valid = StrftimeItems::new(custom).all(|item| !matches!(item, Item::Error));
+
+            if !is_valid || custom.is_empty() {
+                return Err(format!("invalid strftime format string '{custom}'"));
+            }
+
+            return Ok(Self::Custom(custom.to_owned()));
+        }
+
+        match s {
+            "iso" => Ok(Self::Iso),
+            "iso-strict" => Ok(Self::IsoStrict),
+            "short" => Ok(Self::Short),
+            "default" => Ok(Self::Default),
+            _ => Err(String::from(
+                "expected one of 'iso', 'iso-strict', 'short', 'default', or '+FORMAT'",
+            )),
+        }
+    }
+
+    /// Formats `dt` according to `self`.
+    pub fn apply(&self, dt: DateTime<Local>) -> String {
+        let delayed_format = match self {
+            Self::Default if Self::is_recent(dt) => dt.format("%b %d %H:%M"),
+            Self::Default => dt.format("%b %d  %Y"),
+            Self::Iso => dt.format("%Y-%m-%d %H:%M:%S"),
+            Self::IsoStrict => dt.format("%Y-%m-%dT%H:%M:%S%Z"),
+            Self::Short => dt.format("%Y-%m-%d"),
+            Self::Custom(ref fmt) => dt.format(fmt),
+        };
+
+        delayed_format.to_string()
+    }
+
+    /// Whether or not `dt` is within the past six months, in which case `ls -l` shows the time of
+    /// day rather than the year. Timestamps in the future are not considered recent.
+    fn is_recent(dt: DateTime<Local>) -> bool {
+        const SIX_MONTHS_SECS: i64 = 365 * 24 * 60 * 60 / 2;
+
+        let age = Local::now().signed_duration_since(dt).num_seconds();
+
+        (0..SIX_MONTHS_SECS).contains(&age)
+    }
+}
+
+#[test]
+fn test_parse_format() {
+    assert_eq!(Format::parse("iso"), Ok(Format::Iso));
+    assert_eq!(Format::parse("default"), Ok(Format::Default));
+    assert_eq!(
+        Format::parse("+%Y/%m/%d"),
+        Ok(Format::Custom(String::from("%Y/%m/%d")))
+    );
+    assert!(Format::parse("+%Q").is_err());
+    assert!(Format::parse("+").is_err());
+    assert!(Format::parse("%Y").is_err());
+}
+
+#[test]
+fn test_apply_format() {
+    use chrono::TimeZone;
+
+    let dt = Local.with_ymd_and_hms(2023, 7, 1, 4, 57, 0).unwrap();
+
+    assert_eq!(Format::Default.apply(dt), "Jul 01  2023");
+    assert_eq!(Format::Iso.apply(dt), "2023-07-01 04:57:00");
+    assert_eq!(Format::Short.apply(dt), "2023-07-01");
+    assert_eq!(Format::Custom(String::from("%A")).apply(dt), "Saturday");
 }
@@ -1,9 +1,11 @@
 use super::super::units::{BinPrefix, PrefixKind, SiPrefix, UnitPrefix};
+use crate::utils;
 use filesize::PathExt;
 use std::{
     cell::{Ref, RefCell},
     fmt::{self, Display},
     fs::Metadata,
+    ops::AddAssign,
     path::Path,
 };
 
@@ -16,6 +18,9 @@ pub struct Metric {
     kind: MetricKind,
     prefix_kind: PrefixKind,
 
+    /// Whether plain byte counts are printed with thousands separators.
+    grouped: bool,
+
     /// To prevent allocating the same string twice. We allocate the first time
     /// in [`crate::tree::Tree::update_column_properties`] in order to compute the max column width for
     /// human-readable size and cache it. It will then be used again when preparing the output.
@@ -45,6 +50,7 @@ impl Metric {
             human_readable,
             kind,
             prefix_kind,
+            grouped: false,
             cached_display: RefCell::default(),
         }
     }
@@ -56,6 +62,7 @@ impl Metric {
             human_readable,
             kind: MetricKind::Logical,
             prefix_kind,
+            grouped: false,
             cached_display: RefCell::default(),
         }
     }
@@ -67,11 +74,15 @@ impl Metric {
             human_readable,
             kind: MetricKind::Physical,
             prefix_kind,
+            grouped: false,
             cached_display: RefCell::default(),
         }
     }
 
     /// Initializes a [Metric] that stores the total amount of bytes used to store a file on disk.
+    /// On Unix this is derived from the number of 512-byte blocks allocated to the file, i.e.
+    /// `st_blocks`, so sparse files and files smaller than a block are reported like `du` would.
+    /// Elsewhere the logical size is used if the size on disk can't be determined.
     pub fn init_physical(
         path: &Path,
         metadata: &Metadata,
@@ -86,16 +97,40 @@ impl Metric {
             human_readable,
             kind,
             prefix_kind,
+            grouped: false,
             cached_display: RefCell::default(),
         }
     }
 
+    /// Separates every three digits of plain byte counts with a comma if `grouped` is `true`.
+    pub const fn with_grouping(mut self, grouped: bool) -> Self {
+        self.grouped = grouped;
+        self
+    }
+
+    /// The exact number of bytes, grouped by thousands if requested.
+    fn plain(&self) -> String {
+        if self.grouped {
+            utils::group_digits(self.value)
+        } else {
+            self.value.to_string()
+        }
+    }
+
     /// Returns an immutable borrow of the `cached_display`.
     pub fn cached_display(&self) -> Ref<'_, String> {
         self.cached_display.borrow()
     }
 }
 
+/// Adds bytes to the [Metric], invalidating `cached_display`.
+impl AddAssign<u64> for Metric {
+    fn add_assign(&mut self, rhs: u64) {
+        self.value += rhs;
+        self.cached_display.take();
+    }
+}
+
 impl Display for Metric {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         {
@@ -114,14 +149,14 @@ impl Display for Metric {
                     let unit = SiPrefix::from(self.value);
 
                     if unit == SiPrefix::Base {
-                        format!("{} {unit}", self.value)
+                        format!("{} {unit}", self.plain())
                     } else {
                         let base_value = unit.base_value();
                         let size = value / (base_value as f64);
                         format!("{size:.1} {unit}")
                     }
                 } else {
-                    format!("{} {}", self.value, SiPrefix::Base)
+                    format!("{} {}", self.plain(), SiPrefix::Base)
                 }
             },
             PrefixKind::Bin => {
@@ -129,16 +164,17 @@ impl Display for Metric {
                     let unit = BinPrefix::from(self.value);
 
                     if unit == BinPrefix::Base {
-                        format!("{} {unit}", self.value)
+                        format!("{} {unit}", self.plain())
                     } else {
                         let base_value = unit.base_value();
                         let size = value / (base_value as f64);
                         format!("{size:.1} {unit}")
                     }
                 } else {
-                    format!("{} {}", self.value, BinPrefix::Base)
+                    format!("{} {}", self.plain(), BinPrefix::Base)
                 }
             },
+            PrefixKind::Bytes => format!("{} {}", self.plain(), BinPrefix::Base),
         };
 
         write!(f, "{display}")?;
@@ -156,6 +192,7 @@ fn test_metric() {
         kind: MetricKind::Logical,
         human_readable: false,
         prefix_kind: PrefixKind::Bin,
+        grouped: false,
         cached_display: RefCell::<String>::default(),
     };
     assert_eq!(format!("{metric}"), "100 B");
@@ -165,6 +202,7 @@ fn test_metric() {
         kind: MetricKind::Logical,
         human_readable: true,
         prefix_kind: PrefixKind::Si,
+        grouped: false,
         cached_display: RefCell::<String>::default(),
     };
     assert_eq!(format!("{metric}"), "1.0 KB");
@@ -174,6 +212,7 @@ fn test_metric() {
         kind: MetricKind::Logical,
         human_readable: true,
         prefix_kind: PrefixKind::Bin,
+        grouped: false,
         cached_display: RefCell::<String>::default(),
     };
     assert_eq!(format!("{metric}"), "1000 B");
@@ -183,6 +222,7 @@ fn test_metric() {
         kind: MetricKind::Logical,
         human_readable: true,
         prefix_kind: PrefixKind::Bin,
+        grouped: false,
         cached_display: RefCell::<String>::default(),
     };
     assert_eq!(format!("{metric}"), "1.0 KiB");
@@ -192,6 +232,7 @@ fn test_metric() {
         kind: MetricKind::Logical,
         human_readable: true,
         prefix_kind: PrefixKind::Bin,
+        grouped: false,
         cached_display: RefCell::<String>::default(),
     };
     assert_eq!(format!("{metric}"), "1.0 MiB");
@@ -201,7 +242,33 @@ fn test_metric() {
         kind: MetricKind::Logical,
         human_readable: false,
         prefix_kind: PrefixKind::Bin,
+        grouped: false,
         cached_display: RefCell::<String>::default(),
     };
     assert_eq!(format!("{metric}"), "123454 B");
+
+    let metric = Metric {
+        value: 1_234_567,
+        kind: MetricKind::Logical,
+        human_readable: false,
+        prefix_kind: PrefixKind::Bytes,
+        grouped: true,
+        cached_display: RefCell::<String>::default(),
+    };
+    assert_eq!(format!("{metric}"), "1,234,567 B");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_physical_sparse() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    file.as_file().set_len(1024 * 1024 * 1024).unwrap();
+
+    let metadata = file.as_file().metadata().unwrap();
+
+    let logical = Metric::init_logical(&metadata, PrefixKind::Bin, false);
+    let physical = Metric::init_physical(file.path(), &metadata, PrefixKind::Bin, false);
+
+    assert_eq!(logical.value, 1024 * 1024 * 1024);
+    assert!(physical.value < logical.value / 1024);
 }
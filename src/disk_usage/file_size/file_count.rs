@@ -0,0 +1,30 @@
+use std::{
+    convert::From,
+    fmt::{self, Display},
+};
+
+/// Concerned with measuring file size by the number of files, such that every file counts as one
+/// and directories amount to the total number of files they contain.
+#[derive(Default)]
+pub struct Metric {
+    pub value: u64,
+}
+
+impl Metric {
+    /// Initializes a [Metric] for a single file.
+    pub const fn init() -> Self {
+        Self { value: 1 }
+    }
+}
+
+impl From<u64> for Metric {
+    fn from(value: u64) -> Self {
+        Self { value }
+    }
+}
+
+impl Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <u64 as Display>::fmt(&self.value, f)
+    }
+}
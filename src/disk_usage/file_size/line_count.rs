@@ -5,6 +5,10 @@ use std::{
     path::Path,
 };
 
+/// Amount of bytes at the start of a file that are inspected for NUL bytes to determine whether or
+/// not it's binary, the same heuristic used by `git`.
+const BINARY_SNIFF_LEN: usize = 8000;
+
 /// Concerned with measuring file size using line count as a metric.
 #[derive(Default)]
 pub struct Metric {
@@ -14,9 +18,16 @@ pub struct Metric {
 impl Metric {
     /// Reads in contents of a file given by `path` and attempts to compute the total number of
     /// lines in that file. If a file is not UTF-8 encoded as in the case of a binary jpeg file
-    /// then `None` will be returned.
+    /// then `None` will be returned. Files that contain a NUL byte near the start are considered
+    /// binary as well even if they happen to be valid UTF-8.
     pub fn init(path: impl AsRef<Path>) -> Option<Self> {
-        let data = fs::read_to_string(path.as_ref()).ok()?;
+        let bytes = fs::read(path.as_ref()).ok()?;
+
+        if bytes.iter().take(BINARY_SNIFF_LEN).any(|b| *b == 0) {
+            return None;
+        }
+
+        let data = std::str::from_utf8(&bytes).ok()?;
 
         let lines = data.lines().count();
 
@@ -43,3 +54,15 @@ fn test_line_count() {
 
     assert_eq!(metric.value, 4);
 }
+
+#[test]
+fn test_line_count_binary() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = tempfile::TempDir::new()?;
+    let path = tmp.path().join("binary");
+
+    fs::write(&path, b"ELF\0\nnot\nreally\ntext\n")?;
+
+    assert!(Metric::init(&path).is_none());
+
+    Ok(())
+}
@@ -3,7 +3,7 @@ use clap::ValueEnum;
 use std::{
     convert::From,
     fmt::{self, Display},
-    ops::AddAssign,
+    ops::{Add, AddAssign},
 };
 
 /// Concerned with measuring file size in blocks.
@@ -19,6 +19,9 @@ pub mod line_count;
 /// Concerned with measuring file size by word count.
 pub mod word_count;
 
+/// Concerned with measuring file size by the number of files.
+pub mod file_count;
+
 #[cfg(unix)]
 pub const BLOCK_SIZE_BYTES: u16 = 512;
 
@@ -27,6 +30,7 @@ pub enum FileSize {
     Word(word_count::Metric),
     Line(line_count::Metric),
     Byte(byte::Metric),
+    File(file_count::Metric),
     #[cfg(unix)]
     Block(block::Metric),
 }
@@ -47,6 +51,9 @@ pub enum DiskUsage {
     /// How many total words a file contains
     Word,
 
+    /// How many files a directory contains, i.e. each file counts as one
+    File,
+
     /// How many blocks are allocated to store the file
     #[cfg(unix)]
     Block,
@@ -60,6 +67,7 @@ impl FileSize {
             Self::Byte(metric) => metric.value,
             Self::Line(metric) => metric.value,
             Self::Word(metric) => metric.value,
+            Self::File(metric) => metric.value,
 
             #[cfg(unix)]
             Self::Block(metric) => metric.value,
@@ -67,28 +75,61 @@ impl FileSize {
     }
 }
 
-impl AddAssign<&Self> for FileSize {
-    fn add_assign(&mut self, rhs: &Self) {
+/// Adds `rhs` to the raw value of `self`, preserving its metric, units, and scale.
+impl AddAssign<u64> for FileSize {
+    fn add_assign(&mut self, rhs: u64) {
         match self {
-            Self::Byte(metric) => metric.value += rhs.value(),
-            Self::Line(metric) => metric.value += rhs.value(),
-            Self::Word(metric) => metric.value += rhs.value(),
+            Self::Byte(metric) => *metric += rhs,
+            Self::Line(metric) => metric.value += rhs,
+            Self::Word(metric) => metric.value += rhs,
+            Self::File(metric) => metric.value += rhs,
 
             #[cfg(unix)]
-            Self::Block(metric) => metric.value += rhs.value(),
+            Self::Block(metric) => metric.value += rhs,
         }
     }
 }
 
+/// Adds the raw value of `rhs` to `self`. The metric, units, and scale of `self` are preserved
+/// regardless of those of `rhs`.
+impl AddAssign<&Self> for FileSize {
+    fn add_assign(&mut self, rhs: &Self) {
+        *self += rhs.value();
+    }
+}
+
+impl AddAssign for FileSize {
+    fn add_assign(&mut self, rhs: Self) {
+        *self += &rhs;
+    }
+}
+
+/// See [`AddAssign`] for [`FileSize`].
+impl Add for FileSize {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += &rhs;
+        self
+    }
+}
+
 impl From<&Context> for FileSize {
     fn from(ctx: &Context) -> Self {
-        use DiskUsage::{Line, Logical, Physical, Word};
+        use DiskUsage::{File, Line, Logical, Physical, Word};
 
         match ctx.disk_usage {
-            Logical => Self::Byte(byte::Metric::init_empty_logical(ctx.human, ctx.unit)),
-            Physical => Self::Byte(byte::Metric::init_empty_physical(ctx.human, ctx.unit)),
+            Logical => Self::Byte(
+                byte::Metric::init_empty_logical(ctx.human, ctx.unit)
+                    .with_grouping(ctx.group_digits),
+            ),
+            Physical => Self::Byte(
+                byte::Metric::init_empty_physical(ctx.human, ctx.unit)
+                    .with_grouping(ctx.group_digits),
+            ),
             Line => Self::Line(line_count::Metric::default()),
             Word => Self::Word(word_count::Metric::default()),
+            File => Self::File(file_count::Metric::default()),
 
             #[cfg(unix)]
             DiskUsage::Block => Self::Block(block::Metric::default()),
@@ -96,11 +137,29 @@ impl From<&Context> for FileSize {
     }
 }
 
+impl FileSize {
+    /// Initializes an empty [`FileSize`] measured in whichever of logical or physical bytes isn't
+    /// selected by `--disk-usage`. Used for the additional column of `--both-sizes`.
+    pub fn other_from(ctx: &Context) -> Self {
+        match ctx.disk_usage {
+            DiskUsage::Logical => Self::Byte(
+                byte::Metric::init_empty_physical(ctx.human, ctx.unit)
+                    .with_grouping(ctx.group_digits),
+            ),
+            _ => Self::Byte(
+                byte::Metric::init_empty_logical(ctx.human, ctx.unit)
+                    .with_grouping(ctx.group_digits),
+            ),
+        }
+    }
+}
+
 impl Display for FileSize {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Word(metric) => write!(f, "{metric}"),
             Self::Line(metric) => write!(f, "{metric}"),
+            Self::File(metric) => write!(f, "{metric}"),
             Self::Byte(metric) => write!(f, "{metric}"),
 
             #[cfg(unix)]
@@ -108,3 +167,28 @@ impl Display for FileSize {
         }
     }
 }
+
+#[test]
+fn test_file_size_add() {
+    use super::units::PrefixKind;
+
+    let bin = FileSize::Byte(byte::Metric::init_empty_logical(true, PrefixKind::Bin));
+    let si = FileSize::Byte(byte::Metric::init_empty_physical(false, PrefixKind::Si));
+    let lines = FileSize::Line(line_count::Metric::from(1024));
+
+    let total = bin + lines;
+    assert_eq!(total.value(), 1024);
+    assert_eq!(format!("{total}"), "1.0 KiB");
+
+    let mut total = si + total;
+    assert_eq!(total.value(), 1024);
+    assert_eq!(format!("{total}"), "1024 B");
+
+    total += FileSize::Word(word_count::Metric::from(1));
+    assert_eq!(total.value(), 1025);
+    assert_eq!(
+        format!("{total}"),
+        "1025 B",
+        "Expected cached display to be invalidated"
+    );
+}
@@ -13,6 +13,10 @@ pub enum PrefixKind {
 
     /// Displays disk usage using SI prefixes.
     Si,
+
+    /// Displays disk usage as a plain count of bytes, even if human-readable output is requested.
+    #[value(alias = "none")]
+    Bytes,
 }
 
 /// Binary prefixes.
@@ -127,6 +131,39 @@ impl From<u64> for SiPrefix {
     }
 }
 
+/// Parses a size such as `500`, `10K`, `1.5MiB`, or `2GB` into a total number of bytes. Binary
+/// prefixes are assumed if only a single letter such as `K` is given while a trailing `B` without
+/// an `i`, e.g. `KB`, denotes an SI prefix. Units are case-insensitive. Values without units are
+/// taken as-is which allows the parsed value to also be used with non-byte disk usage metrics.
+pub fn parse_size(input: &str) -> Result<u64, String> {
+    let input = input.trim();
+
+    let split_at = input
+        .find(|ch: char| !(ch.is_ascii_digit() || ch == '.'))
+        .unwrap_or(input.len());
+
+    let (num, unit) = input.split_at(split_at);
+
+    let value = num
+        .parse::<f64>()
+        .map_err(|_e| format!("invalid size '{input}'"))?;
+
+    let multiplier = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "ki" | "kib" => BinPrefix::Kibi.base_value(),
+        "m" | "mi" | "mib" => BinPrefix::Mebi.base_value(),
+        "g" | "gi" | "gib" => BinPrefix::Gibi.base_value(),
+        "t" | "ti" | "tib" => BinPrefix::Tebi.base_value(),
+        "kb" => SiPrefix::Kilo.base_value(),
+        "mb" => SiPrefix::Mega.base_value(),
+        "gb" => SiPrefix::Giga.base_value(),
+        "tb" => SiPrefix::Tera.base_value(),
+        _ => return Err(format!("invalid unit in size '{input}'")),
+    };
+
+    Ok((value * multiplier as f64) as u64)
+}
+
 impl Display for BinPrefix {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.as_str())
@@ -138,3 +175,16 @@ impl Display for SiPrefix {
         write!(f, "{}", self.as_str())
     }
 }
+
+#[test]
+fn test_parse_size() {
+    assert_eq!(parse_size("500"), Ok(500));
+    assert_eq!(parse_size("500B"), Ok(500));
+    assert_eq!(parse_size("10K"), Ok(10 * 1024));
+    assert_eq!(parse_size("10kib"), Ok(10 * 1024));
+    assert_eq!(parse_size("10KB"), Ok(10_000));
+    assert_eq!(parse_size("1.5MiB"), Ok(3 * 2_u64.pow(19)));
+    assert_eq!(parse_size("2 GB"), Ok(2 * 10_u64.pow(9)));
+    assert!(parse_size("ten").is_err());
+    assert!(parse_size("10 parsecs").is_err());
+}
@@ -1,7 +1,12 @@
-use std::{convert::TryFrom, fs::Metadata};
+use std::{
+    convert::TryFrom,
+    fs::Metadata,
+    hash::{Hash, Hasher},
+};
 
-/// Represents a file's underlying inode.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// Represents a file's underlying inode. Inodes are identified by their device and inode numbers
+/// alone, so the same inode is considered equal to itself even if its amount of links changes.
+#[derive(Clone, Copy, Debug)]
 pub struct Inode {
     pub ino: u64,
     pub dev: u64,
@@ -15,6 +20,20 @@ impl Inode {
     }
 }
 
+impl PartialEq for Inode {
+    fn eq(&self, other: &Self) -> bool {
+        (self.dev, self.ino) == (other.dev, other.ino)
+    }
+}
+
+impl Eq for Inode {}
+
+impl Hash for Inode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.dev, self.ino).hash(state);
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 #[error("Insufficient information to compute inode")]
 pub struct Error;
@@ -49,3 +68,10 @@ impl TryFrom<&Metadata> for Inode {
         Err(Error {})
     }
 }
+
+#[test]
+fn test_inode_identity() {
+    assert_eq!(Inode::new(42, 1, 2), Inode::new(42, 1, 3));
+    assert_ne!(Inode::new(42, 1, 2), Inode::new(42, 2, 2));
+    assert_ne!(Inode::new(42, 1, 2), Inode::new(43, 1, 2));
+}
@@ -1,9 +1,21 @@
 use errno::{errno, set_errno, Errno};
-use std::{ffi::CStr, fs::Metadata, os::unix::fs::MetadataExt};
+use std::{
+    collections::BTreeMap,
+    ffi::CStr,
+    fs::Metadata,
+    os::unix::fs::MetadataExt,
+    sync::{Mutex, PoisonError},
+};
 
 type Owner = String;
 type Group = String;
 
+/// Names of users that have already been resolved keyed by user ID.
+static OWNERS: Mutex<BTreeMap<libc::uid_t, Owner>> = Mutex::new(BTreeMap::new());
+
+/// Names of groups that have already been resolved keyed by group ID.
+static GROUPS: Mutex<BTreeMap<libc::gid_t, Group>> = Mutex::new(BTreeMap::new());
+
 impl UserGroupInfo for Metadata {}
 
 /// Trait that allows for files to query their owner and group.
@@ -16,17 +28,45 @@ pub trait UserGroupInfo: MetadataExt {
         }
     }
 
-    /// Attempts to query both the owner and group of the implementor.
-    fn try_get_owner_and_group(&self) -> Result<(Owner, Group), Error> {
+    /// Attempts to query the group of the implementor.
+    fn try_get_group(&self) -> Result<String, Error> {
         unsafe {
-            let uid = self.uid();
             let gid = self.gid();
-            let user = try_get_user(uid)?;
-            let group = try_get_group(gid)?;
-
-            Ok((user, group))
+            try_get_group(gid)
         }
     }
+
+    /// Queries the owner of the implementor, falling back to the numeric user ID if the name
+    /// can't be resolved. Names are cached so that the user database is only consulted once per
+    /// user.
+    fn owner_or_uid(&self) -> Owner {
+        cached(&OWNERS, self.uid(), || {
+            self.try_get_owner()
+                .unwrap_or_else(|_| self.uid().to_string())
+        })
+    }
+
+    /// Queries the group of the implementor, falling back to the numeric group ID if the name
+    /// can't be resolved. Names are cached so that the group database is only consulted once per
+    /// group.
+    fn group_or_gid(&self) -> Group {
+        cached(&GROUPS, self.gid(), || {
+            self.try_get_group()
+                .unwrap_or_else(|_| self.gid().to_string())
+        })
+    }
+}
+
+/// Gets the name associated with `id` from `cache`, resolving it with `resolve` if it's missing.
+/// The lock is held while resolving which also serializes calls into `getpwuid` and `getgrgid` as
+/// they aren't thread-safe.
+fn cached<K: Ord>(
+    cache: &Mutex<BTreeMap<K, String>>,
+    id: K,
+    resolve: impl FnOnce() -> String,
+) -> String {
+    let mut cache = cache.lock().unwrap_or_else(PoisonError::into_inner);
+    cache.entry(id).or_insert_with(resolve).clone()
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -82,3 +122,12 @@ unsafe fn try_get_user(uid: libc::uid_t) -> Result<String, Error> {
 
     Ok(CStr::from_ptr(pw_name).to_string_lossy().to_string())
 }
+
+#[test]
+fn test_cached() {
+    let cache = Mutex::new(BTreeMap::new());
+
+    assert_eq!(cached(&cache, 1000, || String::from("cthulhu")), "cthulhu");
+    assert_eq!(cached(&cache, 1000, || unreachable!()), "cthulhu");
+    assert_eq!(cached(&cache, 0, || String::from("root")), "root");
+}
@@ -0,0 +1,178 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// State of a file relative to the index and to the working tree as reported by
+/// `git status --porcelain`, e.g. `M` for modified and `A` for added. A space denotes that there
+/// are no changes. Untracked files are tracked separately so that they aren't conflated with
+/// changes to tracked files when combined into the status of a directory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Status {
+    /// Changes that have been staged.
+    pub staged: char,
+
+    /// Changes in the working tree that haven't been staged.
+    pub unstaged: char,
+
+    /// Whether the file is untracked or, for directories, contains untracked files.
+    pub untracked: bool,
+}
+
+/// Status of every changed file in a repository keyed by absolute path. Directories are assigned
+/// the combined status of all of the changed files beneath them.
+pub struct Statuses {
+    statuses: HashMap<PathBuf, Status>,
+}
+
+impl Status {
+    /// A file without any changes.
+    pub const CLEAN: Self = Self {
+        staged: ' ',
+        unstaged: ' ',
+        untracked: false,
+    };
+
+    /// A file that isn't tracked.
+    pub const UNTRACKED: Self = Self {
+        staged: ' ',
+        unstaged: ' ',
+        untracked: true,
+    };
+
+    /// The staged and unstaged markers to display. Untracked files, as well as directories whose
+    /// only changes are untracked files, are marked `??` as they are by `git status --short`.
+    pub const fn markers(self) -> (char, char) {
+        if self.untracked && self.staged == ' ' && self.unstaged == ' ' {
+            ('?', '?')
+        } else {
+            (self.staged, self.unstaged)
+        }
+    }
+
+    /// Combines two statuses for the sake of a directory containing files with either. Changes of
+    /// different kinds are reported as modifications.
+    fn merge(self, other: Self) -> Self {
+        let merge = |a: char, b: char| match (a, b) {
+            (' ', b) => b,
+            (a, ' ') => a,
+            (a, b) if a == b => a,
+            _ => 'M',
+        };
+
+        Self {
+            staged: merge(self.staged, other.staged),
+            unstaged: merge(self.unstaged, other.unstaged),
+            untracked: self.untracked || other.untracked,
+        }
+    }
+}
+
+impl Default for Status {
+    fn default() -> Self {
+        Self::CLEAN
+    }
+}
+
+impl Statuses {
+    /// Runs `git status` once for the repository that `dir` belongs to. Returns `None` if `dir`
+    /// isn't inside of a repository or if `git` isn't available.
+    pub fn query(dir: &Path) -> Option<Self> {
+        let toplevel = git(dir, &["rev-parse", "--show-toplevel"])?;
+        let toplevel = Path::new(toplevel.trim_end());
+
+        let porcelain = git(
+            toplevel,
+            &["status", "--porcelain=v1", "-z", "--untracked-files=all"],
+        )?;
+
+        Some(Self::parse(toplevel, &porcelain))
+    }
+
+    /// Gets the status of the file or directory at `path` if it has any changes.
+    pub fn get(&self, path: &Path) -> Option<Status> {
+        self.statuses.get(path).copied()
+    }
+
+    /// Parses the NUL-separated output of `git status --porcelain=v1 -z` whose paths are relative
+    /// to `toplevel`.
+    fn parse(toplevel: &Path, porcelain: &str) -> Self {
+        let mut statuses = HashMap::new();
+        let mut entries = porcelain.split('\0');
+
+        while let Some(entry) = entries.next() {
+            let mut chars = entry.chars();
+
+            let (Some(staged), Some(unstaged), Some(' ')) =
+                (chars.next(), chars.next(), chars.next())
+            else {
+                continue;
+            };
+
+            // Renames and copies are followed by the original path which is of no interest.
+            if matches!(staged, 'R' | 'C') {
+                entries.next();
+            }
+
+            let status = if (staged, unstaged) == ('?', '?') {
+                Status::UNTRACKED
+            } else {
+                Status {
+                    staged,
+                    unstaged,
+                    untracked: false,
+                }
+            };
+            let path = toplevel.join(chars.as_str());
+
+            for ancestor in path.ancestors().take_while(|p| p.starts_with(toplevel)) {
+                statuses
+                    .entry(ancestor.to_path_buf())
+                    .and_modify(|s: &mut Status| *s = s.merge(status))
+                    .or_insert(status);
+            }
+        }
+
+        Self { statuses }
+    }
+}
+
+/// Runs `git` in `dir` with `args`, returning its standard output if it exited successfully.
+fn git(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[test]
+fn test_parse() {
+    let toplevel = Path::new("/repo");
+    let porcelain = concat!(
+        "M  src/main.rs\0 M src/lib.rs\0?? notes.txt\0R  new.rs\0old.rs\0",
+        " M docs/a.md\0?? docs/b.md\0?? tmp/c.txt\0"
+    );
+    let statuses = Statuses::parse(toplevel, porcelain);
+
+    let markers = |path: &str| statuses.get(Path::new(path)).map(Status::markers);
+
+    assert_eq!(markers("/repo/src/main.rs"), Some(('M', ' ')));
+    assert_eq!(markers("/repo/src"), Some(('M', 'M')));
+    assert_eq!(markers("/repo/notes.txt"), Some(('?', '?')));
+    assert_eq!(markers("/repo/new.rs"), Some(('R', ' ')));
+    assert_eq!(markers("/repo/old.rs"), None);
+    assert_eq!(markers("/repo/docs"), Some((' ', 'M')));
+    assert_eq!(markers("/repo/tmp"), Some(('?', '?')));
+    assert_eq!(markers("/repo"), Some(('M', 'M')));
+
+    assert_eq!(
+        statuses.get(Path::new("/repo/docs")).map(|s| s.untracked),
+        Some(true)
+    );
+}
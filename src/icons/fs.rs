@@ -1,16 +1,22 @@
 use ansi_term::{ANSIGenericString, Style};
 use ignore::DirEntry;
-use std::{borrow::Cow, path::Path};
+use std::{borrow::Cow, ffi::OsStr, path::Path};
 
 /// Computes a plain, colorless icon with given parameters.
 ///
 /// The precedent from highest to lowest in terms of which parameters determine the icon used
-/// is as followed: file-type, file-extension, and then file-name. If an icon cannot be
-/// computed the fall-back default icon is used.
+/// is as followed: user-defined overrides, file-type, file-extension, and then file-name. If an
+/// icon cannot be computed the fall-back default icon is used.
 ///
 /// If a directory entry is a link and the link target is provided, the link target will be
 /// used to determine the icon.
 pub fn compute(entry: &DirEntry, link_target: Option<&Path>) -> Cow<'static, str> {
+    let ext = extension(entry, link_target);
+
+    if let Some(i) = override_icon(entry, ext) {
+        return Cow::from(i);
+    }
+
     let icon = entry
         .file_type()
         .and_then(super::icon_from_file_type)
@@ -20,11 +26,6 @@ pub fn compute(entry: &DirEntry, link_target: Option<&Path>) -> Cow<'static, str
         return i;
     }
 
-    let ext = match link_target {
-        Some(target) if entry.path_is_symlink() => target.extension(),
-        _ => entry.path().extension(),
-    };
-
     let icon = ext
         .and_then(super::icon_from_ext)
         .map(|(_, i)| Cow::from(i));
@@ -48,10 +49,14 @@ pub fn compute_with_color(
     link_target: Option<&Path>,
     style: Option<Style>,
 ) -> Cow<'static, str> {
-    let icon = entry
-        .file_type()
-        .and_then(super::icon_from_file_type)
-        .map(Cow::from);
+    let ext = extension(entry, link_target);
+
+    let icon = override_icon(entry, ext).map(Cow::from).or_else(|| {
+        entry
+            .file_type()
+            .and_then(super::icon_from_file_type)
+            .map(Cow::from)
+    });
 
     let paint_icon = |icon| match style {
         Some(Style {
@@ -69,11 +74,6 @@ pub fn compute_with_color(
         return paint_icon(icon);
     }
 
-    let ext = match link_target {
-        Some(target) if entry.path_is_symlink() => target.extension(),
-        _ => entry.path().extension(),
-    };
-
     let icon = ext
         .and_then(super::icon_from_ext)
         .map(|attrs| Cow::from(super::col(attrs.0, attrs.1)));
@@ -93,3 +93,18 @@ pub fn compute_with_color(
     let (code, icon) = super::get_default_icon();
     Cow::from(super::col(code, icon))
 }
+
+/// The extension used to determine the icon of `entry`, which is that of the link target if
+/// `entry` is a symlink and the target is provided.
+fn extension<'a>(entry: &'a DirEntry, link_target: Option<&'a Path>) -> Option<&'a OsStr> {
+    match link_target {
+        Some(target) if entry.path_is_symlink() => target.extension(),
+        _ => entry.path().extension(),
+    }
+}
+
+/// Attempts to return a user-defined icon for `entry`. See [`super::init`].
+fn override_icon(entry: &DirEntry, ext: Option<&OsStr>) -> Option<&'static str> {
+    let is_dir = entry.file_type().is_some_and(|ft| ft.is_dir());
+    super::icon_from_overrides(entry.file_name(), ext, is_dir)
+}
@@ -1,15 +1,116 @@
 use crate::hash;
 use ansi_term::Color;
+use ignore::overrides::{Override, OverrideBuilder};
 use once_cell::sync::Lazy;
 use std::{
     collections::HashMap,
     ffi::{OsStr, OsString},
     fs::FileType,
+    path::Path,
+    sync::OnceLock,
 };
 
 /// Concerned with computing icons given filesystem parameters.
 pub mod fs;
 
+/// Errors that may occur while reading the `[icon_overrides]` table of `.erdtree.toml`.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Invalid glob '{0}' in '[icon_overrides]': {1}")]
+    InvalidGlob(String, ignore::Error),
+
+    #[error("Unknown key '{0}' in '[icon_overrides]'")]
+    UnknownKey(String),
+}
+
+/// User-defined icons read from the `[icon_overrides]` table of `.erdtree.toml`. These are layered
+/// on top of the static tables below and take precedence over them.
+#[derive(Clone, Debug, Default)]
+pub struct Overrides {
+    /// Icons keyed by exact file name.
+    names: HashMap<OsString, String>,
+
+    /// Globs matched against file names alongside their icons.
+    globs: Vec<(Override, String)>,
+
+    /// Icons keyed by file extension.
+    extensions: HashMap<OsString, String>,
+}
+
+impl Overrides {
+    /// Builds [Overrides] from the flattened key-value pairs of the `[icon_overrides]` table where
+    /// keys take the form of `names.<file name>`, `globs.<glob>`, or `extensions.<extension>`.
+    pub fn try_from_entries<I>(entries: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut overrides = Self::default();
+
+        let mut entries = entries.into_iter().collect::<Vec<_>>();
+
+        // Globs are checked in order so keep them deterministic.
+        entries.sort();
+
+        for (key, icon) in entries {
+            match key.split_once('.') {
+                Some(("names", name)) => {
+                    overrides.names.insert(OsString::from(name), icon);
+                },
+                Some(("extensions", ext)) => {
+                    overrides.extensions.insert(OsString::from(ext), icon);
+                },
+                Some(("globs", glob)) => {
+                    let matcher = OverrideBuilder::new("")
+                        .add(glob)
+                        .and_then(|builder| builder.build())
+                        .map_err(|e| Error::InvalidGlob(glob.to_owned(), e))?;
+
+                    overrides.globs.push((matcher, icon));
+                },
+                _ => return Err(Error::UnknownKey(key)),
+            }
+        }
+
+        Ok(overrides)
+    }
+
+    /// Attempts to return the icon for the file named `name` with extension `ext`. File names take
+    /// precedence over globs which take precedence over extensions.
+    fn icon(&self, name: &OsStr, ext: Option<&OsStr>, is_dir: bool) -> Option<&str> {
+        self.names
+            .get(name)
+            .or_else(|| {
+                self.globs
+                    .iter()
+                    .find(|(glob, _)| glob.matched(Path::new(name), is_dir).is_whitelist())
+                    .map(|(_, icon)| icon)
+            })
+            .or_else(|| ext.and_then(|ext| self.extensions.get(ext)))
+            .map(String::as_str)
+    }
+
+    /// Whether or not there are any user-defined icons at all.
+    fn is_empty(&self) -> bool {
+        self.names.is_empty() && self.globs.is_empty() && self.extensions.is_empty()
+    }
+}
+
+/// User-defined icons that take precedence over the static tables. Only initialized via [`init`].
+static OVERRIDES: OnceLock<Overrides> = OnceLock::new();
+
+/// Initializes the user-defined icon [Overrides]. Icons are computed purely from the static
+/// tables if this is never called.
+pub fn init(overrides: &Overrides) {
+    if !overrides.is_empty() {
+        OVERRIDES.set(overrides.clone()).unwrap();
+    }
+}
+
+/// Attempts to return a user-defined icon. See [`Overrides::icon`].
+fn icon_from_overrides(name: &OsStr, ext: Option<&OsStr>, is_dir: bool) -> Option<&'static str> {
+    OVERRIDES.get()?.icon(name, ext, is_dir)
+}
+
 /// Attempts to return an icon given a file extension along with its default color code 8-bit
 /// value.
 fn icon_from_ext(ext: &OsStr) -> Option<(u8, &'static str)> {
@@ -319,3 +420,26 @@ static EXT_ICON_MAP: Lazy<HashMap<OsString, (u8, &str)>> = Lazy::new(|| {
         OsString::from("zsh")           => (113, "\u{e795}")    // 
     )
 });
+
+#[test]
+fn test_overrides() {
+    let entries = [
+        ("names.Jenkinsfile".to_owned(), "J".to_owned()),
+        ("globs.*.pb.go".to_owned(), "P".to_owned()),
+        ("extensions.acme".to_owned(), "A".to_owned()),
+    ];
+
+    let overrides = Overrides::try_from_entries(entries).unwrap();
+
+    let icon = |name: &str, ext: Option<&str>| {
+        overrides.icon(OsStr::new(name), ext.map(OsStr::new), false)
+    };
+
+    assert_eq!(icon("Jenkinsfile", None), Some("J"));
+    assert_eq!(icon("api.pb.go", Some("go")), Some("P"));
+    assert_eq!(icon("widget.acme", Some("acme")), Some("A"));
+    assert_eq!(icon("main.go", Some("go")), None);
+
+    let unknown = [("colors.rs".to_owned(), "R".to_owned())];
+    assert!(Overrides::try_from_entries(unknown).is_err());
+}
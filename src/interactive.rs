@@ -0,0 +1,309 @@
+use crate::{
+    ansi::Escaped,
+    context::Context,
+    render::grid::cell::{self, Cell},
+    tree::Tree,
+};
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    style::Print,
+    terminal::{self, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+    ExecutableCommand, QueueableCommand,
+};
+use indextree::NodeId;
+use std::{
+    collections::HashSet,
+    io::{self, stdout, Write},
+};
+
+/// Amount of lines at the bottom of the screen reserved for the status bar.
+const STATUS_HEIGHT: usize = 1;
+
+/// Keybindings shown in the status bar.
+const HELP: &str = "j/k move  h/l collapse/expand  enter toggle  q quit";
+
+/// Browses `tree` in the terminal until the user quits. Directories can be expanded and collapsed
+/// and the view scrolls to follow the selected entry. The terminal is restored when this returns,
+/// even if an error occurs.
+pub fn run(tree: &Tree, ctx: &Context) -> io::Result<()> {
+    let _guard = Screen::enter()?;
+    let mut out = stdout();
+    let mut browser = Browser::new(tree, ctx);
+
+    loop {
+        let (width, height) = terminal::size()?;
+        browser.draw(&mut out, usize::from(width), usize::from(height))?;
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Release && !browser.handle(key) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Puts the terminal into raw mode on the alternate screen for as long as it lives.
+struct Screen;
+
+impl Screen {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        stdout()
+            .execute(EnterAlternateScreen)?
+            .execute(cursor::Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = stdout()
+            .execute(cursor::Show)
+            .and_then(|out| out.execute(LeaveAlternateScreen));
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// State of the interactive view of a [Tree].
+struct Browser<'a> {
+    tree: &'a Tree,
+    ctx: &'a Context,
+
+    /// Directories whose children are shown.
+    expanded: HashSet<NodeId>,
+
+    /// Visible entries from top to bottom along with how deeply they're nested in the view.
+    rows: Vec<(NodeId, usize)>,
+
+    /// Index into `rows` of the selected entry.
+    cursor: usize,
+
+    /// Index into `rows` of the entry at the top of the screen.
+    offset: usize,
+
+    /// Amount of entries that fit on the screen.
+    page: usize,
+}
+
+impl<'a> Browser<'a> {
+    /// Initializes the browser with directories expanded up to [`Context::interactive_level`].
+    fn new(tree: &'a Tree, ctx: &'a Context) -> Self {
+        let arena = tree.arena();
+        let level = ctx.interactive_level();
+
+        let expanded = tree
+            .root_id()
+            .descendants(arena)
+            .filter(|id| {
+                let node = arena[*id].get();
+                node.is_dir() && node.depth() < level
+            })
+            .collect();
+
+        let mut browser = Self {
+            tree,
+            ctx,
+            expanded,
+            rows: vec![],
+            cursor: 0,
+            offset: 0,
+            page: 1,
+        };
+
+        browser.compute_rows();
+        browser
+    }
+
+    /// Computes the entries that are visible i.e. those whose ancestors are all expanded.
+    fn compute_rows(&mut self) {
+        let arena = self.tree.arena();
+        let mut stack = vec![(self.tree.root_id(), 0)];
+
+        self.rows.clear();
+
+        while let Some((id, depth)) = stack.pop() {
+            self.rows.push((id, depth));
+
+            if self.expanded.contains(&id) {
+                stack.extend(id.reverse_children(arena).map(|child| (child, depth + 1)));
+            }
+        }
+
+        self.cursor = self.cursor.min(self.rows.len() - 1);
+    }
+
+    /// Updates the state in response to `key`. Returns `false` if the browser should be closed.
+    fn handle(&mut self, key: KeyEvent) -> bool {
+        let last = self.rows.len() - 1;
+        let (selected, _) = self.rows[self.cursor];
+
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Down | KeyCode::Char('j') => self.cursor = (self.cursor + 1).min(last),
+            KeyCode::Up | KeyCode::Char('k') => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::PageDown => self.cursor = (self.cursor + self.page).min(last),
+            KeyCode::PageUp => self.cursor = self.cursor.saturating_sub(self.page),
+            KeyCode::Home | KeyCode::Char('g') => self.cursor = 0,
+            KeyCode::End | KeyCode::Char('G') => self.cursor = last,
+            KeyCode::Right | KeyCode::Char('l') => self.expand(selected),
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                if !self.expanded.remove(&selected) {
+                    self.expand(selected);
+                }
+            },
+            KeyCode::Left | KeyCode::Char('h') => {
+                if !self.expanded.remove(&selected) {
+                    self.select_parent(selected);
+                }
+            },
+            _ => return true,
+        }
+
+        self.compute_rows();
+        true
+    }
+
+    /// Expands `id` if it's a directory with entries to show.
+    fn expand(&mut self, id: NodeId) {
+        let arena = self.tree.arena();
+
+        if arena[id].get().is_dir() && id.children(arena).next().is_some() {
+            self.expanded.insert(id);
+        }
+    }
+
+    /// Moves the cursor to the parent directory of `id`, if any.
+    fn select_parent(&mut self, id: NodeId) {
+        let Some(parent_id) = self.tree.arena()[id].parent() else {
+            return;
+        };
+
+        if let Some(index) = self.rows.iter().position(|(row, _)| *row == parent_id) {
+            self.cursor = index;
+        }
+    }
+
+    /// Renders a single entry, prefixed with an arrow indicating whether or not directories are
+    /// expanded.
+    fn row(&self, id: NodeId, depth: usize) -> String {
+        let arena = self.tree.arena();
+        let node = arena[id].get();
+
+        let marker = if !node.is_dir() || id.children(arena).next().is_none() {
+            " "
+        } else if self.expanded.contains(&id) {
+            "▾"
+        } else {
+            "▸"
+        };
+
+        let prefix = format!("{}{marker} ", "  ".repeat(depth));
+        let name = Cell::new(
+            node,
+            self.ctx,
+            cell::Kind::FileName {
+                prefix: Some(&prefix),
+            },
+        );
+
+        if self.ctx.suppress_size {
+            return name.to_string();
+        }
+
+        let size = Cell::new(node, self.ctx, cell::Kind::FileSize);
+
+        format!("{size} {name}")
+    }
+
+    /// Draws the visible entries that fit in a screen of the given dimensions followed by the
+    /// status bar, scrolling so that the selected entry is always in view.
+    fn draw(&mut self, out: &mut impl Write, width: usize, height: usize) -> io::Result<()> {
+        self.page = height.saturating_sub(STATUS_HEIGHT).max(1);
+
+        if self.cursor < self.offset {
+            self.offset = self.cursor;
+        } else if self.cursor >= self.offset + self.page {
+            self.offset = self.cursor + 1 - self.page;
+        }
+
+        out.queue(terminal::Clear(ClearType::All))?;
+
+        let visible = self
+            .rows
+            .iter()
+            .enumerate()
+            .skip(self.offset)
+            .take(self.page);
+
+        for (line, (index, &(id, depth))) in visible.enumerate() {
+            let pointer = if index == self.cursor { ">" } else { " " };
+            let row = format!("{pointer} {}", self.row(id, depth));
+
+            out.queue(cursor::MoveTo(0, line as u16))?
+                .queue(Print(<str as Escaped>::ellipsize(&row, width)))?;
+        }
+
+        let (selected, _) = self.rows[self.cursor];
+        let path = self.tree.arena()[selected].get().path().display();
+        let status = format!("{path}  |  {HELP}");
+
+        out.queue(cursor::MoveTo(0, self.page as u16))?
+            .queue(Print(<str as Escaped>::ellipsize(&status, width)))?;
+
+        out.flush()
+    }
+}
+
+#[test]
+fn test_browser() {
+    use clap::Parser;
+
+    let ctx = Context::try_parse_from([
+        "erd",
+        "--threads",
+        "1",
+        "--sort",
+        "name",
+        "--color",
+        "none",
+        "tests/data",
+    ])
+    .unwrap();
+
+    let (tree, ctx) = Tree::try_init(ctx, None).unwrap();
+    let mut browser = Browser::new(&tree, &ctx);
+
+    let names = |browser: &Browser| {
+        browser
+            .rows
+            .iter()
+            .map(|(id, _)| {
+                tree.arena()[*id]
+                    .get()
+                    .file_name()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(names(&browser).len(), 7);
+
+    let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+    // Expand `dream_cycle` which is the first entry beneath the root.
+    assert!(browser.handle(key(KeyCode::Down)));
+    assert!(browser.handle(key(KeyCode::Right)));
+    assert_eq!(names(&browser)[..3], ["data", "dream_cycle", "polaris.txt"]);
+
+    // Moving left from a file selects its parent and moving left again collapses it.
+    assert!(browser.handle(key(KeyCode::Down)));
+    assert!(browser.handle(key(KeyCode::Left)));
+    assert_eq!(browser.cursor, 1);
+    assert!(browser.handle(key(KeyCode::Left)));
+    assert_eq!(names(&browser).len(), 7);
+
+    assert!(!browser.handle(key(KeyCode::Char('q'))));
+}
@@ -20,10 +20,17 @@
 )]
 
 use clap::CommandFactory;
-use context::{layout, Context};
+use context::Context;
+use disk_usage::file_size::FileSize;
 use progress::Message;
-use render::{Engine, Flat, FlatInverted, Inverted, Regular};
-use std::{error::Error, io::stdout, process::ExitCode, sync::Arc};
+use std::{
+    error::Error,
+    fs::File,
+    io::{self, stdout, Write},
+    process::ExitCode,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tree::Tree;
 
 /// Operations to wrangle ANSI escaped strings.
@@ -38,9 +45,15 @@ mod disk_usage;
 /// Filesystem operations.
 mod fs;
 
+/// Querying the status of files in a git repository for `--git-status`.
+mod git;
+
 /// All things related to icons on how to map certain files to the appropriate icons.
 mod icons;
 
+/// Browsing the tree in the terminal with `--interactive`.
+mod interactive;
+
 /// Concerned with displaying a progress indicator when stdout is a tty.
 mod progress;
 
@@ -50,6 +63,9 @@ mod progress;
 /// [`Node`]: tree::node::Node
 mod render;
 
+/// Saving the size of every entry with `--snapshot` to compare against later with `--diff`.
+mod snapshot;
+
 /// Global used throughout the program to paint the output.
 mod styles;
 
@@ -63,6 +79,9 @@ mod tty;
 /// Common utilities across all modules.
 mod utils;
 
+/// Re-rendering the tree as the filesystem changes with `--watch`.
+mod watch;
+
 fn main() -> ExitCode {
     let result = run();
 
@@ -85,10 +104,21 @@ fn run() -> Result<(), Box<dyn Error>> {
 
     context::color::no_color_env();
 
-    styles::init(ctx.no_color());
+    styles::init(
+        ctx.no_color(),
+        ctx.color_icons || ctx.html_color(),
+        ctx.ascii,
+        &ctx.theme,
+    );
+
+    icons::init(&ctx.icon_overrides);
+
+    if ctx.watch {
+        return watch::run(&ctx);
+    }
 
-    let indicator = (ctx.stdout_is_tty && !ctx.no_progress)
-        .then(progress::Indicator::measure)
+    let indicator = (ctx.stderr_is_tty && !ctx.no_progress)
+        .then(|| progress::Indicator::measure(ctx.unit))
         .map(Arc::new);
 
     if indicator.is_some() {
@@ -100,27 +130,32 @@ fn run() -> Result<(), Box<dyn Error>> {
         })?;
     }
 
-    let (tree, ctx) = match Tree::try_init(ctx, indicator.clone()) {
-        Ok(res) => res,
-        Err(err) => {
-            let _ = progress::IndicatorHandle::terminate(indicator);
-            return Err(Box::new(err));
-        },
-    };
+    let mut trees = vec![];
+
+    for root_ctx in ctx.roots() {
+        match Tree::try_init(root_ctx, indicator.clone()) {
+            Ok(res) => trees.push(res),
+            Err(err) => {
+                let _ = progress::IndicatorHandle::terminate(indicator);
+                return Err(Box::new(err));
+            },
+        }
+    }
 
-    macro_rules! compute_output {
-        ($t:ty) => {{
-            let render = Engine::<$t>::new(tree, ctx);
-            format!("{render}")
-        }};
+    if let (Some(path), Some((tree, root_ctx))) = (ctx.snapshot.as_ref(), trees.first()) {
+        snapshot::Snapshot::new(tree, root_ctx).save(path)?;
     }
 
-    let output = match ctx.layout {
-        layout::Type::Flat => compute_output!(Flat),
-        layout::Type::Iflat => compute_output!(FlatInverted),
-        layout::Type::Inverted => compute_output!(Inverted),
-        layout::Type::Regular => compute_output!(Regular),
-    };
+    // Output is buffered so that it isn't written while the progress indicator is still active.
+    let mut output = vec![];
+
+    let render_start = Instant::now();
+
+    if !ctx.interactive {
+        render_trees(&trees, &mut output, &ctx)?;
+    }
+
+    let rendering = render_start.elapsed();
 
     if let Some(mut progress) = indicator {
         progress.mailbox().send(Message::RenderReady)?;
@@ -133,17 +168,95 @@ fn run() -> Result<(), Box<dyn Error>> {
         }
     }
 
+    if ctx.interactive {
+        let (tree, ctx) = &trees[0];
+        return Ok(interactive::run(tree, ctx)?);
+    }
+
+    // Only created once output is ready so that an existing file isn't clobbered should erdtree
+    // fail before then.
+    if let Some(path) = ctx.output.as_ref() {
+        File::create(path)
+            .and_then(|mut file| file.write_all(&output))
+            .map_err(|e| context::error::Error::Output(path.display().to_string(), e))?;
+
+        report_timings(&trees, rendering, &ctx);
+        return Ok(());
+    }
+
     #[cfg(debug_assertions)]
     {
         if std::env::var_os("ERDTREE_DEBUG").is_none() {
-            println!("{output}");
+            stdout().write_all(&output)?;
         }
     }
 
     #[cfg(not(debug_assertions))]
     {
-        println!("{output}");
+        stdout().write_all(&output)?;
     }
 
+    report_timings(&trees, rendering, &ctx);
+
     Ok(())
 }
+
+/// Prints the [`tree::timings::Timings`] of all `trees` to stderr if `--timings` is enabled, after
+/// output has been written so as to not be interleaved with it.
+fn report_timings(trees: &[(Tree, Context)], rendering: Duration, ctx: &Context) {
+    if !ctx.timings {
+        return;
+    }
+
+    let mut timings = tree::timings::Timings::default();
+
+    for (tree, _) in trees {
+        timings += tree.timings();
+    }
+
+    timings.rendering = rendering;
+
+    eprintln!("{timings}");
+}
+
+/// Renders each of the `trees` one after another separated by a blank line, except for `--list`
+/// whose output is meant to be piped. If there are several then the tree and flat layouts are
+/// followed by the combined disk usage of all of the roots, each directory counted once.
+fn render_trees(trees: &[(Tree, Context)], w: &mut impl Write, ctx: &Context) -> io::Result<()> {
+    for (i, (tree, root_ctx)) in trees.iter().enumerate() {
+        if i > 0 && !ctx.list {
+            writeln!(w)?;
+        }
+
+        tree.render(w, root_ctx)?;
+    }
+
+    if trees.len() < 2 || ctx.suppress_size || ctx.list || ctx.top.is_some() {
+        return Ok(());
+    }
+
+    let mut total = FileSize::from(ctx);
+
+    let roots = trees
+        .iter()
+        .map(|(_, root_ctx)| root_ctx.dir_canonical())
+        .collect::<Vec<_>>();
+
+    for (i, (tree, _)) in trees.iter().enumerate() {
+        // Like `du -c`, roots that are the same as or nested inside of another root are already
+        // accounted for by it.
+        let is_counted = roots.iter().enumerate().any(|(j, other)| {
+            j != i && roots[i].starts_with(other) && (roots[i] != *other || j < i)
+        });
+
+        if is_counted {
+            continue;
+        }
+
+        if let Some(file_size) = tree.arena()[tree.root_id()].get().file_size() {
+            total += file_size;
+        }
+    }
+
+    writeln!(w, "\n{total} total")
+}
@@ -1,3 +1,8 @@
+use crate::{
+    ansi::Escaped,
+    disk_usage::{file_size::byte, units::PrefixKind},
+    utils,
+};
 use crossterm::{
     cursor,
     terminal::{self, ClearType},
@@ -5,23 +10,33 @@ use crossterm::{
 };
 use std::{
     io::{self, Write},
+    path::{Path, PathBuf},
     sync::{
-        mpsc::{self, SendError, SyncSender},
-        Arc,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc::{self, RecvTimeoutError, SendError, SyncSender},
+        Arc, Mutex,
     },
     thread::{self, JoinHandle},
     time::Duration,
 };
 
-/// Timeout used for [`IndicatorHandle`]'s `priority_mailbox`.
-const PRIORITY_MAIL_TIMEOUT: Duration = Duration::from_nanos(1);
+/// How often the [`Indicator`] is redrawn while indexing.
+const TICK: Duration = Duration::from_millis(80);
+
+/// Frames of the spinner drawn in front of the progress text.
+const SPINNER: [char; 4] = ['|', '/', '-', '\\'];
 
 /// Responsible for displying the progress indicator. This struct will be owned by a separate
 /// thread that is responsible for displaying the progress text whereas the [`IndicatorHandle`]
 /// is how the outside world will interact with it.
+///
+/// The progress text is written to stderr so that it never ends up in output that is piped or
+/// redirected elsewhere.
 pub struct Indicator<'a> {
-    count: u64,
-    stdout: io::StdoutLock<'a>,
+    tally: Arc<Tally>,
+    prefix_kind: PrefixKind,
+    frame: usize,
+    stderr: io::StderrLock<'a>,
     state: IndicatorState,
 }
 
@@ -31,18 +46,30 @@ pub struct Indicator<'a> {
 ///
 /// The `priority_mailbox` is used to prematurely terminate the [`Indicator`] in the case of say a
 /// `SIGINT` signal.
+///
+/// What has been indexed is kept track of by `tally` which is shared with the traversal's worker
+/// threads so that they may update it directly.
 pub struct IndicatorHandle {
     pub join_handle: Option<JoinHandle<Result<(), Error>>>,
     mailbox: SyncSender<Message>,
     priority_mailbox: SyncSender<()>,
+    tally: Arc<Tally>,
+    terminated: AtomicBool,
+}
+
+/// The amount of entries and bytes indexed so far, the path of an entry that was recently
+/// indexed, and the amount of errors encountered along the way.
+#[derive(Default)]
+pub struct Tally {
+    entries: AtomicU64,
+    bytes: AtomicU64,
+    errors: AtomicU64,
+    current: Mutex<PathBuf>,
 }
 
 /// The different messages that could be sent to the thread that owns the [`Indicator`].
 #[derive(Debug, PartialEq, Eq)]
 pub enum Message {
-    /// Message that indicates that we are currently reading from disk and that a file was indexed.
-    Index,
-
     /// Message that indicates that we are done reading from disk and are preparing the output.
     DoneIndexing,
 
@@ -76,31 +103,33 @@ pub enum Error {
     Send(#[from] SendError<()>),
 }
 
-impl Default for Indicator<'_> {
-    /// Default constructor for [`Indicator`].
-    fn default() -> Self {
-        Self {
-            count: u64::default(),
-            stdout: io::stdout().lock(),
-            state: IndicatorState::default(),
-        }
-    }
-}
-
 impl IndicatorHandle {
     /// The constructor for an [`IndicatorHandle`].
     pub fn new(
         join_handle: Option<JoinHandle<Result<(), Error>>>,
         mailbox: SyncSender<Message>,
         priority_mailbox: SyncSender<()>,
+        tally: Arc<Tally>,
     ) -> Self {
         Self {
             join_handle,
             mailbox,
             priority_mailbox,
+            tally,
+            terminated: AtomicBool::new(false),
         }
     }
 
+    /// Getter for a cloned `tally` which is to be updated every time a file is indexed.
+    pub fn tally(&self) -> Arc<Tally> {
+        Arc::clone(&self.tally)
+    }
+
+    /// Whether or not the [`Indicator`] was prematurely torn down via [`Self::terminate`].
+    pub fn is_terminated(&self) -> bool {
+        self.terminated.load(Ordering::Relaxed)
+    }
+
     /// Getter for a cloned `mailbox` wherewith to send [`Message`]s to the [`Indicator`].
     pub fn mailbox(&self) -> SyncSender<Message> {
         self.mailbox.clone()
@@ -114,6 +143,7 @@ impl IndicatorHandle {
     /// Send a message through to the `priority_mailbox` tear down the [`Indicator`].
     pub fn terminate(this: Option<Arc<Self>>) -> Result<(), Error> {
         if let Some(mut handle) = this {
+            handle.terminated.store(true, Ordering::Relaxed);
             handle.priority_mailbox().send(())?;
 
             if let Some(hand) = Arc::get_mut(&mut handle) {
@@ -127,44 +157,88 @@ impl IndicatorHandle {
     }
 }
 
+impl Tally {
+    /// Records an entry at `path` whose size is `bytes`. The current path is only replaced if no
+    /// other worker thread is in the midst of doing so, so that workers never wait on one another.
+    pub fn record(&self, path: &Path, bytes: u64) {
+        self.entries.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+
+        if let Ok(mut current) = self.current.try_lock() {
+            current.clear();
+            current.push(path);
+        }
+    }
+
+    /// Records an error encountered while indexing.
+    pub fn record_error(&self) {
+        self.errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// The amount of entries indexed so far.
+    pub fn entries(&self) -> u64 {
+        self.entries.load(Ordering::Relaxed)
+    }
+
+    /// The amount of errors encountered so far.
+    pub fn errors(&self) -> u64 {
+        self.errors.load(Ordering::Relaxed)
+    }
+}
+
 impl<'a> Indicator<'a> {
     /// Initializes a worker thread that owns [`Indicator`] that awaits on [`Message`]s to traverse
     /// through its internal states. An [`IndicatorHandle`] is returned as a mechanism to allow the
     /// outside world to send messages to the worker thread and ultimately to the [`Indicator`].
-    pub fn measure() -> IndicatorHandle {
+    /// Bytes indexed are reported using `prefix_kind`.
+    pub fn measure(prefix_kind: PrefixKind) -> IndicatorHandle {
         let (tx, rx) = mpsc::sync_channel(1024);
         let (ptx, prx) = mpsc::sync_channel(1);
+        let tally = Arc::new(Tally::default());
+        let indicator_tally = Arc::clone(&tally);
 
         let join_handle = thread::spawn(move || {
-            let mut indicator = Self::default();
+            let mut indicator = Self::new(indicator_tally, prefix_kind);
 
-            indicator.stdout.execute(cursor::SavePosition)?;
-            indicator.stdout.execute(cursor::Hide)?;
+            indicator.stderr.execute(cursor::SavePosition)?;
+            indicator.stderr.execute(cursor::Hide)?;
 
-            while let Ok(msg) = rx.recv() {
-                if prx.recv_timeout(PRIORITY_MAIL_TIMEOUT).is_ok() {
+            loop {
+                if prx.try_recv().is_ok() {
                     indicator.update_state(IndicatorState::Done)?;
                     break;
                 }
 
-                match msg {
-                    Message::Index => indicator.index()?,
-                    Message::DoneIndexing => {
+                match rx.recv_timeout(TICK) {
+                    Ok(Message::DoneIndexing) => {
                         indicator.update_state(IndicatorState::Rendering)?;
                     },
-                    Message::RenderReady => {
+                    Ok(Message::RenderReady) => {
                         indicator.update_state(IndicatorState::Done)?;
                         return Ok(());
                     },
+                    Err(RecvTimeoutError::Timeout) => indicator.index()?,
+                    Err(RecvTimeoutError::Disconnected) => break,
                 }
 
-                indicator.stdout.execute(cursor::RestorePosition)?;
+                indicator.stderr.execute(cursor::RestorePosition)?;
             }
 
             Ok(())
         });
 
-        IndicatorHandle::new(Some(join_handle), tx, ptx)
+        IndicatorHandle::new(Some(join_handle), tx, ptx, tally)
+    }
+
+    /// Constructor for [`Indicator`] that reports what was indexed according to `tally`.
+    fn new(tally: Arc<Tally>, prefix_kind: PrefixKind) -> Self {
+        Self {
+            tally,
+            prefix_kind,
+            frame: 0,
+            stderr: io::stderr().lock(),
+            state: IndicatorState::default(),
+        }
     }
 
     /// Updates the `state` of the [`Indicator`] to `new_state`, immediately running an associated
@@ -175,16 +249,16 @@ impl<'a> Indicator<'a> {
 
         match (&self.state, &new_state) {
             (Indexing, Rendering) => {
-                let stdout = &mut self.stdout;
-                stdout.execute(terminal::Clear(ClearType::CurrentLine))?;
-                stdout.execute(cursor::RestorePosition)?;
+                let stderr = &mut self.stderr;
+                stderr.execute(terminal::Clear(ClearType::CurrentLine))?;
+                stderr.execute(cursor::RestorePosition)?;
                 self.rendering();
             },
 
             (Rendering | Indexing, Done) => {
-                let stdout = &mut self.stdout;
-                stdout.execute(terminal::Clear(ClearType::CurrentLine))?;
-                stdout.execute(cursor::RestorePosition)?;
+                let stderr = &mut self.stderr;
+                stderr.execute(terminal::Clear(ClearType::CurrentLine))?;
+                stderr.execute(cursor::RestorePosition)?;
             },
             _ => (),
         }
@@ -194,17 +268,52 @@ impl<'a> Indicator<'a> {
         Ok(())
     }
 
-    /// The user-facing output when the `state` of the [`Indicator`] is `Indexing`.
+    /// The user-facing output when the `state` of the [`Indicator`] is `Indexing`, i.e. the amount
+    /// of files and bytes indexed followed by the path of a file that was recently indexed. The
+    /// line is truncated so as not to wrap. Each call advances the spinner by a single frame.
     #[inline]
     fn index(&mut self) -> Result<(), Error> {
-        self.count += 1;
-        write!(self.stdout, "Indexing {} files...", self.count)?;
+        if self.state != IndicatorState::Indexing {
+            return Ok(());
+        }
+
+        let spinner = SPINNER[self.frame % SPINNER.len()];
+        let entries = self.tally.entries();
+        let bytes = self.tally.bytes.load(Ordering::Relaxed);
+        self.frame += 1;
+
+        let size = if bytes > 0 {
+            let mut metric = byte::Metric::init_empty_logical(true, self.prefix_kind);
+            metric += bytes;
+            format!(", {metric}")
+        } else {
+            String::new()
+        };
+
+        let current = self
+            .tally
+            .current
+            .lock()
+            .ok()
+            .filter(|current| !current.as_os_str().is_empty())
+            .map(|current| format!(": {}", current.display()))
+            .unwrap_or_default();
+
+        let entries = utils::group_digits(entries);
+        let line = format!("{spinner} Indexing {entries} files{size}{current}");
+
+        let width = terminal::size().map_or(usize::MAX, |(cols, _)| usize::from(cols));
+        let line = <str as Escaped>::ellipsize(&line, width.saturating_sub(1));
+
+        self.stderr
+            .execute(terminal::Clear(ClearType::CurrentLine))?;
+        write!(self.stderr, "{line}")?;
         Ok(())
     }
 
     /// The user-facing output when the `state` of the [`Indicator`] is `Rendering`.
     #[inline]
     fn rendering(&mut self) {
-        write!(self.stdout, "Preparing output...").unwrap();
+        write!(self.stderr, "Preparing output...").unwrap();
     }
 }
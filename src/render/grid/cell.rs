@@ -1,17 +1,21 @@
 use crate::{
-    context::Context,
+    ansi,
+    context::{heat, path, size, Context},
     disk_usage::{
         file_size::{byte, DiskUsage, FileSize},
         units::{BinPrefix, PrefixKind, SiPrefix},
     },
     render::theme,
     styles,
-    tree::node::Node,
+    tree::node::{Node, Overflow},
+    utils,
 };
+use ansi_term::{Color, Style};
 use std::{
+    borrow::Cow,
     ffi::OsStr,
     fmt::{self, Display},
-    path::Path,
+    path::{Path, MAIN_SEPARATOR},
 };
 
 #[cfg(unix)]
@@ -19,11 +23,16 @@ use chrono::{DateTime, Local};
 
 #[cfg(unix)]
 use crate::{
-    context::time,
     disk_usage::file_size::{block, BLOCK_SIZE_BYTES},
     styles::PLACEHOLDER,
 };
 
+/// Block elements that fill one through seven eighths of a character cell, used to draw the
+/// fractional end of the bar rendered by `--bars`.
+const PARTIAL_BLOCKS: [char; 7] = [
+    '\u{258F}', '\u{258E}', '\u{258D}', '\u{258C}', '\u{258B}', '\u{258A}', '\u{2589}',
+];
+
 /// Constitutes a single cell in a given row of the output. The `kind` field denotes what type of
 /// data actually goes into the cell once rendered. Each `kind` which is of type [Kind] has its own
 /// rules for rendering. Cell's do not have to be of a consistent width.
@@ -40,9 +49,12 @@ pub enum Kind<'a> {
     },
     FilePath,
     FileSize,
+    OtherFileSize,
+    FileCount,
+    Extension,
+    Bar,
     #[cfg(unix)]
     Datetime,
-    #[cfg(unix)]
     Ino,
     #[cfg(unix)]
     Nlink,
@@ -70,15 +82,39 @@ impl<'a> Cell<'a> {
         match self.kind {
             Kind::FileName { prefix } => {
                 let pre = prefix.unwrap_or_default();
-                let name = theme::stylize_file_name(node);
+
+                if let Some(overflow) = node.overflow() {
+                    let git = self.git_status();
+                    let label = self.overflow_label(overflow);
+                    return write!(f, "{git}{pre}{label}");
+                }
+
+                let mut name = theme::stylize_file_name(node, Self::name_style(node, ctx));
+
+                if ctx.hyperlink {
+                    name = Cow::from(ansi::hyperlink(&name, node.path()));
+                }
+
+                let stats = self.dir_stats();
+                let summary = self.summary();
+                let delta = self.delta();
+                let unfollowed = self.unfollowed_note();
+                let broken = self.broken_note();
+                let git = self.git_status();
 
                 if !ctx.icons {
-                    return write!(f, "{pre}{name}");
+                    return write!(
+                        f,
+                        "{git}{pre}{name}{broken}{stats}{summary}{delta}{unfollowed}"
+                    );
                 }
 
-                let icon = node.compute_icon(ctx.no_color());
+                let icon = node.compute_icon(ctx.no_icon_color());
 
-                write!(f, "{pre}{icon} {name}")
+                write!(
+                    f,
+                    "{git}{pre}{icon} {name}{broken}{stats}{summary}{delta}{unfollowed}"
+                )
             },
 
             _ => unreachable!(),
@@ -91,7 +127,13 @@ impl<'a> Cell<'a> {
         let node = self.node;
         let ctx = self.ctx;
 
-        let path = if node.depth() == 0 {
+        if let Some(overflow) = node.overflow() {
+            return self.fmt_overflow_path(f, overflow);
+        }
+
+        let path = if matches!(ctx.paths, path::Type::Absolute) {
+            node.path().display()
+        } else if node.depth() == 0 {
             let file_name = node.file_name();
             <OsStr as AsRef<Path>>::as_ref(file_name).display()
         } else {
@@ -101,18 +143,321 @@ impl<'a> Cell<'a> {
                 .display()
         };
 
-        let formatted_path = node.style().map_or_else(
+        let mut formatted_path = Self::name_style(node, ctx).map_or_else(
             || path.to_string(),
             |style| format!("{}", style.paint(path.to_string())),
         );
 
+        if ctx.hyperlink {
+            formatted_path = ansi::hyperlink(&formatted_path, node.path());
+        }
+
+        let stats = self.dir_stats();
+        let summary = self.summary();
+        let delta = self.delta();
+        let links = self.hardlink_note();
+        let unfollowed = self.unfollowed_note();
+        let broken = self.broken_note();
+        let git = self.git_status();
+
         if !ctx.icons {
-            return write!(f, "{formatted_path}");
+            return write!(
+                f,
+                "{git}{formatted_path}{broken}{stats}{summary}{delta}{links}{unfollowed}"
+            );
         }
 
-        let icon = node.compute_icon(ctx.no_color());
+        let icon = node.compute_icon(ctx.no_icon_color());
+
+        write!(
+            f,
+            "{git}{icon} {formatted_path}{broken}{stats}{summary}{delta}{links}{unfollowed}"
+        )
+    }
+
+    /// Rules on how to render the path of a [Node] standing in for the entries hidden by
+    /// `--limit`, i.e. the label prefixed with the path of their parent directory.
+    fn fmt_overflow_path(&self, f: &mut fmt::Formatter<'_>, overflow: Overflow) -> fmt::Result {
+        let node_path = self.node.path();
+        let parent = node_path.parent().unwrap_or(node_path);
+
+        let parent = if matches!(self.ctx.paths, path::Type::Absolute) {
+            parent
+        } else {
+            parent
+                .strip_prefix(self.ctx.dir_canonical())
+                .unwrap_or(parent)
+        };
+
+        let git = self.git_status();
+        let label = self.overflow_label(overflow);
+
+        if parent.as_os_str().is_empty() {
+            return write!(f, "{git}{label}");
+        }
+
+        write!(f, "{git}{}{MAIN_SEPARATOR}{label}", parent.display())
+    }
+
+    /// Label of a [Node] standing in for the entries hidden by `--limit`, e.g. `… 37 more`, which
+    /// is dimmed unless color is disabled. Padded so as to line up with names that have icons.
+    fn overflow_label(&self, overflow: Overflow) -> String {
+        let pad = if self.ctx.icons { "  " } else { "" };
+        let label = format!(
+            "\u{2026} {} more",
+            utils::group_digits(overflow.entries as u64)
+        );
+
+        if self.ctx.no_color() {
+            format!("{pad}{label}")
+        } else {
+            format!("{pad}{}", Style::new().dimmed().paint(label))
+        }
+    }
+
+    /// Annotation for directories containing the total number of files and the total size of
+    /// the directory, e.g. ` (14 files, 3.2 MiB)`. Empty if `--dir-stats` isn't enabled or if the
+    /// [Node] isn't a directory.
+    #[inline]
+    fn dir_stats(&self) -> String {
+        let Some(count) = self.node.file_count().filter(|_| self.ctx.dir_stats) else {
+            return String::new();
+        };
+
+        let noun = if count == 1 { "file" } else { "files" };
+
+        match self.node.file_size() {
+            Some(file_size) if !self.ctx.suppress_size => format!(" ({count} {noun}, {file_size})"),
+            _ => format!(" ({count} {noun})"),
+        }
+    }
+
+    /// Annotation for directories at the cutoff of `--level` describing what's beneath them, e.g.
+    /// ` (142 files, 3 dirs, 1.2 GiB)`. Empty unless `--summarize` is enabled.
+    #[inline]
+    fn summary(&self) -> String {
+        let Some(count) = self.node.summary() else {
+            return String::new();
+        };
+
+        let plural = |n: usize, noun: &str| {
+            if n == 1 {
+                format!("{n} {noun}")
+            } else {
+                format!("{n} {noun}s")
+            }
+        };
 
-        write!(f, "{icon} {formatted_path}")
+        let mut parts = vec![
+            plural(count.num_files, "file"),
+            plural(count.num_dirs, "dir"),
+        ];
+
+        if count.num_links > 0 {
+            parts.push(plural(count.num_links, "link"));
+        }
+
+        if let Some(file_size) = self.node.file_size().filter(|_| !self.ctx.suppress_size) {
+            parts.push(file_size.to_string());
+        }
+
+        format!(" ({})", parts.join(", "))
+    }
+
+    /// Annotation noting how an entry changed since the snapshot provided via `--diff` was taken,
+    /// e.g. ` (new)`, ` (+1.2 MiB)`, or ` (-4 KiB, 2 removed)`. Additions are painted green and
+    /// removals red.
+    #[inline]
+    fn delta(&self) -> String {
+        let Some(delta) = self.node.delta() else {
+            return String::new();
+        };
+
+        let no_color = self.ctx.no_color();
+
+        let paint = |text: String, color: Color| {
+            if no_color {
+                text
+            } else {
+                color.paint(text).to_string()
+            }
+        };
+
+        let mut parts = vec![];
+
+        match delta.before {
+            None => parts.push(paint(String::from("new"), Color::Green)),
+            Some(before) if before != delta.after => {
+                let mut change = FileSize::from(self.ctx);
+                change += before.abs_diff(delta.after);
+
+                if delta.after > before {
+                    parts.push(paint(format!("+{change}"), Color::Green));
+                } else {
+                    parts.push(paint(format!("-{change}"), Color::Red));
+                }
+            },
+            Some(_) => {},
+        }
+
+        if delta.removed > 0 {
+            parts.push(paint(format!("{} removed", delta.removed), Color::Red));
+        }
+
+        format!(" ({})", parts.join(", "))
+    }
+
+    /// Annotation for files with multiple hard-links noting how many other links to the same
+    /// inode exist, e.g. ` (+2 links)`. Empty unless `--dedupe-hardlinks` is enabled.
+    #[inline]
+    fn hardlink_note(&self) -> String {
+        if !self.ctx.dedupe_hardlinks || self.node.is_dir() {
+            return String::new();
+        }
+
+        match self.node.inode() {
+            Some(inode) if inode.nlink == 2 => String::from(" (+1 link)"),
+            Some(inode) if inode.nlink > 2 => format!(" (+{} links)", inode.nlink - 1),
+            _ => String::new(),
+        }
+    }
+
+    /// Two character marker preceding file names with `--git-status` denoting staged changes
+    /// followed by unstaged changes, e.g. `M-` for a file whose modifications have been staged or
+    /// `??` for an untracked file. Unchanged files are marked `--`.
+    #[inline]
+    fn git_status(&self) -> String {
+        if !self.ctx.git_status {
+            return String::new();
+        }
+
+        let status = self
+            .node
+            .git_status()
+            .filter(|_| self.node.overflow().is_none())
+            .unwrap_or_default();
+        let no_color = self.ctx.no_color();
+
+        let paint = |marker: char, color: Color| {
+            if marker == ' ' {
+                styles::get_placeholder_style().map_or_else(
+                    |_| String::from(styles::PLACEHOLDER),
+                    |style| style.paint(styles::PLACEHOLDER).to_string(),
+                )
+            } else if no_color {
+                String::from(marker)
+            } else {
+                color.paint(marker.to_string()).to_string()
+            }
+        };
+
+        let (staged, unstaged) = status.markers();
+
+        format!(
+            "{}{} ",
+            paint(staged, Color::Green),
+            paint(unstaged, Color::Red)
+        )
+    }
+
+    /// Annotation for directories that weren't descended into: symlinks that weren't followed
+    /// because doing so would result in a cycle or show a directory that is shown elsewhere, and
+    /// mount points left by `--one-file-system`.
+    #[inline]
+    const fn unfollowed_note(&self) -> &'static str {
+        if self.node.is_cyclic() {
+            " (cycle)"
+        } else if self.node.is_revisit() {
+            " (already shown)"
+        } else if self.node.is_mount_point() {
+            " (mount point)"
+        } else {
+            ""
+        }
+    }
+
+    /// Annotation for symlinks whose targets don't exist, i.e. ` ⇥ missing`, painted red.
+    #[inline]
+    fn broken_note(&self) -> String {
+        if !self.node.is_broken_link() {
+            return String::new();
+        }
+
+        let note = " \u{21E5} missing";
+
+        if self.ctx.no_color() {
+            String::from(note)
+        } else {
+            Color::Red.paint(note).to_string()
+        }
+    }
+
+    /// The style used to paint file names and paths. Names are left plain if color is disabled
+    /// even if the [Node] was assigned a style so that icons could be colorized. With
+    /// `--size-gradient` the style is derived from the [Node]'s disk usage, relative to whatever
+    /// `--heat-scope` calls for, instead; sizes are fully aggregated by the time rendering occurs
+    /// so directories are colored by their totals. Likewise with `--heat age` the style is derived
+    /// from how long ago the [Node] was modified.
+    #[inline]
+    fn name_style(node: &Node, ctx: &Context) -> Option<ansi_term::Style> {
+        if ctx.no_color() {
+            return None;
+        }
+
+        let style = match node.file_size() {
+            Some(file_size) if ctx.size_gradient => Some(node.heat().map_or_else(
+                || theme::size_gradient_style(file_size.value(), ctx.max_file_size),
+                theme::gradient_style,
+            )),
+            _ if ctx.heat == Some(heat::Metric::Age) => node.heat().map(theme::age_gradient_style),
+            _ => node.style(),
+        };
+
+        if ctx.dim_depth {
+            return style.map(|style| theme::dim_by_depth(style, node.depth()));
+        }
+
+        style
+    }
+
+    /// Rules on how to render a file's extension. The column is left-aligned and padded to the
+    /// width of the widest extension.
+    #[inline]
+    fn fmt_extension(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let max_ext_width = self.ctx.max_ext_width;
+        let ext = self.node.extension_label();
+
+        write!(f, "{ext:<max_ext_width$}")
+    }
+
+    /// Rules on how to render the bar representing the [Node]'s share of its parent directory's
+    /// disk usage. The bar is always `--bar-width` wide and is drawn with ASCII characters if color
+    /// is disabled. Otherwise it's drawn with block elements down to an eighth of a character so
+    /// that small differences in share remain visible. Nodes whose share couldn't be computed get
+    /// an empty bar.
+    #[inline]
+    fn fmt_bar(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.ctx.bar_width.get();
+        let share = self.node.share().unwrap_or_default().clamp(0.0, 1.0);
+
+        if self.ctx.no_color() || self.ctx.ascii {
+            let filled = (share * width as f64).round() as usize;
+            let empty = width - filled;
+            return write!(f, "[{}{}]", "#".repeat(filled), "-".repeat(empty));
+        }
+
+        let eighths = (share * (width * 8) as f64).round() as usize;
+        let mut filled = "\u{2588}".repeat(eighths / 8);
+        let mut empty = width - eighths / 8;
+
+        if let Some(partial) = (eighths % 8).checked_sub(1) {
+            filled.push(PARTIAL_BLOCKS[partial]);
+            empty -= 1;
+        }
+
+        let filled = Color::Green.paint(filled);
+
+        write!(f, "[{filled}{}]", "\u{2591}".repeat(empty))
     }
 
     /// Rules on how to render the file size.
@@ -121,20 +466,59 @@ impl<'a> Cell<'a> {
         let node = self.node;
         let ctx = self.ctx;
 
+        let widths = Self::size_widths(ctx);
+
         let Some(file_size) = node.file_size() else {
-            return Self::fmt_size_placeholder(f, ctx)
+            return Self::fmt_size_placeholder(f, ctx, widths.0)
         };
 
         match file_size {
-            FileSize::Byte(metric) => Self::fmt_bytes(f, metric, ctx),
+            FileSize::Byte(metric) => Self::fmt_bytes(f, metric, ctx, widths),
             FileSize::Line(metric) => Self::fmt_unitless_disk_usage(f, metric, ctx),
             FileSize::Word(metric) => Self::fmt_unitless_disk_usage(f, metric, ctx),
+            FileSize::File(metric) => Self::fmt_unitless_disk_usage(f, metric, ctx),
 
             #[cfg(unix)]
             FileSize::Block(metric) => Self::fmt_block_usage(f, metric, ctx),
         }
     }
 
+    /// Rules on how to render the additional file size from `--both-sizes`, which is always
+    /// measured in bytes.
+    #[inline]
+    fn fmt_other_file_size(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ctx = self.ctx;
+        let widths = Self::other_size_widths(ctx);
+
+        match self.node.other_file_size() {
+            Some(FileSize::Byte(metric)) => Self::fmt_bytes(f, metric, ctx, widths),
+            _ => Self::fmt_size_placeholder(f, ctx, widths.0),
+        }
+    }
+
+    /// Rules on how to render the number of files within a directory for `--count`. Files other
+    /// than directories count themselves.
+    #[inline]
+    fn fmt_file_count(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ctx = self.ctx;
+        let count = self.node.file_count().unwrap_or(1);
+
+        let max_width = match ctx.size_position {
+            size::Position::Left => ctx.max_count_width,
+            size::Position::Right => 0,
+        };
+
+        let formatted = format!("{count:>max_width$}");
+
+        if ctx.no_color() {
+            return write!(f, "{formatted}");
+        }
+
+        let color = styles::get_du_theme().unwrap().get("B").unwrap();
+
+        write!(f, "{}", color.paint(formatted))
+    }
+
     /// Rules on how to format nlink for rendering.
     #[cfg(unix)]
     #[inline]
@@ -158,8 +542,8 @@ impl<'a> Cell<'a> {
         write!(f, "{formatted_nlink}")
     }
 
-    /// Rules on how to format ino for rendering.
-    #[cfg(unix)]
+    /// Rules on how to format ino for rendering. Entries without an inode number, e.g. on
+    /// platforms that don't expose one, get a placeholder so that the column stays aligned.
     #[inline]
     fn fmt_ino(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let node = self.node;
@@ -168,7 +552,7 @@ impl<'a> Cell<'a> {
         let max_width = ctx.max_ino_width;
 
         let out = node.ino().map_or_else(
-            || format!("{PLACEHOLDER:>max_width$}"),
+            || format!("{:>max_width$}", styles::PLACEHOLDER),
             |num| format!("{num:>max_width$}"),
         );
 
@@ -220,16 +604,15 @@ impl<'a> Cell<'a> {
         let node = self.node;
         let ctx = self.ctx;
 
-        let datetime = match ctx.time() {
-            time::Stamp::Create => node.created(),
-            time::Stamp::Access => node.accessed(),
-            time::Stamp::Mod => node.modified(),
-        };
+        let max_width = ctx.max_time_width;
 
-        let out = datetime.map(DateTime::<Local>::from).map_or_else(
-            || format!("{PLACEHOLDER:>12}"),
-            |dt| format!("{:>12}", self.fmt_timestamp(dt)),
-        );
+        let out = node
+            .timestamp(ctx.time())
+            .map(DateTime::<Local>::from)
+            .map_or_else(
+                || format!("{PLACEHOLDER:>max_width$}"),
+                |dt| format!("{:>max_width$}", ctx.time_format().apply(dt)),
+            );
 
         let formatted_datetime = if let Ok(style) = styles::get_datetime_style() {
             style.paint(out).to_string()
@@ -240,21 +623,6 @@ impl<'a> Cell<'a> {
         write!(f, "{formatted_datetime}")
     }
 
-    /// Rules on how to format timestamp
-    #[cfg(unix)]
-    #[inline]
-    fn fmt_timestamp(&self, dt: DateTime<Local>) -> String {
-        let time_format = self.ctx.time_format();
-        let delayed_format = match time_format {
-            time::Format::Default => dt.format("%d %h %H:%M %g"),
-            time::Format::Iso => dt.format("%Y-%m-%d %H:%M:%S"),
-            time::Format::IsoStrict => dt.format("%Y-%m-%dT%H:%M:%S%Z"),
-            time::Format::Short => dt.format("%Y-%m-%d"),
-        };
-
-        format!("{delayed_format:>12}")
-    }
-
     /// Rules on how to format permissions for rendering
     #[cfg(unix)]
     #[inline]
@@ -273,19 +641,35 @@ impl<'a> Cell<'a> {
 
     /// Formatter for the placeholder for file sizes.
     #[inline]
-    fn fmt_size_placeholder(f: &mut fmt::Formatter<'_>, ctx: &Context) -> fmt::Result {
-        if ctx.suppress_size || ctx.max_size_width == 0 {
+    fn fmt_size_placeholder(
+        f: &mut fmt::Formatter<'_>,
+        ctx: &Context,
+        max_size_width: usize,
+    ) -> fmt::Result {
+        if ctx.suppress_size {
+            return write!(f, "");
+        }
+
+        if matches!(ctx.size_position, size::Position::Right) {
+            return match styles::get_placeholder_style() {
+                Ok(style) => write!(f, "{}", style.paint(styles::PLACEHOLDER)),
+                Err(_) => write!(f, "{}", styles::PLACEHOLDER),
+            };
+        }
+
+        if max_size_width == 0 {
             return write!(f, "");
         }
 
-        let mut padding = ctx.max_size_width + 1;
+        let mut padding = max_size_width + 1;
 
         match ctx.disk_usage {
+            DiskUsage::Logical | DiskUsage::Physical if ctx.raw => padding -= 1,
             DiskUsage::Logical | DiskUsage::Physical => match ctx.unit {
                 PrefixKind::Si if ctx.human => padding += 2,
                 PrefixKind::Bin if ctx.human => padding += 3,
                 PrefixKind::Si => padding += 0,
-                PrefixKind::Bin => padding += 1,
+                PrefixKind::Bin | PrefixKind::Bytes => padding += 1,
             },
             _ => padding -= 1,
         }
@@ -299,17 +683,45 @@ impl<'a> Cell<'a> {
         }
     }
 
-    /// Rules to format disk usage as bytes
+    /// Widths to pad the size and unit of disk usage to. Disk usage displayed to the right of
+    /// file names isn't aligned so no padding is applied.
     #[inline]
-    fn fmt_bytes(f: &mut fmt::Formatter<'_>, metric: &byte::Metric, ctx: &Context) -> fmt::Result {
-        let max_size_width = ctx.max_size_width;
-        let max_unit_width = ctx.max_size_unit_width;
+    const fn size_widths(ctx: &Context) -> (usize, usize) {
+        match ctx.size_position {
+            size::Position::Left => (ctx.max_size_width, ctx.max_size_unit_width),
+            size::Position::Right => (0, 0),
+        }
+    }
+
+    /// Same as [`Cell::size_widths`] but for the additional file size from `--both-sizes`.
+    #[inline]
+    const fn other_size_widths(ctx: &Context) -> (usize, usize) {
+        match ctx.size_position {
+            size::Position::Left => (ctx.max_other_size_width, ctx.max_size_unit_width),
+            size::Position::Right => (0, 0),
+        }
+    }
+
+    /// Rules to format disk usage as bytes padded to the given size and unit widths.
+    #[inline]
+    fn fmt_bytes(
+        f: &mut fmt::Formatter<'_>,
+        metric: &byte::Metric,
+        ctx: &Context,
+        (max_size_width, max_unit_width): (usize, usize),
+    ) -> fmt::Result {
         let out = format!("{metric}");
 
         let [size, unit]: [&str; 2] = out.split(' ').collect::<Vec<&str>>().try_into().unwrap();
 
+        let formatted = if ctx.raw {
+            format!("{size:>max_size_width$}")
+        } else {
+            format!("{size:>max_size_width$} {unit:>max_unit_width$}")
+        };
+
         if ctx.no_color() {
-            return write!(f, "{size:>max_size_width$} {unit:>max_unit_width$}");
+            return write!(f, "{formatted}");
         }
 
         let color = if metric.human_readable {
@@ -320,14 +732,14 @@ impl<'a> Cell<'a> {
                     let pre = SiPrefix::from(metric.value);
                     styles::get_du_theme().unwrap().get(pre.as_str()).unwrap()
                 },
-                PrefixKind::Bin => {
+                PrefixKind::Bin | PrefixKind::Bytes => {
                     let pre = BinPrefix::from(metric.value);
                     styles::get_du_theme().unwrap().get(pre.as_str()).unwrap()
                 },
             }
         };
 
-        let out = color.paint(format!("{size:>max_size_width$} {unit:>max_unit_width$}"));
+        let out = color.paint(formatted);
 
         write!(f, "{out}")
     }
@@ -339,7 +751,7 @@ impl<'a> Cell<'a> {
         metric: &block::Metric,
         ctx: &Context,
     ) -> fmt::Result {
-        let max_size_width = ctx.max_size_width;
+        let (max_size_width, _) = Self::size_widths(ctx);
 
         if ctx.no_color() {
             return write!(f, "{metric:>max_size_width$}");
@@ -352,7 +764,7 @@ impl<'a> Cell<'a> {
                 let pre = SiPrefix::from(bytes);
                 styles::get_du_theme().unwrap().get(pre.as_str()).unwrap()
             },
-            PrefixKind::Bin => {
+            PrefixKind::Bin | PrefixKind::Bytes => {
                 let pre = BinPrefix::from(bytes);
                 styles::get_du_theme().unwrap().get(pre.as_str()).unwrap()
             },
@@ -370,7 +782,7 @@ impl<'a> Cell<'a> {
         metric: &M,
         ctx: &Context,
     ) -> fmt::Result {
-        let max_size_width = ctx.max_size_width;
+        let (max_size_width, _) = Self::size_widths(ctx);
 
         if ctx.no_color() {
             return write!(f, "{metric:>max_size_width$}");
@@ -387,8 +799,11 @@ impl Display for Cell<'_> {
             Kind::FileName { prefix: _prefix } => self.fmt_name(f),
             Kind::FilePath => self.fmt_path(f),
             Kind::FileSize => self.fmt_file_size(f),
+            Kind::OtherFileSize => self.fmt_other_file_size(f),
+            Kind::FileCount => self.fmt_file_count(f),
+            Kind::Extension => self.fmt_extension(f),
+            Kind::Bar => self.fmt_bar(f),
 
-            #[cfg(unix)]
             Kind::Ino => self.fmt_ino(f),
 
             #[cfg(unix)]
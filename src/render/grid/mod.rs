@@ -1,4 +1,6 @@
-use crate::{ansi::Escaped, tree::node::Node, Context};
+use crate::{
+    ansi::Escaped, context::size, disk_usage::file_size::DiskUsage, tree::node::Node, Context,
+};
 use cell::Cell;
 use std::{
     fmt::{self, Display},
@@ -33,6 +35,80 @@ impl<'a, T> Row<'a, T> {
             layout: PhantomData,
         }
     }
+
+    /// Renders the disk usage that goes before and after the file name, respectively, depending
+    /// on the [`size::Position`]. Disk usage on the left is followed by `sep` whereas disk usage
+    /// on the right is wrapped in parentheses. The bar from `--bars` is always on the left.
+    fn size_cells(&self, sep: &str) -> (String, String) {
+        let size = Cell::new(self.node, self.ctx, cell::Kind::FileSize);
+
+        // Logical size always comes first with `--both-sizes`.
+        let size = if self.ctx.count {
+            Cell::new(self.node, self.ctx, cell::Kind::FileCount).to_string()
+        } else if self.ctx.both_sizes {
+            let other = Cell::new(self.node, self.ctx, cell::Kind::OtherFileSize);
+
+            match self.ctx.disk_usage {
+                DiskUsage::Logical => format!("{size} {other}"),
+                _ => format!("{other} {size}"),
+            }
+        } else {
+            size.to_string()
+        };
+
+        if self.ctx.bars {
+            let bar = Cell::new(self.node, self.ctx, cell::Kind::Bar);
+
+            return match self.ctx.size_position {
+                size::Position::Left => (format!("{size} {bar}{sep}"), String::new()),
+                size::Position::Right => (format!("{bar}{sep}"), format!(" ({size})")),
+            };
+        }
+
+        match self.ctx.size_position {
+            size::Position::Left => (format!("{size}{sep}"), String::new()),
+            size::Position::Right if self.ctx.suppress_size => (String::new(), String::new()),
+            size::Position::Right => (String::new(), format!(" ({size})")),
+        }
+    }
+
+    /// Prepends the inode number followed by `sep` to `row` if `--ino` is enabled. It's the
+    /// left-most column regardless of whether or not the long view is enabled.
+    fn prepend_ino(&self, row: String, sep: &str) -> String {
+        if !self.ctx.ino {
+            return row;
+        }
+
+        let ino = self.own_cell(Cell::new(self.node, self.ctx, cell::Kind::Ino));
+
+        format!("{ino}{sep}{row}")
+    }
+
+    /// Cells that describe a file of its own, such as those of the long view, are left blank for
+    /// a [Node] standing in for the entries hidden by `--limit` while keeping their width.
+    fn own_cell(&self, cell: impl Display) -> String {
+        let cell = cell.to_string();
+
+        if self.node.overflow().is_none() {
+            return cell;
+        }
+
+        " ".repeat(cell.display_width())
+    }
+
+    /// Writes `row` followed by the disk usage on the right, if any. If output is to be truncated
+    /// then `row` is shortened with an ellipsis so that the line fits within the window; the disk
+    /// usage on the right is never truncated.
+    fn write_fitted(&self, f: &mut fmt::Formatter<'_>, row: &str, size_right: &str) -> fmt::Result {
+        match self.ctx.window_width {
+            Some(window_width) if self.ctx.should_truncate() => {
+                let width = window_width.saturating_sub(size_right.display_width());
+                let out = <str as Escaped>::ellipsize(row, width);
+                write!(f, "{out}{size_right}")
+            },
+            _ => write!(f, "{row}{size_right}"),
+        }
+    }
 }
 
 #[cfg(unix)]
@@ -41,7 +117,7 @@ impl Display for Row<'_, Tree> {
         let node = self.node;
         let ctx = self.ctx;
 
-        let size = Cell::new(node, ctx, cell::Kind::FileSize);
+        let (size_left, size_right) = self.size_cells(" ");
         let name = Cell::new(
             node,
             ctx,
@@ -52,20 +128,16 @@ impl Display for Row<'_, Tree> {
 
         let row = if ctx.long {
             let optionals = long::Optionals::from(ctx);
-            let long_display = long::Display::new(optionals, node, ctx);
+            let long_display = self.own_cell(long::Display::new(optionals, node, ctx));
 
-            format!("{long_display} {size} {name}")
+            format!("{long_display} {size_left}{name}")
         } else {
-            format!("{size} {name}")
+            format!("{size_left}{name}")
         };
 
-        if ctx.truncate && ctx.window_width.is_some() {
-            let window_width = ctx.window_width.unwrap();
-            let out = <str as Escaped>::truncate(&row, window_width);
-            write!(f, "{out}")
-        } else {
-            write!(f, "{row}")
-        }
+        let row = self.prepend_ino(row, " ");
+
+        self.write_fitted(f, &row, &size_right)
     }
 }
 
@@ -75,25 +147,46 @@ impl Display for Row<'_, Flat> {
         let node = self.node;
         let ctx = self.ctx;
 
-        let size = Cell::new(node, ctx, cell::Kind::FileSize);
         let path = Cell::new(node, ctx, cell::Kind::FilePath);
 
-        let row = if ctx.long {
-            let optionals = long::Optionals::from(ctx);
-            let long_display = long::Display::new(optionals, node, ctx);
+        let (row, size_right) = match (ctx.long, ctx.ext_column) {
+            (true, true) => {
+                let optionals = long::Optionals::from(ctx);
+                let long_display = self.own_cell(long::Display::new(optionals, node, ctx));
+                let ext = self.own_cell(Cell::new(node, ctx, cell::Kind::Extension));
 
-            format!("{long_display}   {size} {path}")
-        } else {
-            format!("{size}   {path}")
+                let (size_left, size_right) = self.size_cells("   ");
+
+                (
+                    format!("{long_display}   {size_left}{ext}   {path}"),
+                    size_right,
+                )
+            },
+            (true, false) => {
+                let optionals = long::Optionals::from(ctx);
+                let long_display = self.own_cell(long::Display::new(optionals, node, ctx));
+
+                let (size_left, size_right) = self.size_cells(" ");
+
+                (format!("{long_display}   {size_left}{path}"), size_right)
+            },
+            (false, true) => {
+                let ext = self.own_cell(Cell::new(node, ctx, cell::Kind::Extension));
+
+                let (size_left, size_right) = self.size_cells("   ");
+
+                (format!("{size_left}{ext}   {path}"), size_right)
+            },
+            (false, false) => {
+                let (size_left, size_right) = self.size_cells("   ");
+
+                (format!("{size_left}{path}"), size_right)
+            },
         };
 
-        if ctx.truncate && ctx.window_width.is_some() {
-            let window_width = ctx.window_width.unwrap();
-            let out = <str as Escaped>::truncate(&row, window_width);
-            write!(f, "{out}")
-        } else {
-            write!(f, "{row}")
-        }
+        let row = self.prepend_ino(row, "   ");
+
+        self.write_fitted(f, &row, &size_right)
     }
 }
 
@@ -103,7 +196,7 @@ impl Display for Row<'_, Tree> {
         let node = self.node;
         let ctx = self.ctx;
 
-        let size = Cell::new(node, ctx, cell::Kind::FileSize);
+        let (size_left, size_right) = self.size_cells(" ");
         let name = Cell::new(
             node,
             ctx,
@@ -112,15 +205,9 @@ impl Display for Row<'_, Tree> {
             },
         );
 
-        let row = format!("{size} {name}");
+        let row = self.prepend_ino(format!("{size_left}{name}"), " ");
 
-        if ctx.truncate && ctx.window_width.is_some() {
-            let window_width = ctx.window_width.unwrap();
-            let out = <str as Escaped>::truncate(&row, window_width);
-            write!(f, "{out}")
-        } else {
-            write!(f, "{row}")
-        }
+        self.write_fitted(f, &row, &size_right)
     }
 }
 
@@ -130,17 +217,19 @@ impl Display for Row<'_, Flat> {
         let node = self.node;
         let ctx = self.ctx;
 
-        let size = Cell::new(node, ctx, cell::Kind::FileSize);
         let path = Cell::new(node, ctx, cell::Kind::FilePath);
+        let (size_left, size_right) = self.size_cells("   ");
 
-        let row = format!("{size}   {path}");
+        let row = if ctx.ext_column {
+            let ext = self.own_cell(Cell::new(node, ctx, cell::Kind::Extension));
 
-        if ctx.truncate && ctx.window_width.is_some() {
-            let window_width = ctx.window_width.unwrap();
-            let out = <str as Escaped>::truncate(&row, window_width);
-            write!(f, "{out}")
+            format!("{size_left}{ext}   {path}")
         } else {
-            write!(f, "{row}")
-        }
+            format!("{size_left}{path}")
+        };
+
+        let row = self.prepend_ino(row, "   ");
+
+        self.write_fitted(f, &row, &size_right)
     }
 }
@@ -0,0 +1,71 @@
+use crate::{
+    disk_usage::file_size::FileSize,
+    render::{Csv, Engine},
+};
+use std::{
+    borrow::Cow,
+    fmt::{self, Display},
+};
+
+/// Column names of the header row.
+const HEADER: &str = "depth,path,file_type,size,ino,nlink";
+
+impl Display for Engine<'_, Csv> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ctx = self.context();
+        let tree = self.tree();
+        let arena = tree.arena();
+        let root_id = tree.root_id();
+        let max_depth = ctx.level();
+
+        if !ctx.no_header {
+            writeln!(f, "{HEADER}")?;
+        }
+
+        for node_id in root_id.descendants(arena) {
+            let node = arena[node_id].get();
+
+            if node.depth() > max_depth {
+                continue;
+            }
+
+            let size = node
+                .file_size()
+                .filter(|_| !ctx.suppress_size)
+                .map(FileSize::value)
+                .map(|value| value.to_string())
+                .unwrap_or_default();
+
+            let inode = node.inode();
+            let ino = inode.map(|i| i.ino.to_string()).unwrap_or_default();
+            let nlink = inode.map(|i| i.nlink.to_string()).unwrap_or_default();
+
+            writeln!(
+                f,
+                "{},{},{},{size},{ino},{nlink}",
+                node.depth(),
+                escape(&node.path().to_string_lossy()),
+                node.file_type_identifier(),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Quotes `field` if it contains a comma, double-quote, or line break, doubling any
+/// double-quotes within it as per RFC 4180.
+fn escape(field: &str) -> Cow<'_, str> {
+    if !field.contains([',', '"', '\n', '\r']) {
+        return Cow::from(field);
+    }
+
+    Cow::from(format!("\"{}\"", field.replace('"', "\"\"")))
+}
+
+#[test]
+fn test_escape() {
+    assert_eq!(escape("data/nemesis.txt"), "data/nemesis.txt");
+    assert_eq!(escape("a,b.txt"), "\"a,b.txt\"");
+    assert_eq!(escape("say \"hi\".txt"), "\"say \"\"hi\"\".txt\"");
+}
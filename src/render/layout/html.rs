@@ -0,0 +1,232 @@
+use crate::render::{Engine, Html};
+use ansi_term::{Color, Style};
+use indextree::NodeEdge;
+use std::{
+    borrow::Cow,
+    fmt::{self, Display},
+};
+
+/// Styles shared by every document. Entries are colored by file type unless `LS_COLORS` provides
+/// a more specific style which is inlined on the entry's name. Directories with entries are
+/// wrapped in `<details>` so that they can be collapsed without any scripting.
+const STYLESHEET: &str = "\
+body { background: #1e1e1e; color: #d4d4d4; font-family: monospace; }
+ul { list-style: none; margin: 0; padding-left: 2ch; border-left: 1px solid #555; }
+ul.tree { padding-left: 0; border-left: none; }
+summary { cursor: pointer; }
+.size { display: inline-block; min-width: 10ch; margin-right: 1ch; text-align: right; color: #888; }
+.directory > .name, .directory > details > summary > .name { color: #5c5cff; font-weight: bold; }
+.symlink > .name { color: #00cdcd; }
+.other > .name { color: #cdcd00; }";
+
+impl Display for Engine<'_, Html> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ctx = self.context();
+        let tree = self.tree();
+        let arena = tree.arena();
+        let root_id = tree.root_id();
+        let max_depth = ctx.level();
+        let title = escape(&arena[root_id].get().file_name().to_string_lossy()).into_owned();
+
+        writeln!(f, "<!DOCTYPE html>")?;
+        writeln!(f, "<html>")?;
+        writeln!(f, "<head>")?;
+        writeln!(f, "<meta charset=\"utf-8\">")?;
+        writeln!(f, "<title>{title}</title>")?;
+        writeln!(f, "<style>\n{STYLESHEET}\n</style>")?;
+        writeln!(f, "</head>")?;
+        writeln!(f, "<body>")?;
+        writeln!(f, "<ul class=\"tree\">")?;
+
+        for edge in root_id.traverse(arena) {
+            match edge {
+                NodeEdge::Start(id) => {
+                    let node = arena[id].get();
+                    let depth = node.depth();
+
+                    if depth > max_depth {
+                        continue;
+                    }
+
+                    let indent = "  ".repeat(depth + 1);
+                    let class = node.file_type_identifier();
+                    let is_branch = id.children(arena).next().is_some() && depth < max_depth;
+
+                    write!(f, "{indent}<li class=\"{class}\">")?;
+
+                    if is_branch {
+                        write!(f, "<details open><summary>")?;
+                    }
+
+                    if !ctx.suppress_size {
+                        let size = node
+                            .file_size()
+                            .map(ToString::to_string)
+                            .unwrap_or_default();
+                        write!(f, "<span class=\"size\">{}</span>", escape(&size))?;
+                    }
+
+                    if ctx.icons {
+                        let icon = node.compute_icon(true);
+                        write!(f, "<span class=\"icon\">{}</span> ", escape(&icon))?;
+                    }
+
+                    let name = escape(&node.file_name().to_string_lossy()).into_owned();
+
+                    match node.style().map(|style| css(&style)) {
+                        Some(css) if !css.is_empty() => {
+                            write!(f, "<span class=\"name\" style=\"{css}\">{name}</span>")?;
+                        },
+                        _ => write!(f, "<span class=\"name\">{name}</span>")?,
+                    }
+
+                    if is_branch {
+                        writeln!(f, "</summary>\n{indent}<ul>")?;
+                    } else {
+                        writeln!(f, "</li>")?;
+                    }
+                },
+
+                NodeEdge::End(id) => {
+                    let depth = arena[id].get().depth();
+
+                    if depth >= max_depth || id.children(arena).next().is_none() {
+                        continue;
+                    }
+
+                    let indent = "  ".repeat(depth + 1);
+
+                    writeln!(f, "{indent}</ul>")?;
+                    writeln!(f, "{indent}</details></li>")?;
+                },
+            }
+        }
+
+        writeln!(f, "</ul>")?;
+        writeln!(f, "</body>")?;
+        write!(f, "</html>")
+    }
+}
+
+/// Escapes characters that have special meaning in HTML so that file names can't break markup.
+fn escape(text: &str) -> Cow<'_, str> {
+    if !text.contains(['&', '<', '>', '"', '\'']) {
+        return Cow::from(text);
+    }
+
+    let mut escaped = String::with_capacity(text.len());
+
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+
+    Cow::from(escaped)
+}
+
+/// Translates a [Style], usually derived from `LS_COLORS`, into inline CSS declarations.
+fn css(style: &Style) -> String {
+    let mut declarations = vec![];
+
+    if let Some(fg) = style.foreground {
+        declarations.push(format!("color: {}", hex(fg)));
+    }
+
+    if let Some(bg) = style.background {
+        declarations.push(format!("background: {}", hex(bg)));
+    }
+
+    if style.is_bold {
+        declarations.push(String::from("font-weight: bold"));
+    }
+
+    if style.is_dimmed {
+        declarations.push(String::from("opacity: 0.6"));
+    }
+
+    if style.is_italic {
+        declarations.push(String::from("font-style: italic"));
+    }
+
+    if style.is_underline {
+        declarations.push(String::from("text-decoration: underline"));
+    }
+
+    declarations.join("; ")
+}
+
+/// Hexadecimal representation of a terminal [Color] using the xterm palette.
+fn hex(color: Color) -> String {
+    /// The 16 standard and bright colors of the xterm palette.
+    const BASE: [(u8, u8, u8); 16] = [
+        (0, 0, 0),
+        (205, 0, 0),
+        (0, 205, 0),
+        (205, 205, 0),
+        (0, 0, 238),
+        (205, 0, 205),
+        (0, 205, 205),
+        (229, 229, 229),
+        (127, 127, 127),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (92, 92, 255),
+        (255, 0, 255),
+        (0, 255, 255),
+        (255, 255, 255),
+    ];
+
+    let cube = |level: u8| if level == 0 { 0 } else { 55 + 40 * level };
+
+    let (r, g, b) = match color {
+        Color::Black => BASE[0],
+        Color::Red => BASE[1],
+        Color::Green => BASE[2],
+        Color::Yellow => BASE[3],
+        Color::Blue => BASE[4],
+        Color::Purple => BASE[5],
+        Color::Cyan => BASE[6],
+        Color::White => BASE[7],
+        Color::Fixed(n @ 0..=15) => BASE[usize::from(n)],
+        Color::Fixed(n @ 16..=231) => {
+            let n = n - 16;
+            (cube(n / 36), cube((n / 6) % 6), cube(n % 6))
+        },
+        Color::Fixed(n) => {
+            let gray = 8 + 10 * (n - 232);
+            (gray, gray, gray)
+        },
+        Color::RGB(r, g, b) => (r, g, b),
+    };
+
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+#[test]
+fn test_escape() {
+    assert_eq!(escape("nemesis.txt"), "nemesis.txt");
+    assert_eq!(escape("<a&b>.txt"), "&lt;a&amp;b&gt;.txt");
+    assert_eq!(escape("say \"hi\".txt"), "say &quot;hi&quot;.txt");
+}
+
+#[test]
+fn test_css() {
+    assert_eq!(css(&Style::default()), "");
+    assert_eq!(
+        css(&Color::Blue.bold()),
+        "color: #0000ee; font-weight: bold"
+    );
+    assert_eq!(css(&Color::Fixed(196).normal()), "color: #ff0000");
+    assert_eq!(css(&Color::Fixed(244).normal()), "color: #808080");
+    assert_eq!(
+        css(&Color::RGB(1, 2, 3).underline()),
+        "color: #010203; text-decoration: underline"
+    );
+}
@@ -1,14 +1,10 @@
-use crate::{
-    render::{
-        grid::{self, Row},
-        theme, Engine, Inverted,
-    },
-    styles,
-    tree::{count::FileCount, Tree},
+use crate::render::{
+    grid::{self, Row},
+    theme, Engine, Inverted,
 };
 use std::fmt::{self, Display};
 
-impl Display for Engine<Inverted> {
+impl Display for Engine<'_, Inverted> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let ctx = self.context();
         let tree = self.tree();
@@ -16,15 +12,12 @@ impl Display for Engine<Inverted> {
         let root_id = tree.root_id();
         let arena = tree.arena();
         let level = ctx.level();
-        let mut file_count_data = vec![];
 
         let mut descendants = root_id.descendants(arena).skip(1).peekable();
 
         let root = Row::<grid::Tree>::new(arena[root_id].get(), ctx, Some(""));
         writeln!(f, "{root}")?;
 
-        file_count_data.push(Tree::compute_file_count(root_id, arena));
-
         let mut get_theme = if ctx.follow {
             theme::link_theme_getter()
         } else {
@@ -34,8 +27,6 @@ impl Display for Engine<Inverted> {
         let mut base_prefix_components = vec![""];
 
         while let Some(current_node_id) = descendants.next() {
-            file_count_data.push(Tree::compute_file_count(current_node_id, arena));
-
             let current_node = arena[current_node_id].get();
 
             let current_depth = current_node.depth();
@@ -63,30 +54,27 @@ impl Display for Engine<Inverted> {
                 writeln!(f, "{row}")?;
             }
 
-            if let Some(next_id) = descendants.peek() {
-                let next_node = arena[*next_id].get();
-
-                let next_depth = next_node.depth();
+            // Relationships rather than depths determine the prefix as depths of adjacent nodes
+            // may differ by more than one level if `--collapse` merged a chain of directories.
+            if let Some(&next_id) = descendants.peek() {
+                let next_parent_id = arena[next_id].parent();
 
-                if next_depth == current_depth + 1 {
+                if next_parent_id == Some(current_node_id) {
                     if last_sibling {
-                        base_prefix_components.push(styles::SEP);
+                        base_prefix_components.push(theme.get("sep").unwrap());
                     } else {
                         let prefix = theme.get("vt").unwrap();
                         base_prefix_components.push(prefix);
                     }
-                } else if next_depth < current_depth {
-                    let depth_delta = current_depth - next_depth;
-
+                } else if let Some(depth_delta) = current_node_id
+                    .ancestors(arena)
+                    .position(|id| arena[id].parent() == next_parent_id)
+                {
                     base_prefix_components.truncate(base_prefix_components.len() - depth_delta);
                 }
             }
         }
 
-        if !file_count_data.is_empty() {
-            write!(f, "\n{}", FileCount::from(file_count_data))?;
-        }
-
-        Ok(())
+        self.write_count(f, tree.file_count())
     }
 }
@@ -0,0 +1,95 @@
+use crate::{
+    disk_usage::file_size::FileSize,
+    render::{Engine, Json},
+};
+use indextree::NodeEdge;
+use serde_json::{json, Map, Value};
+use std::fmt::{self, Display};
+
+impl Display for Engine<'_, Json> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ctx = self.context();
+        let tree = self.tree();
+        let arena = tree.arena();
+        let root_id = tree.root_id();
+        let max_depth = ctx.level();
+
+        // Objects for directories that are still having their children serialized. Nodes are
+        // visited without recursion so that the depth of the tree doesn't affect the stack.
+        let mut stack: Vec<Map<String, Value>> = vec![];
+        let mut root = None;
+
+        for edge in root_id.traverse(arena) {
+            match edge {
+                NodeEdge::Start(id) => {
+                    let node = arena[id].get();
+
+                    if node.depth() > max_depth {
+                        continue;
+                    }
+
+                    let mut object = Map::new();
+
+                    object.insert(
+                        "file_name".into(),
+                        json!(node.file_name().to_string_lossy()),
+                    );
+                    object.insert("path".into(), json!(node.path().to_string_lossy()));
+                    object.insert("file_type".into(), json!(node.file_type_identifier()));
+                    object.insert("depth".into(), json!(node.depth()));
+                    object.insert(
+                        "symlink_target".into(),
+                        json!(node
+                            .symlink_target_path()
+                            .map(|target| target.to_string_lossy())),
+                    );
+
+                    if !ctx.suppress_size {
+                        let file_size = node.file_size();
+
+                        object.insert("size".into(), json!(file_size.map(FileSize::value)));
+                        object.insert(
+                            "formatted_size".into(),
+                            json!(file_size.map(ToString::to_string)),
+                        );
+                    }
+
+                    let inode = node.inode();
+
+                    object.insert("ino".into(), json!(inode.map(|i| i.ino)));
+                    object.insert("nlink".into(), json!(inode.map(|i| i.nlink)));
+
+                    if node.is_dir() {
+                        object.insert("children".into(), json!([]));
+                    }
+
+                    stack.push(object);
+                },
+
+                NodeEdge::End(id) => {
+                    if arena[id].get().depth() > max_depth {
+                        continue;
+                    }
+
+                    let Some(object) = stack.pop() else {
+                        continue;
+                    };
+
+                    match stack.last_mut() {
+                        Some(parent) => {
+                            if let Some(Value::Array(children)) = parent.get_mut("children") {
+                                children.push(Value::Object(object));
+                            }
+                        },
+                        None => root = Some(Value::Object(object)),
+                    }
+                },
+            }
+        }
+
+        let out =
+            serde_json::to_string_pretty(&root.unwrap_or_default()).map_err(|_| fmt::Error)?;
+
+        write!(f, "{out}")
+    }
+}
@@ -0,0 +1,55 @@
+use crate::{
+    render::{Engine, List},
+    styles,
+};
+use indextree::NodeEdge;
+use std::{
+    collections::HashSet,
+    fmt::{self, Display},
+};
+
+impl Display for Engine<'_, List> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ctx = self.context();
+        let tree = self.tree();
+        let arena = tree.arena();
+        let root_id = tree.root_id();
+        let max_depth = ctx.level();
+        let mut inodes = HashSet::new();
+
+        for edge in root_id.traverse(arena) {
+            let node_id = match edge {
+                NodeEdge::Start(id) => id,
+                NodeEdge::End(_) => continue,
+            };
+
+            let node = arena[node_id].get();
+
+            if node.depth() > max_depth {
+                continue;
+            }
+
+            if ctx.dedupe_hardlinks && !node.is_dir() {
+                if let Some(inode) = node.inode() {
+                    if inode.nlink > 1 && !inodes.insert(inode) {
+                        continue;
+                    }
+                }
+            }
+
+            let path = node.path().display();
+
+            if !ctx.list_size {
+                writeln!(f, "{path}")?;
+                continue;
+            }
+
+            match node.file_size() {
+                Some(file_size) => writeln!(f, "{file_size}\t{path}")?,
+                None => writeln!(f, "{}\t{path}", styles::PLACEHOLDER)?,
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use crate::render::{Engine, Markdown};
+use std::{
+    borrow::Cow,
+    fmt::{self, Display},
+};
+
+impl Display for Engine<'_, Markdown> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ctx = self.context();
+        let tree = self.tree();
+        let arena = tree.arena();
+        let root_id = tree.root_id();
+        let max_depth = ctx.level();
+
+        for node_id in root_id.descendants(arena) {
+            let node = arena[node_id].get();
+            let depth = node.depth();
+
+            if depth > max_depth {
+                continue;
+            }
+
+            let indent = "  ".repeat(depth);
+            let name = escape(&node.file_name().to_string_lossy()).into_owned();
+            let slash = if node.is_dir() { "/" } else { "" };
+
+            let size = node
+                .file_size()
+                .filter(|_| !ctx.suppress_size)
+                .map(|file_size| format!("`{file_size}` "))
+                .unwrap_or_default();
+
+            writeln!(f, "{indent}- {size}{name}{slash}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Escapes characters that Markdown would otherwise interpret as formatting with a backslash.
+fn escape(text: &str) -> Cow<'_, str> {
+    const SPECIAL: [char; 11] = ['\\', '`', '*', '_', '[', ']', '<', '>', '#', '|', '~'];
+
+    if !text.contains(SPECIAL) {
+        return Cow::from(text);
+    }
+
+    let mut escaped = String::with_capacity(text.len() * 2);
+
+    for ch in text.chars() {
+        if SPECIAL.contains(&ch) {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+
+    Cow::from(escaped)
+}
+
+#[test]
+fn test_escape() {
+    assert_eq!(escape("nemesis.txt"), "nemesis.txt");
+    assert_eq!(escape("__init__.py"), "\\_\\_init\\_\\_.py");
+    assert_eq!(escape("[draft] *notes*.md"), "\\[draft\\] \\*notes\\*.md");
+}
@@ -9,3 +9,24 @@ pub mod flat_inverted;
 
 /// See [`super::Inverted`]
 pub mod inverted;
+
+/// See [`super::Json`]
+pub mod json;
+
+/// See [`super::Csv`]
+pub mod csv;
+
+/// See [`super::Top`]
+pub mod top;
+
+/// See [`super::List`]
+pub mod list;
+
+/// See [`super::Html`]
+pub mod html;
+
+/// See [`super::Ncdu`]
+pub mod ncdu;
+
+/// See [`super::Markdown`]
+pub mod markdown;
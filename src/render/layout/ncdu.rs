@@ -0,0 +1,141 @@
+use crate::{
+    render::{Engine, Ncdu},
+    tree::node::Node,
+};
+use indextree::NodeEdge;
+use serde_json::{json, Map, Value};
+use std::{
+    fmt::{self, Display},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+#[cfg(unix)]
+use crate::disk_usage::file_size::BLOCK_SIZE_BYTES;
+
+/// Major version of the ncdu export format.
+const MAJOR_VERSION: u8 = 1;
+
+/// Minor version of the ncdu export format.
+const MINOR_VERSION: u8 = 2;
+
+/// Renders the tree as `[1, 2, {metadata}, [root, ...]]` where each directory is an array whose
+/// first element describes the directory itself and whose remaining elements are its entries.
+/// Sizes are those of each entry on its own as ncdu computes the totals of directories itself, so
+/// the entire tree is exported regardless of `--level`.
+impl Display for Engine<'_, Ncdu> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tree = self.tree();
+        let arena = tree.arena();
+        let root_id = tree.root_id();
+
+        // Arrays for directories that are still having their entries serialized. Nodes are
+        // visited without recursion so that the depth of the tree doesn't affect the stack.
+        let mut stack: Vec<Vec<Value>> = vec![];
+        let mut root = None;
+
+        for edge in root_id.traverse(arena) {
+            match edge {
+                NodeEdge::Start(id) => {
+                    let node = arena[id].get();
+                    let parent = arena[id].parent().map(|parent_id| arena[parent_id].get());
+                    let info = Value::Object(info(node, parent));
+
+                    if node.is_dir() {
+                        stack.push(vec![info]);
+                    } else if let Some(entries) = stack.last_mut() {
+                        entries.push(info);
+                    } else {
+                        root = Some(info);
+                    }
+                },
+
+                NodeEdge::End(id) => {
+                    if !arena[id].get().is_dir() {
+                        continue;
+                    }
+
+                    let Some(entries) = stack.pop() else {
+                        continue;
+                    };
+
+                    match stack.last_mut() {
+                        Some(parent) => parent.push(Value::Array(entries)),
+                        None => root = Some(Value::Array(entries)),
+                    }
+                },
+            }
+        }
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+
+        let metadata = json!({
+            "progname": env!("CARGO_PKG_NAME"),
+            "progver": env!("CARGO_PKG_VERSION"),
+            "timestamp": timestamp,
+        });
+
+        let export = json!([
+            MAJOR_VERSION,
+            MINOR_VERSION,
+            metadata,
+            root.unwrap_or_default()
+        ]);
+
+        let out = serde_json::to_string(&export).map_err(|_| fmt::Error)?;
+
+        write!(f, "{out}")
+    }
+}
+
+/// The object describing a single entry. The root, which has no `parent`, is named by its full
+/// path as ncdu does. The device number is only noted for the root and for directories that are on
+/// a different device than their parent.
+fn info(node: &Node, parent: Option<&Node>) -> Map<String, Value> {
+    let mut object = Map::new();
+
+    let name = if parent.is_none() {
+        node.path().to_string_lossy()
+    } else {
+        node.file_name().to_string_lossy()
+    };
+
+    let asize = node.apparent_size();
+
+    #[cfg(unix)]
+    let dsize = node.blocks().unwrap_or(0) * u64::from(BLOCK_SIZE_BYTES);
+
+    #[cfg(not(unix))]
+    let dsize = asize;
+
+    object.insert("name".into(), json!(name));
+    object.insert("asize".into(), json!(asize));
+    object.insert("dsize".into(), json!(dsize));
+
+    if let Some(inode) = node.inode() {
+        let parent_dev = parent
+            .and_then(Node::inode)
+            .map(|parent_inode| parent_inode.dev);
+
+        if parent_dev != Some(inode.dev) {
+            object.insert("dev".into(), json!(inode.dev));
+        }
+
+        object.insert("ino".into(), json!(inode.ino));
+
+        if inode.nlink > 1 && !node.is_dir() {
+            object.insert("hlnkc".into(), json!(true));
+        }
+    }
+
+    let is_regular = node
+        .file_type()
+        .is_some_and(|file_type| file_type.is_file());
+
+    if !node.is_dir() && !is_regular {
+        object.insert("notreg".into(), json!(true));
+    }
+
+    object
+}
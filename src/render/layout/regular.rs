@@ -1,22 +1,17 @@
-use crate::{
-    render::{
-        grid::{self, Row},
-        theme, Engine, Regular,
-    },
-    styles,
-    tree::{count::FileCount, Tree},
+use crate::render::{
+    grid::{self, Row},
+    theme, Engine, Regular,
 };
 use indextree::NodeEdge;
 use std::fmt::{self, Display};
 
-impl Display for Engine<Regular> {
+impl Display for Engine<'_, Regular> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let ctx = self.context();
         let tree = self.tree();
         let root_id = tree.root_id();
         let arena = tree.arena();
         let max_depth = ctx.level();
-        let mut file_count_data = vec![];
 
         let mut get_theme = if ctx.follow {
             theme::link_theme_getter()
@@ -44,7 +39,7 @@ impl Display for Engine<Regular> {
                     let topmost_sibling = id.following_siblings(arena).nth(1).is_none();
 
                     if topmost_sibling {
-                        base_prefix_components.push(styles::SEP);
+                        base_prefix_components.push(theme.get("sep").unwrap());
                     } else {
                         base_prefix_components.push(theme.get("vt").unwrap());
                     }
@@ -53,8 +48,6 @@ impl Display for Engine<Regular> {
                 },
             };
 
-            file_count_data.push(Tree::compute_file_count(current_node_id, arena));
-
             let current_node = arena[current_node_id].get();
 
             let node_depth = current_node.depth();
@@ -94,10 +87,6 @@ impl Display for Engine<Regular> {
             }
         }
 
-        if !file_count_data.is_empty() {
-            write!(f, "\n{}", FileCount::from(file_count_data))?;
-        }
-
-        Ok(())
+        self.write_count(f, tree.file_count())
     }
 }
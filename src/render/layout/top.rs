@@ -0,0 +1,56 @@
+use crate::{
+    disk_usage::file_size::FileSize,
+    render::{
+        grid::{self, Row},
+        Engine, Top,
+    },
+    tree::count::FileCount,
+};
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    fmt::{self, Display},
+};
+
+impl Display for Engine<'_, Top> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ctx = self.context();
+        let tree = self.tree();
+        let arena = tree.arena();
+        let root_id = tree.root_id();
+        let n = ctx.top.unwrap_or_default();
+
+        // Min-heap of the largest files seen so far whose top is the smallest of them, so that
+        // only `n` files are held onto at any one time. Ties are broken by path so that output is
+        // deterministic.
+        let mut largest = BinaryHeap::with_capacity(n + 1);
+
+        for node_id in root_id.descendants(arena) {
+            let node = arena[node_id].get();
+
+            if node.is_dir() {
+                continue;
+            }
+
+            let size = node.file_size().map_or(0, FileSize::value);
+            largest.push(Reverse((size, Reverse(node.path()), node_id)));
+
+            if largest.len() > n {
+                largest.pop();
+            }
+        }
+
+        let mut file_count = FileCount::default();
+
+        // Ascending order of `Reverse` is largest first.
+        for Reverse((_, _, node_id)) in largest.into_sorted_vec() {
+            let node = arena[node_id].get();
+            let row = Row::<grid::Flat>::new(node, ctx, None);
+            writeln!(f, "{row}")?;
+
+            file_count += node;
+        }
+
+        self.write_count(f, file_count)
+    }
+}
@@ -18,7 +18,6 @@ pub struct Optionals {
     #[allow(dead_code)]
     owner: bool,
     group: bool,
-    ino: bool,
     #[allow(dead_code)]
     nlink: bool,
     #[allow(dead_code)]
@@ -44,7 +43,6 @@ impl Default for Optionals {
             perms: true,
             owner: true,
             group: false,
-            ino: false,
             nlink: false,
             time: true,
         }
@@ -54,9 +52,7 @@ impl Default for Optionals {
 impl fmt::Display for Display<'_> {
     /// Formatting the attributes associated with the long view.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let Optionals {
-            group, ino, nlink, ..
-        } = self.optional;
+        let Optionals { group, nlink, .. } = self.optional;
         let node = self.node;
         let ctx = self.ctx;
 
@@ -64,73 +60,39 @@ impl fmt::Display for Display<'_> {
         let owner = Cell::new(node, ctx, cell::Kind::Owner);
         let time = Cell::new(node, ctx, cell::Kind::Datetime);
 
-        match (group, ino, nlink) {
-            (false, false, false) => {
+        match (group, nlink) {
+            (false, false) => {
                 write!(f, "{perms} {owner} {time}")
             },
 
-            (true, true, true) => {
-                let group_out = Cell::new(node, ctx, cell::Kind::Group);
-                let ino_out = Cell::new(node, ctx, cell::Kind::Ino);
-                let nlink_out = Cell::new(node, ctx, cell::Kind::Nlink);
-
-                write!(
-                    f,
-                    "{ino_out} {perms} {nlink_out} {owner} {group_out} {time}"
-                )
-            },
-
-            (true, false, false) => {
+            (true, false) => {
                 let group_out = Cell::new(node, ctx, cell::Kind::Group);
 
                 write!(f, "{perms} {owner} {group_out} {time}")
             },
 
-            (true, true, false) => {
-                let group_out = Cell::new(node, ctx, cell::Kind::Group);
-                let ino_out = Cell::new(node, ctx, cell::Kind::Ino);
-
-                write!(f, "{ino_out} {perms} {owner} {group_out} {time}")
-            },
-
-            (false, false, true) => {
+            (false, true) => {
                 let nlink_out = Cell::new(node, ctx, cell::Kind::Nlink);
 
                 write!(f, "{perms} {nlink_out} {owner} {time}")
             },
 
-            (true, false, true) => {
+            (true, true) => {
                 let group_out = Cell::new(node, ctx, cell::Kind::Group);
                 let nlink_out = Cell::new(node, ctx, cell::Kind::Nlink);
 
                 write!(f, "{perms} {nlink_out} {owner} {group_out} {time}")
             },
-
-            (false, true, false) => {
-                let ino_out = Cell::new(node, ctx, cell::Kind::Ino);
-
-                write!(f, "{ino_out} {perms} {owner} {time}")
-            },
-
-            (false, true, true) => {
-                let ino_out = Cell::new(node, ctx, cell::Kind::Ino);
-                let nlink_out = Cell::new(node, ctx, cell::Kind::Nlink);
-
-                write!(f, "{ino_out} {perms} {nlink_out} {owner} {time}")
-            },
         }
     }
 }
 
 impl From<&Context> for Optionals {
     fn from(ctx: &Context) -> Self {
-        let Context {
-            group, ino, nlink, ..
-        } = *ctx;
+        let Context { group, nlink, .. } = *ctx;
 
         Self {
             group,
-            ino,
             nlink,
             ..Self::default()
         }
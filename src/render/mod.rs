@@ -1,5 +1,11 @@
-use crate::{context::Context, tree::Tree};
-use std::marker::PhantomData;
+use crate::{
+    context::Context,
+    tree::{count::FileCount, Tree},
+};
+use std::{
+    fmt::{self, Write},
+    marker::PhantomData,
+};
 
 /// Module containing all of the layout variants.
 pub mod layout;
@@ -17,9 +23,9 @@ pub mod long;
 
 /// The struct that is generic over T, which is generally expected to be a unit-struct that
 /// ultimately determines which variant to use for the output.
-pub struct Engine<T> {
-    ctx: Context,
-    tree: Tree,
+pub struct Engine<'a, T> {
+    ctx: &'a Context,
+    tree: &'a Tree,
     layout: PhantomData<T>,
 }
 
@@ -36,9 +42,30 @@ pub struct Regular;
 /// `tree` command.
 pub struct Inverted;
 
-impl<T> Engine<T> {
+/// The tree serialized as a nested JSON document for consumption by other programs.
+pub struct Json;
+
+/// One comma-separated row per node for consumption by spreadsheets and the like.
+pub struct Csv;
+
+/// A flat list of only the largest files, ranked by disk usage.
+pub struct Top;
+
+/// Nothing but the full path of each node, one per line, for consumption by other programs.
+pub struct List;
+
+/// The tree as a self-contained HTML document of nested lists for embedding in reports.
+pub struct Html;
+
+/// The tree in the JSON export format of ncdu so that it can be browsed with `ncdu -f`.
+pub struct Ncdu;
+
+/// The tree as a nested Markdown list for pasting into READMEs and issues.
+pub struct Markdown;
+
+impl<'a, T> Engine<'a, T> {
     /// Initializes a new [Engine].
-    pub const fn new(tree: Tree, ctx: Context) -> Self {
+    pub const fn new(tree: &'a Tree, ctx: &'a Context) -> Self {
         Self {
             ctx,
             tree,
@@ -48,11 +75,34 @@ impl<T> Engine<T> {
 
     /// Getter for the inner [Context] object.
     const fn context(&self) -> &Context {
-        &self.ctx
+        self.ctx
     }
 
     /// Getter for the inner [Tree] data structure.
     const fn tree(&self) -> &Tree {
-        &self.tree
+        self.tree
+    }
+
+    /// Writes the trailing summary of file counts unless `--no-count` is enabled. With `--stats`
+    /// the aggregate disk usage of the root is included as well.
+    fn write_count(&self, f: &mut impl Write, file_count: FileCount) -> fmt::Result {
+        let ctx = self.context();
+
+        if ctx.no_count || file_count.is_empty() {
+            return Ok(());
+        }
+
+        write!(f, "\n{file_count}")?;
+
+        if !ctx.stats {
+            return Ok(());
+        }
+
+        let tree = self.tree();
+
+        tree.arena()[tree.root_id()]
+            .get()
+            .file_size()
+            .map_or(Ok(()), |file_size| write!(f, ", {file_size} total"))
     }
 }
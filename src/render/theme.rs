@@ -22,7 +22,7 @@ pub fn link_theme_getter() -> Theme {
         let current_depth = node.depth();
 
         if let Some(ldepth) = link_depth {
-            if current_depth == ldepth {
+            if current_depth <= ldepth {
                 link_depth = None;
             }
         }
@@ -38,17 +38,76 @@ pub fn link_theme_getter() -> Theme {
     })
 }
 
+/// Computes a style whose color lies on a gradient from green to yellow to red depending on how
+/// large `size` is relative to `max_size`, using the 256-color palette. Sizes greater than
+/// `max_size` are colored red.
+pub fn size_gradient_style(size: u64, max_size: u64) -> Style {
+    let ratio = if max_size == 0 {
+        0.0
+    } else {
+        size as f64 / max_size as f64
+    };
+
+    gradient_style(ratio)
+}
+
+/// Computes a style whose color lies on a gradient from green to yellow to red depending on
+/// `ratio`, which is clamped between 0 and 1, using the 256-color palette.
+pub fn gradient_style(ratio: f64) -> Style {
+    let ratio = ratio.clamp(0.0, 1.0);
+
+    // Components of the 6x6x6 color cube; red ramps up over the first half of the gradient and
+    // green ramps down over the second.
+    let red = (ratio * 2.0).min(1.0).mul_add(5.0, 0.5) as u8;
+    let green = ((1.0 - ratio) * 2.0).min(1.0).mul_add(5.0, 0.5) as u8;
+
+    Style::new().fg(Color::Fixed(16 + 36 * red + 6 * green))
+}
+
+/// Computes a style whose color lies on a grayscale gradient from bright to dim depending on
+/// `ratio`, which is clamped between 0 and 1, using the 256-color palette. The dimmest shade is
+/// kept light enough to remain legible on dark backgrounds.
+pub fn age_gradient_style(ratio: f64) -> Style {
+    let ratio = ratio.clamp(0.0, 1.0);
+    let shade = ratio.mul_add(-15.0, 255.5) as u8;
+
+    Style::new().fg(Color::Fixed(shade))
+}
+
+/// Depth at which `--dim-depth` starts dimming entries.
+const DIM_DEPTH: usize = 2;
+
+/// Layers the dim attribute on top of `style` for entries at least [`DIM_DEPTH`] deep, leaving
+/// their base color intact. Dimming is the faintest intensity available so very deep entries are
+/// never faded beyond it and remain legible.
+pub const fn dim_by_depth(style: Style, depth: usize) -> Style {
+    if depth < DIM_DEPTH {
+        return style;
+    }
+
+    Style {
+        is_bold: false,
+        is_dimmed: true,
+        ..style
+    }
+}
+
 /// Stylizes the input `file_name` with the provided `style`. If `None` is provided then the
 /// underlying `String` is returned unmodified as a [Cow]. If the provided [Node] is a symlink then
 /// it will be styled accordingly.
-pub fn stylize_file_name(node: &Node) -> Cow<'_, str> {
+pub fn stylize_file_name(node: &Node, style: Option<Style>) -> Cow<'_, str> {
     let name = node.file_name();
-    let style = node.style();
 
     let Some(target_name) = node.symlink_target_file_name() else {
-        if let Some(Style {foreground: Some(ref fg), .. }) = style {
+        if let Some(Style {foreground: Some(ref fg), is_dimmed, .. }) = style {
             let file_name = name.to_string_lossy();
-            let styled_name = fg.bold().paint(file_name).to_string();
+            let base = if is_dimmed { fg.dimmed() } else { fg.bold() };
+            let styled_name = base.paint(file_name).to_string();
+            return Cow::from(styled_name);
+        }
+
+        if let Some(dimmed) = style.filter(|s| s.is_dimmed) {
+            let styled_name = dimmed.paint(name.to_string_lossy()).to_string();
             return Cow::from(styled_name);
         }
 
@@ -108,3 +167,36 @@ pub fn style_oct_permissions(node: &Node) -> String {
         oct
     }
 }
+
+#[test]
+fn test_size_gradient_style() {
+    assert_eq!(size_gradient_style(0, 100), Color::Fixed(46).normal());
+    assert_eq!(size_gradient_style(50, 100), Color::Fixed(226).normal());
+    assert_eq!(size_gradient_style(100, 100), Color::Fixed(196).normal());
+    assert_eq!(size_gradient_style(200, 100), Color::Fixed(196).normal());
+    assert_eq!(size_gradient_style(0, 0), Color::Fixed(46).normal());
+}
+
+#[test]
+fn test_gradient_style() {
+    assert_eq!(gradient_style(0.5), Color::Fixed(226).normal());
+    assert_eq!(gradient_style(1.5), Color::Fixed(196).normal());
+    assert_eq!(gradient_style(-1.0), Color::Fixed(46).normal());
+}
+
+#[test]
+fn test_age_gradient_style() {
+    assert_eq!(age_gradient_style(0.0), Color::Fixed(255).normal());
+    assert_eq!(age_gradient_style(1.0), Color::Fixed(240).normal());
+    assert_eq!(age_gradient_style(2.0), Color::Fixed(240).normal());
+}
+
+#[test]
+fn test_dim_by_depth() {
+    let style = Color::Blue.bold();
+
+    assert_eq!(dim_by_depth(style, 0), style);
+    assert_eq!(dim_by_depth(style, 1), style);
+    assert_eq!(dim_by_depth(style, 2), Color::Blue.dimmed());
+    assert_eq!(dim_by_depth(style, 64), Color::Blue.dimmed());
+}
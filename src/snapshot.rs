@@ -0,0 +1,177 @@
+use crate::{context::Context, disk_usage::file_size::FileSize, tree::Tree};
+use clap::ValueEnum;
+use serde_json::{json, Map, Value};
+use std::{
+    collections::HashMap,
+    fs,
+    io::Error as IoError,
+    path::{Path, PathBuf},
+};
+
+/// Version of the snapshot format written by `--snapshot`.
+const VERSION: u64 = 1;
+
+/// Errors that may occur while saving or loading a [Snapshot].
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Failed to read snapshot '{0}': {1}")]
+    Read(String, IoError),
+
+    #[error("Failed to write snapshot '{0}': {1}")]
+    Write(String, IoError),
+
+    #[error("'{0}' isn't a snapshot written by '--snapshot'")]
+    Malformed(String),
+
+    #[error("Snapshot '{0}' was taken with '--disk-usage {1}' rather than '--disk-usage {2}'")]
+    DiskUsageMismatch(String, String, String),
+}
+
+/// The size of every entry of a [Tree] keyed by its path relative to the root, saved with
+/// `--snapshot` so that a later run can be compared against it with `--diff`.
+pub struct Snapshot {
+    disk_usage: String,
+    sizes: HashMap<PathBuf, u64>,
+}
+
+/// How an entry has changed since a [Snapshot] was taken.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Delta {
+    /// Size at the time of the snapshot or `None` if the entry is new.
+    pub before: Option<u64>,
+
+    /// Current size.
+    pub after: u64,
+
+    /// Number of entries directly beneath a directory that have since been removed.
+    pub removed: usize,
+}
+
+impl Snapshot {
+    /// Records the size of every entry in `tree`, regardless of `--level`.
+    pub fn new(tree: &Tree, ctx: &Context) -> Self {
+        let arena = tree.arena();
+        let root_id = tree.root_id();
+        let root_path = arena[root_id].get().path();
+
+        let sizes = root_id
+            .descendants(arena)
+            .map(|node_id| {
+                let node = arena[node_id].get();
+                let path = node
+                    .path()
+                    .strip_prefix(root_path)
+                    .unwrap_or_else(|_| node.path());
+                let size = node.file_size().map_or(0, FileSize::value);
+
+                (path.to_path_buf(), size)
+            })
+            .collect();
+
+        Self {
+            disk_usage: disk_usage_name(ctx),
+            sizes,
+        }
+    }
+
+    /// Writes the snapshot to `path` as JSON.
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let sizes = self
+            .sizes
+            .iter()
+            .map(|(path, size)| (path.to_string_lossy().into_owned(), json!(size)))
+            .collect::<Map<_, _>>();
+
+        let snapshot = json!({
+            "version": VERSION,
+            "disk_usage": self.disk_usage,
+            "sizes": sizes,
+        });
+
+        fs::write(path, snapshot.to_string())
+            .map_err(|e| Error::Write(path.display().to_string(), e))
+    }
+
+    /// Reads a snapshot previously written to `path`. Snapshots are only comparable if they were
+    /// taken using the same `--disk-usage` as `ctx`.
+    pub fn load(path: &Path, ctx: &Context) -> Result<Self, Error> {
+        let display = path.display().to_string();
+
+        let contents = fs::read_to_string(path).map_err(|e| Error::Read(display.clone(), e))?;
+
+        let snapshot = serde_json::from_str::<Value>(&contents)
+            .ok()
+            .filter(|snapshot| snapshot["version"] == VERSION)
+            .ok_or_else(|| Error::Malformed(display.clone()))?;
+
+        let (Some(disk_usage), Some(sizes)) = (
+            snapshot["disk_usage"].as_str(),
+            snapshot["sizes"].as_object(),
+        ) else {
+            return Err(Error::Malformed(display));
+        };
+
+        let expected = disk_usage_name(ctx);
+
+        if disk_usage != expected {
+            return Err(Error::DiskUsageMismatch(
+                display,
+                disk_usage.to_owned(),
+                expected,
+            ));
+        }
+
+        let sizes = sizes
+            .iter()
+            .map(|(path, size)| size.as_u64().map(|size| (PathBuf::from(path), size)))
+            .collect::<Option<HashMap<_, _>>>()
+            .ok_or(Error::Malformed(display))?;
+
+        Ok(Self {
+            disk_usage: disk_usage.to_owned(),
+            sizes,
+        })
+    }
+
+    /// Gets the size of the entry at `path`, relative to the root, when the snapshot was taken.
+    pub fn get(&self, path: &Path) -> Option<u64> {
+        self.sizes.get(path).copied()
+    }
+
+    /// Paths, relative to the root, of every entry in the snapshot.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.sizes.keys().map(PathBuf::as_path)
+    }
+}
+
+impl Delta {
+    /// Whether there's anything worth noting, i.e. the entry is new, its size changed, or entries
+    /// beneath it were removed.
+    pub const fn is_change(&self) -> bool {
+        match self.before {
+            Some(before) => before != self.after || self.removed > 0,
+            None => true,
+        }
+    }
+}
+
+/// Name of the `--disk-usage` of `ctx` as it appears on the command-line.
+fn disk_usage_name(ctx: &Context) -> String {
+    ctx.disk_usage
+        .to_possible_value()
+        .map_or_else(String::new, |value| value.get_name().to_owned())
+}
+
+#[test]
+fn test_delta_is_change() {
+    let delta = |before, after, removed| Delta {
+        before,
+        after,
+        removed,
+    };
+
+    assert!(delta(None, 0, 0).is_change());
+    assert!(delta(Some(1), 2, 0).is_change());
+    assert!(delta(Some(2), 2, 1).is_change());
+    assert!(!delta(Some(2), 2, 0).is_change());
+}
@@ -1,15 +1,22 @@
 use crate::hash;
 use ansi_term::{Color, Style};
 use error::Error;
-use lscolors::LsColors;
-use std::{collections::HashMap, sync::OnceLock};
+use lscolors::{Indicator, LsColors, Style as LsStyle};
+use std::{collections::HashMap, fs::Metadata, path::Path, sync::OnceLock};
+use theme::Theme;
 
 /// Errors for this module.
 pub mod error;
 
+/// User-defined colors from the `[theme]` table of `.erdtree.toml`.
+pub mod theme;
+
 /// Used as general placeholder for an empty field.
 pub const PLACEHOLDER: &str = "-";
 
+/// Used in the extension column for files that don't have an extension.
+pub const NO_EXTENSION: &str = "(none)";
+
 /// Used for padding between tree branches.
 pub const SEP: &str = "   ";
 
@@ -25,6 +32,33 @@ pub const UPRT: &str = "\u{2514}\u{2500} ";
 /// The `├─` box drawing characters.
 pub const VTRT: &str = "\u{251C}\u{2500} ";
 
+/// The set of characters used to draw the branches of the tree.
+pub struct Glyphs {
+    pub sep: &'static str,
+    pub vt: &'static str,
+    pub drt: &'static str,
+    pub uprt: &'static str,
+    pub vtrt: &'static str,
+}
+
+/// Unicode box drawing characters; used by default.
+pub const BOX_DRAWING: Glyphs = Glyphs {
+    sep: SEP,
+    vt: VT,
+    drt: DRT,
+    uprt: UPRT,
+    vtrt: VTRT,
+};
+
+/// Pure ASCII equivalents of [`BOX_DRAWING`] for environments that mangle Unicode.
+pub const ASCII: Glyphs = Glyphs {
+    sep: "    ",
+    vt: "|   ",
+    drt: ",-- ",
+    uprt: "`-- ",
+    vtrt: "|-- ",
+};
+
 /// A runtime evaluated static. [`LS_COLORS`] the `LS_COLORS` environment variable to determine what
 /// ANSI colors to use when printing the names of files. If `LS_COLORS` is not set it will fallback
 /// to a default defined in the `lscolors` crate.
@@ -34,6 +68,10 @@ pub const VTRT: &str = "\u{251C}\u{2500} ";
 /// variable to customize output color or rely on the default.
 static LS_COLORS: OnceLock<LsColors> = OnceLock::new();
 
+/// Runtime evaluated static that contains the user's [`Theme`] whose file-type styles take
+/// precedence over [`LS_COLORS`].
+static FILE_THEME: OnceLock<Theme> = OnceLock::new();
+
 /// Runtime evaluated static that contains ANSI-colored box drawing characters used for the
 /// printing of [`super::tree::Tree`]'s branches.
 static TREE_THEME: OnceLock<ThemesMap> = OnceLock::new();
@@ -57,7 +95,6 @@ static OCTAL_PERMISSIONS_STYLE: OnceLock<Style> = OnceLock::new();
 static PLACEHOLDER_STYLE: OnceLock<Style> = OnceLock::new();
 
 /// Runtime evaluated static that contains style for inode number i.e. `ino`.
-#[cfg(unix)]
 static INO_STYLE: OnceLock<Style> = OnceLock::new();
 
 /// Runtime evaluated static that contains style for the file owner string.
@@ -80,16 +117,24 @@ static DATETIME_STYLE: OnceLock<Style> = OnceLock::new();
 pub type ThemesMap = HashMap<&'static str, String>;
 
 /// Initializes both [`LS_COLORS`] and all themes. If `plain` argument is `true` then plain colorless
-/// themes are used and [`LS_COLORS`] won't be initialized.
-pub fn init(plain: bool) {
+/// themes are used and [`LS_COLORS`] won't be initialized unless `color_icons` is `true`, in which
+/// case it is still needed to colorize icons. The branches of the tree are drawn using [`ASCII`]
+/// if `ascii` is `true`, otherwise [`BOX_DRAWING`]. Anything set in `theme` overrides both
+/// [`LS_COLORS`] and the default themes.
+pub fn init(plain: bool, color_icons: bool, ascii: bool, theme: &Theme) {
     #[cfg(windows)]
     let _ = ansi_term::enable_ansi_support();
 
+    let glyphs = if ascii { &ASCII } else { &BOX_DRAWING };
+
     if plain {
-        init_plain();
+        if color_icons {
+            init_ls_colors(theme);
+        }
+        init_plain(glyphs);
     } else {
-        init_ls_colors();
-        init_themes();
+        init_ls_colors(theme);
+        init_themes(glyphs, theme);
     }
 }
 
@@ -99,6 +144,33 @@ pub fn get_ls_colors() -> Result<&'static LsColors, Error<'static>> {
     LS_COLORS.get().ok_or(Error::Uninitialized("LS_COLORS"))
 }
 
+/// Determines the style of the file at `path` from the user's [`Theme`], falling back to
+/// [`LS_COLORS`]. Broken symlinks are painted bold red unless [`LS_COLORS`] already tells them
+/// apart from other symlinks. Returns `None` if [`LS_COLORS`] is not initialized.
+pub fn get_file_style(path: &Path, metadata: &Metadata) -> Option<Style> {
+    let ls_colors = get_ls_colors().ok()?;
+
+    let style = FILE_THEME
+        .get()
+        .and_then(|theme| theme.file_style(path, metadata))
+        .unwrap_or_else(|| {
+            let is_broken = metadata.is_symlink() && !path.exists();
+
+            if is_broken
+                && ls_colors.style_for_indicator(Indicator::OrphanedSymbolicLink)
+                    == ls_colors.style_for_indicator(Indicator::SymbolicLink)
+            {
+                return Color::Red.bold();
+            }
+
+            ls_colors
+                .style_for_path_with_metadata(path, Some(metadata))
+                .map_or_else(Style::default, LsStyle::to_ansi_term_style)
+        });
+
+    Some(style)
+}
+
 /// Getter for [`DU_THEME`]. Returns an error if not initialized.
 #[inline]
 pub fn get_du_theme() -> Result<&'static HashMap<&'static str, Style>, Error<'static>> {
@@ -144,7 +216,6 @@ pub fn get_placeholder_style() -> Result<&'static Style, Error<'static>> {
 }
 
 /// Getter for [`INO_STYLE`]. Returns an error if not initialized.
-#[cfg(unix)]
 #[inline]
 pub fn get_ino_style() -> Result<&'static Style, Error<'static>> {
     INO_STYLE.get().ok_or(Error::Uninitialized("INO_STYLE"))
@@ -181,28 +252,32 @@ pub fn get_datetime_style() -> Result<&'static Style, Error<'static>> {
 }
 
 /// Initializes [`LS_COLORS`] by reading in the `LS_COLORS` environment variable. If it isn't set, a
-/// default determined by `lscolors` crate will be used.
-fn init_ls_colors() {
+/// default determined by `lscolors` crate will be used. Also initializes [`FILE_THEME`].
+fn init_ls_colors(theme: &Theme) {
     LS_COLORS
         .set(LsColors::from_env().unwrap_or_default())
         .unwrap();
+
+    FILE_THEME.set(theme.clone()).unwrap();
 }
 
 /// Colorless themes
-fn init_plain() {
+fn init_plain(glyphs: &Glyphs) {
     let theme = hash! {
-        "vt" => VT.to_owned(),
-        "uprt" => UPRT.to_owned(),
-        "drt" => DRT.to_owned(),
-        "vtrt" => VTRT.to_owned()
+        "sep" => glyphs.sep.to_owned(),
+        "vt" => glyphs.vt.to_owned(),
+        "uprt" => glyphs.uprt.to_owned(),
+        "drt" => glyphs.drt.to_owned(),
+        "vtrt" => glyphs.vtrt.to_owned()
     };
     TREE_THEME.set(theme).unwrap();
 
     let link_theme = hash! {
-        "vt" => VT.to_owned(),
-        "uprt" => UPRT.to_owned(),
-        "drt" => DRT.to_owned(),
-        "vtrt" => VTRT.to_owned()
+        "sep" => glyphs.sep.to_owned(),
+        "vt" => glyphs.vt.to_owned(),
+        "uprt" => glyphs.uprt.to_owned(),
+        "drt" => glyphs.drt.to_owned(),
+        "vtrt" => glyphs.vtrt.to_owned()
     };
     LINK_THEME.set(link_theme).unwrap();
 }
@@ -228,9 +303,6 @@ fn init_themes_for_long_view() {
         .set(octal_permissions_style)
         .unwrap();
 
-    let ino_style = Color::Cyan.bold();
-    INO_STYLE.set(ino_style).unwrap();
-
     let nlink_style = Color::Red.bold();
     NLINK_STYLE.set(nlink_style).unwrap();
 
@@ -244,36 +316,52 @@ fn init_themes_for_long_view() {
     GROUP_STYLE.set(group_style).unwrap();
 }
 
-/// Initializes all color themes.
-fn init_themes() {
+/// Initializes all color themes, preferring styles from the user's `theme` over the defaults.
+fn init_themes(glyphs: &Glyphs, user_theme: &Theme) {
+    let branches = user_theme
+        .branches
+        .unwrap_or_else(|| Color::Purple.normal());
+
     let theme = hash! {
-        "vt" => format!("{}", Color::Purple.paint(VT)),
-        "uprt" => format!("{}", Color::Purple.paint(UPRT)),
-        "drt" => format!("{}", Color::Purple.paint(DRT)),
-        "vtrt" => format!("{}", Color::Purple.paint(VTRT))
+        "sep" => glyphs.sep.to_owned(),
+        "vt" => format!("{}", branches.paint(glyphs.vt)),
+        "uprt" => format!("{}", branches.paint(glyphs.uprt)),
+        "drt" => format!("{}", branches.paint(glyphs.drt)),
+        "vtrt" => format!("{}", branches.paint(glyphs.vtrt))
     };
     TREE_THEME.set(theme).unwrap();
 
+    let link_branches = user_theme
+        .link_branches
+        .unwrap_or_else(|| Color::Red.normal());
+
     let link_theme = hash! {
-        "vt" => format!("{}", Color::Red.paint(VT)),
-        "uprt" => format!("{}", Color::Red.paint(UPRT)),
-        "drt" => format!("{}", Color::Red.paint(DRT)),
-        "vtrt" => format!("{}", Color::Red.paint(VTRT))
+        "sep" => glyphs.sep.to_owned(),
+        "vt" => format!("{}", link_branches.paint(glyphs.vt)),
+        "uprt" => format!("{}", link_branches.paint(glyphs.uprt)),
+        "drt" => format!("{}", link_branches.paint(glyphs.drt)),
+        "vtrt" => format!("{}", link_branches.paint(glyphs.vtrt))
     };
     LINK_THEME.set(link_theme).unwrap();
 
-    let du_theme = hash! {
+    let mut du_theme = hash! {
         "B" => Color::Cyan.bold(),
         "KB" | "KiB" => Color::Yellow.bold(),
         "MB" | "MiB" => Color::Green.bold(),
         "GB" | "GiB" => Color::Red.bold(),
         "TB" | "TiB" => Color::Blue.bold()
     };
+    du_theme.extend(&user_theme.units);
     DU_THEME.set(du_theme).unwrap();
 
-    let placeholder_style = Color::Purple.normal();
+    let placeholder_style = user_theme
+        .placeholder
+        .unwrap_or_else(|| Color::Purple.normal());
     PLACEHOLDER_STYLE.set(placeholder_style).unwrap();
 
+    let ino_style = Color::Cyan.bold();
+    INO_STYLE.set(ino_style).unwrap();
+
     #[cfg(unix)]
     init_themes_for_long_view();
 }
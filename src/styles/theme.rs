@@ -0,0 +1,182 @@
+use ansi_term::{Color, Style};
+use std::{collections::HashMap, fs::Metadata, path::Path};
+
+/// Names of the units whose color may be customized via the `size` key of the theme.
+const UNITS: [&str; 9] = ["B", "KB", "KiB", "MB", "MiB", "GB", "GiB", "TB", "TiB"];
+
+/// Errors that may occur while reading the `[theme]` table of `.erdtree.toml`.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Invalid style '{1}' for '{0}' in '[theme]'")]
+    InvalidStyle(String, String),
+
+    #[error("Unknown key '{0}' in '[theme]'")]
+    UnknownKey(String),
+}
+
+/// User-defined colors read from the `[theme]` table of `.erdtree.toml`. Anything left unset
+/// falls back to `LS_COLORS` in the case of file names and to the defaults otherwise.
+#[derive(Clone, Debug, Default)]
+pub struct Theme {
+    /// Style of the branches of the tree.
+    pub branches: Option<Style>,
+
+    /// Style of the branches beneath a symlink that is being followed.
+    pub link_branches: Option<Style>,
+
+    /// Style of the general use placeholder "-".
+    pub placeholder: Option<Style>,
+
+    /// Styles of disk usage keyed by unit e.g. `KiB`.
+    pub units: HashMap<&'static str, Style>,
+
+    /// Styles of file names keyed by lowercase extension, `directory`, `symlink`, or `broken`.
+    pub file_types: HashMap<String, Style>,
+}
+
+impl Theme {
+    /// Builds a [Theme] from the flattened key-value pairs of the `[theme]` table, where nested
+    /// keys are joined by a `.` e.g. `size.kib` or `file_types.rs`. Keys are case-insensitive.
+    pub fn try_from_entries<I>(entries: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut theme = Self::default();
+
+        for (key, spec) in entries {
+            let key = key.to_lowercase();
+
+            let style = parse_style(&spec).ok_or_else(|| Error::InvalidStyle(key.clone(), spec))?;
+
+            match key.split_once('.') {
+                None if key == "branches" => theme.branches = Some(style),
+                None if key == "link_branches" => theme.link_branches = Some(style),
+                None if key == "placeholder" => theme.placeholder = Some(style),
+                None if key == "size" => {
+                    for unit in UNITS {
+                        theme.units.entry(unit).or_insert(style);
+                    }
+                },
+                Some(("size", unit)) => {
+                    let unit = UNITS
+                        .into_iter()
+                        .find(|u| u.eq_ignore_ascii_case(unit))
+                        .ok_or_else(|| Error::UnknownKey(key.clone()))?;
+
+                    theme.units.insert(unit, style);
+                },
+                Some(("file_types", file_type)) => {
+                    theme.file_types.insert(file_type.to_owned(), style);
+                },
+                _ => return Err(Error::UnknownKey(key)),
+            }
+        }
+
+        Ok(theme)
+    }
+
+    /// The user-defined style of the file at `path`, if any. Directories and symlinks are looked
+    /// up by their type and everything else by extension. Broken symlinks are looked up as
+    /// `broken` if that's set, otherwise as `symlink`.
+    pub fn file_style(&self, path: &Path, metadata: &Metadata) -> Option<Style> {
+        if self.file_types.is_empty() {
+            return None;
+        }
+
+        let file_type = if metadata.is_symlink() {
+            let is_broken = self.file_types.contains_key("broken") && !path.exists();
+            Some(if is_broken { "broken" } else { "symlink" }.to_owned())
+        } else if metadata.is_dir() {
+            Some("directory".to_owned())
+        } else {
+            path.extension()
+                .map(|ext| ext.to_string_lossy().to_lowercase())
+        };
+
+        file_type.and_then(|file_type| self.file_types.get(&file_type).copied())
+    }
+}
+
+/// Parses a whitespace-separated style specification such as `bold red`, `208 on blue`, or
+/// `underline #ff8700`. The first color is the foreground and a color preceded by `on` the
+/// background. Returns `None` if any word isn't recognized.
+pub fn parse_style(spec: &str) -> Option<Style> {
+    let mut style = Style::new();
+    let mut words = spec.split_whitespace();
+
+    while let Some(word) = words.next() {
+        let word = word.to_lowercase();
+
+        style = match word.as_str() {
+            "bold" => style.bold(),
+            "dim" | "dimmed" => style.dimmed(),
+            "italic" => style.italic(),
+            "underline" => style.underline(),
+            "blink" => style.blink(),
+            "reverse" => style.reverse(),
+            "hidden" => style.hidden(),
+            "strikethrough" => style.strikethrough(),
+            "normal" | "default" => style,
+            "on" => style.on(parse_color(words.next()?)?),
+            _ => style.fg(parse_color(&word)?),
+        };
+    }
+
+    Some(style)
+}
+
+/// Parses a named color, an 8-bit color number, or a `#rrggbb` hex triplet.
+fn parse_color(word: &str) -> Option<Color> {
+    let color = match word.to_lowercase().as_str() {
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "purple" | "magenta" => Color::Purple,
+        "cyan" => Color::Cyan,
+        "white" => Color::White,
+        hex if hex.starts_with('#') && hex.len() == 7 => {
+            let channel = |i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+            Color::RGB(channel(1)?, channel(3)?, channel(5)?)
+        },
+        num => Color::Fixed(num.parse().ok()?),
+    };
+
+    Some(color)
+}
+
+#[test]
+fn test_parse_style() {
+    assert_eq!(parse_style("bold red"), Some(Color::Red.bold()));
+    assert_eq!(parse_style("208"), Some(Color::Fixed(208).normal()));
+    assert_eq!(
+        parse_style("Underline #ff8700 on blue"),
+        Some(Color::RGB(255, 135, 0).underline().on(Color::Blue))
+    );
+    assert_eq!(parse_style("normal"), Some(Style::new()));
+    assert_eq!(parse_style("bold on"), None);
+    assert_eq!(parse_style("#ff87"), None);
+    assert_eq!(parse_style("chartreuse"), None);
+}
+
+#[test]
+fn test_try_from_entries() {
+    let entries = [
+        ("size".to_owned(), "green".to_owned()),
+        ("size.kib".to_owned(), "bold yellow".to_owned()),
+        ("file_types.RS".to_owned(), "208".to_owned()),
+    ];
+
+    let theme = Theme::try_from_entries(entries).unwrap();
+
+    assert_eq!(theme.units.get("KiB"), Some(&Color::Yellow.bold()));
+    assert_eq!(theme.units.get("B"), Some(&Color::Green.normal()));
+    assert_eq!(
+        theme.file_types.get("rs"),
+        Some(&Color::Fixed(208).normal())
+    );
+
+    let unknown = [("size.parsec".to_owned(), "red".to_owned())];
+    assert!(Theme::try_from_entries(unknown).is_err());
+}
@@ -1,13 +1,13 @@
 use super::Node;
+use crate::utils;
 use std::{
-    convert::From,
     fmt::{self, Display},
     ops::{Add, AddAssign},
 };
 
 /// For keeping track of the number of various file-types of [Node]'s chlidren.
 #[allow(clippy::module_name_repetitions)]
-#[derive(Default)]
+#[derive(Clone, Copy, Default)]
 pub struct FileCount {
     pub num_dirs: usize,
     pub num_files: usize,
@@ -15,10 +15,15 @@ pub struct FileCount {
 }
 
 impl AddAssign<&Node> for FileCount {
-    /// Update [Self] with information from [Node].
+    /// Update [Self] with information from [Node]. Directory symlinks left unfollowed are counted
+    /// as links, a [Node] standing in for siblings hidden by `--limit` counts everything that is
+    /// hidden, and a directory that `--collapse` merged a chain into counts every directory of the
+    /// chain.
     fn add_assign(&mut self, rhs: &Node) {
-        if rhs.is_dir() {
-            self.num_dirs += 1;
+        if let Some(overflow) = rhs.overflow() {
+            *self += overflow.file_count;
+        } else if rhs.is_dir() && !rhs.is_unfollowed() {
+            self.num_dirs += 1 + rhs.collapsed();
         } else if rhs.is_symlink() {
             self.num_links += 1;
         } else {
@@ -30,9 +35,13 @@ impl Add<&Node> for FileCount {
     type Output = Self;
     /// Update [Self] with information from [Node].
     fn add(self, rhs: &Node) -> Self::Output {
-        if rhs.is_dir() {
+        if let Some(overflow) = rhs.overflow() {
+            return self + overflow.file_count;
+        }
+
+        if rhs.is_dir() && !rhs.is_unfollowed() {
             Self {
-                num_dirs: self.num_dirs + 1,
+                num_dirs: self.num_dirs + 1 + rhs.collapsed(),
                 ..self
             }
         } else if rhs.is_symlink() {
@@ -69,10 +78,10 @@ impl Add for FileCount {
     }
 }
 
-impl From<Vec<Self>> for FileCount {
-    fn from(data: Vec<Self>) -> Self {
-        data.into_iter()
-            .fold(Self::default(), |acc, datum| acc + datum)
+impl FileCount {
+    /// Whether nothing at all has been counted.
+    pub const fn is_empty(&self) -> bool {
+        self.num_dirs == 0 && self.num_files == 0 && self.num_links == 0
     }
 }
 
@@ -83,7 +92,7 @@ impl Display for FileCount {
         if self.num_dirs > 0 {
             let output = format!(
                 "{} {}",
-                self.num_dirs,
+                utils::group_digits(self.num_dirs as u64),
                 if self.num_dirs > 1 {
                     "directories"
                 } else {
@@ -97,7 +106,7 @@ impl Display for FileCount {
         if self.num_files > 0 {
             let output = format!(
                 "{} {}",
-                self.num_files,
+                utils::group_digits(self.num_files as u64),
                 if self.num_files > 1 { "files" } else { "file" }
             );
 
@@ -107,7 +116,7 @@ impl Display for FileCount {
         if self.num_links > 0 {
             let output = format!(
                 "{} {}",
-                self.num_links,
+                utils::group_digits(self.num_links as u64),
                 if self.num_links > 1 { "links" } else { "link" }
             );
 
@@ -1,4 +1,7 @@
-use crate::{context::error::Error as CtxError, styles::error::Error as StyleError};
+use crate::{
+    context::error::Error as CtxError, snapshot::Error as SnapshotError,
+    styles::error::Error as StyleError,
+};
 use ignore::Error as IgnoreError;
 use std::io::Error as IoError;
 
@@ -22,6 +25,9 @@ pub enum Error {
     #[error("Invalid glob patterns: {0}")]
     InvalidGlobPatterns(#[from] IgnoreError),
 
+    #[error("Failed to query file metadata.")]
+    MissingMetadata,
+
     #[error("Failed to compute root node.")]
     MissingRoot,
 
@@ -35,6 +41,9 @@ pub enum Error {
     #[error("{0}")]
     Permissions(#[from] PermissionsError),
 
+    #[error("{0}")]
+    Snapshot(#[from] SnapshotError),
+
     #[error("{0}")]
     UninitializedTheme(#[from] StyleError<'static>),
 
@@ -1,29 +1,44 @@
 use crate::{
-    context::{column, Context},
+    context::{column, heat, layout, Context},
     disk_usage::file_size::FileSize,
     fs::inode::Inode,
-    progress::{IndicatorHandle, Message},
+    git,
+    progress::{IndicatorHandle, Message, Tally},
+    render::{
+        Csv, Engine, Flat, FlatInverted, Html, Inverted, Json, List, Markdown, Ncdu, Regular, Top,
+    },
+    snapshot::{Delta, Snapshot},
     utils,
 };
 use count::FileCount;
 use error::Error;
-use ignore::{WalkBuilder, WalkParallel};
+use ignore::{DirEntry, WalkBuilder, WalkParallel};
 use indextree::{Arena, NodeId};
-use node::{cmp::NodeComparator, Node};
+use node::{cmp::NodeComparator, Node, Overflow};
 use std::{
     collections::{HashMap, HashSet},
     convert::TryFrom,
     fs,
-    path::PathBuf,
+    io::{self, Write},
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
     result::Result as StdResult,
     sync::{
         mpsc::{self, Sender},
         Arc,
     },
     thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
+use timings::Timings;
 use visitor::{BranchVisitorBuilder, TraversalState};
 
+#[cfg(unix)]
+use crate::styles::PLACEHOLDER;
+
+#[cfg(unix)]
+use chrono::{DateTime, Local};
+
 /// Operations to handle and display aggregate file counts based on their type.
 pub mod count;
 
@@ -36,6 +51,9 @@ pub mod error;
 /// [`DirEntry`]: ignore::DirEntry
 pub mod node;
 
+/// Durations and throughput reported by `--timings`.
+pub mod timings;
+
 /// Custom visitor that operates on each thread during filesystem traversal.
 mod visitor;
 
@@ -43,14 +61,38 @@ mod visitor;
 pub struct Tree {
     arena: Arena<Node>,
     root_id: NodeId,
+    file_count: FileCount,
+    timings: Timings,
 }
 
 pub type Result<T> = StdResult<T, Error>;
 
+/// A directory whose children are in the process of being assembled by [`Tree::assemble_tree`].
+struct Frame {
+    node_id: NodeId,
+    children: Vec<NodeId>,
+    cursor: usize,
+    descended: bool,
+    dir_size: FileSize,
+    other_dir_size: Option<FileSize>,
+    file_count: usize,
+}
+
 impl Tree {
-    /// Constructor for [Tree].
-    pub const fn new(arena: Arena<Node>, root_id: NodeId) -> Self {
-        Self { arena, root_id }
+    /// Constructor for [Tree]. Everything beneath the root is tallied up front for the trailing
+    /// summary.
+    pub fn new(arena: Arena<Node>, root_id: NodeId) -> Self {
+        let file_count = root_id
+            .descendants(&arena)
+            .skip(1)
+            .fold(FileCount::default(), |count, id| count + arena[id].get());
+
+        Self {
+            arena,
+            root_id,
+            file_count,
+            timings: Timings::default(),
+        }
     }
 
     /// Initiates file-system traversal and [Tree] as well as updates the [Context] object with
@@ -61,23 +103,80 @@ impl Tree {
     ) -> Result<(Self, Context)> {
         let mut column_properties = column::Properties::from(&ctx);
 
-        let (arena, root_id) = Self::traverse(&ctx, &mut column_properties, indicator)?;
+        // Loaded ahead of traversal so that an invalid snapshot is reported immediately.
+        let snapshot = ctx
+            .diff
+            .as_deref()
+            .map(|path| Snapshot::load(path, &ctx))
+            .transpose()?;
+
+        // Also kept when there's no progress indicator so that `--timings` may report on it.
+        let tally = indicator
+            .as_ref()
+            .map(|arc| arc.tally())
+            .or_else(|| ctx.timings.then(Arc::default));
+
+        let start = Instant::now();
+
+        let (arena, root_id, traversal) = Self::traverse(
+            &ctx,
+            &mut column_properties,
+            indicator,
+            tally.clone(),
+            snapshot.as_ref(),
+        )?;
+
+        let timings = Timings::new(traversal, start.elapsed(), tally.as_deref());
 
         ctx.update_column_properties(&column_properties);
 
-        if ctx.truncate {
+        if ctx.should_truncate() {
             ctx.set_window_width();
         }
 
-        let tree = Self::new(arena, root_id);
+        let mut tree = Self::new(arena, root_id);
+        tree.timings = timings;
 
-        if tree.is_stump() {
+        if tree.is_stump() && tree.arena()[root_id].get().is_dir() {
             return Err(Error::NoMatches);
         }
 
         Ok((tree, ctx))
     }
 
+    /// Renders the [Tree] into `w` using the layout specified by [Context], as JSON, CSV, HTML, an
+    /// ncdu export, or Markdown if the respective argument is enabled, or as a list of the largest
+    /// files if `--top` is provided. Output is terminated by a single newline and is preceded by
+    /// the effective command if `--print-command` is enabled.
+    pub fn render(&self, w: &mut impl Write, ctx: &Context) -> io::Result<()> {
+        macro_rules! compute_output {
+            ($t:ty) => {{
+                let render = Engine::<$t>::new(self, ctx);
+                format!("{render}")
+            }};
+        }
+
+        let output = match ctx.layout {
+            _ if ctx.json => compute_output!(Json),
+            _ if ctx.csv => compute_output!(Csv),
+            _ if ctx.html => compute_output!(Html),
+            _ if ctx.ncdu => compute_output!(Ncdu),
+            _ if ctx.markdown => compute_output!(Markdown),
+            _ if ctx.top.is_some() => compute_output!(Top),
+            _ if ctx.list => compute_output!(List),
+            layout::Type::Flat => compute_output!(Flat),
+            layout::Type::Iflat => compute_output!(FlatInverted),
+            layout::Type::Inverted => compute_output!(Inverted),
+            layout::Type::Regular => compute_output!(Regular),
+        };
+
+        if let Some(ref cmd) = ctx.effective_command {
+            writeln!(w, "# {cmd}")?;
+        }
+
+        writeln!(w, "{}", output.trim_end_matches('\n'))
+    }
+
     /// Returns `true` if there are no entries to show excluding the `root_id`.
     pub fn is_stump(&self) -> bool {
         self.root_id
@@ -98,31 +197,49 @@ impl Tree {
         &self.arena
     }
 
+    /// How long it took to build [Tree] along with the entries visited and errors encountered.
+    pub const fn timings(&self) -> Timings {
+        self.timings
+    }
+
+    /// Number of directories, files, and symlinks beneath the root.
+    pub const fn file_count(&self) -> FileCount {
+        self.file_count
+    }
+
     /// Parallel traversal of the `root_id` directory and its contents. Parallel traversal relies on
     /// `WalkParallel`. Any filesystem I/O or related system calls are expected to occur during
     /// parallel traversal; post-processing post-processing of all directory entries should
     /// be completely CPU-bound.
+    #[allow(clippy::too_many_lines)]
     fn traverse(
         ctx: &Context,
         column_properties: &mut column::Properties,
         indicator: Option<Arc<IndicatorHandle>>,
-    ) -> Result<(Arena<Node>, NodeId)> {
+        tally: Option<Arc<Tally>>,
+        snapshot: Option<&Snapshot>,
+    ) -> Result<(Arena<Node>, NodeId, Duration)> {
         let walker = WalkParallel::try_from(ctx)?;
         let (tx, rx) = mpsc::channel();
+        let start = Instant::now();
 
-        let progress_indicator_mailbox = indicator.map(|arc| arc.mailbox());
+        let progress_indicator_mailbox = indicator.as_ref().map(|arc| arc.mailbox());
 
         thread::scope(|s| {
             let res = s.spawn(move || {
+                // Queried while the traversal is underway so as to not hold up anything.
+                let git_statuses = ctx
+                    .git_status
+                    .then(|| git::Statuses::query(&ctx.dir_canonical()))
+                    .flatten();
+
                 let mut tree = Arena::new();
                 let mut branches: HashMap<PathBuf, Vec<NodeId>> = HashMap::new();
                 let mut root_id = None;
 
-                while let Ok(TraversalState::Ongoing(node)) = rx.recv() {
-                    if let Some(ref mailbox) = progress_indicator_mailbox {
-                        if mailbox.send(Message::Index).is_err() {
-                            return Err(Error::Terminated);
-                        }
+                while let Ok(TraversalState::Ongoing(mut node)) = rx.recv() {
+                    if indicator.as_ref().is_some_and(|arc| arc.is_terminated()) {
+                        return Err(Error::Terminated);
                     }
 
                     if node.is_dir() {
@@ -131,13 +248,31 @@ impl Tree {
                         if !branches.contains_key(node_path) {
                             branches.insert(node_path.to_owned(), vec![]);
                         }
+                    }
 
-                        if node.depth() == 0 {
-                            root_id = Some(tree.new_node(node));
+                    if node.depth() == 0 {
+                        if let Some(alias) = ctx.root_alias() {
+                            node.set_alias(alias.to_owned());
+                        }
+                        root_id = Some(tree.new_node(node));
+                        continue;
+                    }
+
+                    if ctx.has_size_filter() && !node.is_dir() {
+                        let size = node.file_size().map_or(0, FileSize::value);
+
+                        if !ctx.size_in_range(size) {
                             continue;
                         }
                     }
 
+                    if ctx.has_time_filter()
+                        && !node.is_dir()
+                        && !ctx.modified_in_range(node.modified())
+                    {
+                        continue;
+                    }
+
                     let parent = node.parent_path().ok_or(Error::ExpectedParent)?.to_owned();
 
                     let node_id = tree.new_node(node);
@@ -151,6 +286,8 @@ impl Tree {
                     }
                 }
 
+                let traversal = start.elapsed();
+
                 if let Some(ref mailbox) = progress_indicator_mailbox {
                     if mailbox.send(Message::DoneIndexing).is_err() {
                         return Err(Error::Terminated);
@@ -161,6 +298,10 @@ impl Tree {
                 let node_comparator = node::cmp::comparator(ctx);
                 let mut inodes = HashSet::new();
 
+                if ctx.follow {
+                    Self::mark_revisits(&mut tree, root_id, &mut branches, &node_comparator);
+                }
+
                 Self::assemble_tree(
                     &mut tree,
                     root_id,
@@ -171,18 +312,47 @@ impl Tree {
                     ctx,
                 );
 
-                if ctx.prune || ctx.pattern.is_some() {
-                    Self::prune_directories(root_id, &mut tree);
+                if let Some(min_dir_size) = ctx.min_dir_size {
+                    Self::prune_small_directories(root_id, &mut tree, min_dir_size);
+                }
+
+                Self::prune(root_id, &mut tree, ctx)?;
+
+                // Done before files are filtered out so that directories containing files aren't
+                // mistaken for ones that only contain a single directory.
+                if ctx.collapse {
+                    Self::collapse_directories(root_id, &mut tree);
+                }
+
+                // Done before files are filtered out so that they're accounted for.
+                if ctx.summarize {
+                    Self::summarize_directories(root_id, &mut tree, ctx.level());
                 }
 
                 if ctx.dirs_only {
                     Self::filter_directories(root_id, &mut tree);
                 }
 
-                Ok((tree, root_id))
+                Self::surface_entries(root_id, &mut tree, &node_comparator, ctx);
+
+                Self::limit_children(root_id, &mut tree, ctx);
+
+                Self::compute_shares(root_id, &mut tree, ctx);
+
+                Self::compute_heat(root_id, &mut tree, ctx);
+
+                if let Some(ref statuses) = git_statuses {
+                    Self::apply_git_statuses(root_id, &mut tree, statuses);
+                }
+
+                if let Some(snapshot) = snapshot {
+                    Self::apply_deltas(root_id, &mut tree, snapshot);
+                }
+
+                Ok((tree, root_id, traversal))
             });
 
-            let mut visitor_builder = BranchVisitorBuilder::new(ctx, Sender::clone(&tx));
+            let mut visitor_builder = BranchVisitorBuilder::new(ctx, Sender::clone(&tx), tally);
 
             walker.visit(&mut visitor_builder);
 
@@ -195,110 +365,700 @@ impl Tree {
     /// Takes the results of the parallel traversal and uses it to construct the [Tree] data
     /// structure. Sorting occurs if specified. The amount of columns needed to fit all of the disk
     /// usages is also computed here.
+    ///
+    /// Directories are visited depth-first using an explicit stack of [Frame]s rather than
+    /// recursion so that pathologically deep directory structures can't overflow the stack.
     fn assemble_tree(
         tree: &mut Arena<Node>,
-        current_node_id: NodeId,
+        root_id: NodeId,
         branches: &mut HashMap<PathBuf, Vec<NodeId>>,
         node_comparator: &NodeComparator,
         inode_set: &mut HashSet<Inode>,
         column_properties: &mut column::Properties,
         ctx: &Context,
     ) {
-        let current_node = tree[current_node_id].get_mut();
+        let mut stack = vec![Frame::new(tree, root_id, branches, ctx)];
+
+        while let Some(frame) = stack.last_mut() {
+            let Some(&index) = frame.children.get(frame.cursor) else {
+                let Frame {
+                    node_id: current_node_id,
+                    mut children,
+                    dir_size,
+                    other_dir_size,
+                    file_count,
+                    ..
+                } = stack.pop().unwrap();
+
+                if dir_size.value() > 0 {
+                    let dir = tree[current_node_id].get_mut();
+
+                    dir.set_file_size(dir_size);
+                }
+
+                if let Some(other_dir_size) = other_dir_size.filter(|size| size.value() > 0) {
+                    let dir = tree[current_node_id].get_mut();
+
+                    dir.set_other_file_size(other_dir_size);
+                }
+
+                if ctx.dir_stats || ctx.count {
+                    let dir = tree[current_node_id].get_mut();
+
+                    dir.set_file_count(file_count);
+                }
+
+                let dir = tree[current_node_id].get();
+
+                #[cfg(unix)]
+                Self::update_column_properties(column_properties, dir, ctx);
 
-        let mut children = branches.remove(current_node.path()).unwrap();
+                #[cfg(not(unix))]
+                Self::update_column_properties(column_properties, dir, ctx);
 
-        let mut dir_size = FileSize::from(ctx);
+                children.sort_by(|&id_a, &id_b| {
+                    let node_a = tree[id_a].get();
+                    let node_b = tree[id_b].get();
+                    node_comparator(node_a, node_b)
+                });
 
-        for child_id in &children {
-            let index = *child_id;
+                // Append children to current node.
+                for child_id in children {
+                    current_node_id.append(child_id, tree);
+                }
 
-            let is_dir = {
-                let arena = tree[index].get();
-                arena.is_dir()
+                continue;
             };
 
-            if is_dir {
-                Self::assemble_tree(
-                    tree,
-                    index,
-                    branches,
-                    node_comparator,
-                    inode_set,
-                    column_properties,
-                    ctx,
-                );
+            let is_dir = tree[index].get().is_dir();
+
+            // Descend into child directories before accounting for them so that their aggregate
+            // sizes are available.
+            if is_dir && !frame.descended {
+                frame.descended = true;
+                stack.push(Frame::new(tree, index, branches, ctx));
+                continue;
             }
 
+            frame.cursor += 1;
+            frame.descended = false;
+
             let node = tree[index].get();
 
+            // If a hard-link is already accounted for then it adds to neither the size nor the file
+            // count of its parent directory unless every link is to be counted, which keeps
+            // `--count` in agreement with `--disk-usage file`. Symlinks left unfollowed share the
+            // inode of their target but have no size of their own so they mustn't claim it. There
+            // is nothing to account for without sizes or counts so the inode isn't queried for at
+            // all.
+            let counted = ctx.count_hard_links
+                || node.is_unfollowed()
+                || ctx.suppress_size && !ctx.count && !ctx.dir_stats
+                || node
+                    .inode()
+                    .map_or(true, |inode| inode.nlink <= 1 || inode_set.insert(inode));
+
+            if is_dir {
+                frame.file_count += node.file_count().unwrap_or_default();
+            } else if counted {
+                frame.file_count += 1;
+            }
+
             #[cfg(unix)]
             Self::update_column_properties(column_properties, node, ctx);
 
             #[cfg(not(unix))]
             Self::update_column_properties(column_properties, node, ctx);
 
-            // If a hard-link is already accounted for then don't increment parent dir size.
-            if let Some(inode) = node.inode() {
-                if inode.nlink > 1 && !inode_set.insert(inode) {
-                    continue;
-                }
+            if !counted {
+                continue;
             }
 
             if let Some(file_size) = node.file_size() {
-                dir_size += file_size;
+                frame.dir_size += file_size;
+            }
+
+            if let (Some(other_dir_size), Some(other_file_size)) =
+                (frame.other_dir_size.as_mut(), node.other_file_size())
+            {
+                *other_dir_size += other_file_size;
             }
         }
+    }
+
+    /// Decides which symlinks to directories get to show their contents when following links so
+    /// that no directory is shown, and counted, more than once. A symlink is marked as a revisit
+    /// and its contents are discarded if its target is part of the tree by way of its real path or
+    /// was already reached through a symlink that comes before it in sorted order. Directories that
+    /// were left out of the tree, e.g. because they're hidden or excluded, don't count as shown.
+    fn mark_revisits(
+        tree: &mut Arena<Node>,
+        root_id: NodeId,
+        branches: &mut HashMap<PathBuf, Vec<NodeId>>,
+        node_comparator: &NodeComparator,
+    ) {
+        let is_real_dir = |node: &Node| node.is_dir() && !node.is_symlink();
 
-        if dir_size.value() > 0 {
-            let dir = tree[current_node_id].get_mut();
+        // Directories reached by way of their real path take precedence over every symlink no
+        // matter where either of them is sorted.
+        let mut shown = HashSet::new();
+        let mut stack = vec![root_id];
 
-            dir.set_file_size(dir_size);
+        while let Some(node_id) = stack.pop() {
+            let node = tree[node_id].get();
+
+            if let Some(inode) = node.inode() {
+                shown.insert(inode);
+            }
+
+            if let Some(children) = branches.get(node.path()) {
+                stack.extend(children.iter().filter(|id| is_real_dir(tree[**id].get())));
+            }
         }
 
-        let dir = tree[current_node_id].get();
+        let mut stack = vec![root_id];
+
+        while let Some(node_id) = stack.pop() {
+            let node = tree[node_id].get();
+
+            let is_link = node_id != root_id && node.is_symlink() && !node.is_cyclic();
+            let is_new = node.inode().map_or(true, |inode| shown.insert(inode));
+
+            if is_link && !is_new {
+                let path = node.path().to_path_buf();
+                tree[node_id].get_mut().mark_revisit();
+                branches.remove(&path);
+                continue;
+            }
 
-        #[cfg(unix)]
-        Self::update_column_properties(column_properties, dir, ctx);
+            let Some(children) = branches.get_mut(node.path()) else {
+                continue;
+            };
 
-        #[cfg(not(unix))]
-        Self::update_column_properties(column_properties, dir, ctx);
+            children.sort_by(|id_a, id_b| node_comparator(tree[*id_a].get(), tree[*id_b].get()));
 
-        children.sort_by(|&id_a, &id_b| {
-            let node_a = tree[id_a].get();
-            let node_b = tree[id_b].get();
-            node_comparator(node_a, node_b)
-        });
+            // Pushed in reverse so that they're popped in sorted order.
+            stack.extend(children.iter().rev().filter(|id| tree[**id].get().is_dir()));
+        }
+    }
 
-        // Append children to current node.
-        for child_id in children {
-            current_node_id.append(child_id, tree);
+    /// Removes what shouldn't be shown on account of filtering: everything besides matches and
+    /// their ancestors if `--matches-only` is provided, otherwise empty directories.
+    fn prune(root_id: NodeId, tree: &mut Arena<Node>, ctx: &Context) -> Result<()> {
+        if ctx.matches_only {
+            Self::prune_unmatched(root_id, tree, ctx.match_predicate()?);
+        } else if ctx.prune
+            || !ctx.pattern.is_empty()
+            || !ctx.extension.is_empty()
+            || ctx.broken_only
+            || ctx.has_size_filter()
+            || ctx.has_time_filter()
+        {
+            Self::prune_directories(root_id, tree);
         }
+
+        Ok(())
     }
 
-    /// Function to remove empty directories.
+    /// Function to remove empty directories, including those left empty once their own empty
+    /// directories are removed. Directories are visited in reverse pre-order, which puts each of
+    /// them after everything beneath it, so that a single pass suffices.
     fn prune_directories(root_id: NodeId, tree: &mut Arena<Node>) {
-        let to_prune = root_id
+        let mut dirs = root_id
+            .descendants(tree)
+            .skip(1)
+            .filter(|node_id| tree[*node_id].get().is_dir())
+            .collect::<Vec<_>>();
+
+        while let Some(node_id) = dirs.pop() {
+            if node_id.children(tree).next().is_none() {
+                node_id.remove_subtree(tree);
+            }
+        }
+    }
+
+    /// Notes how each [Node] changed since `snapshot` was taken. Entries that have since been
+    /// removed are attributed to the nearest directory that still exists.
+    fn apply_deltas(root_id: NodeId, tree: &mut Arena<Node>, snapshot: &Snapshot) {
+        let root_path = tree[root_id].get().path().to_path_buf();
+
+        let mut deltas = root_id
+            .descendants(tree)
+            .map(|node_id| {
+                let node = tree[node_id].get();
+                let path = node
+                    .path()
+                    .strip_prefix(&root_path)
+                    .unwrap_or_else(|_| node.path());
+
+                let delta = Delta {
+                    before: snapshot.get(path),
+                    after: node.file_size().map_or(0, FileSize::value),
+                    removed: 0,
+                };
+
+                (path.to_path_buf(), (node_id, delta))
+            })
+            .collect::<HashMap<_, _>>();
+
+        let removed_parents = snapshot
+            .paths()
+            .filter(|path| !deltas.contains_key(*path))
+            .filter_map(Path::parent)
+            .map(Path::to_path_buf)
+            .collect::<Vec<_>>();
+
+        for parent in removed_parents {
+            if let Some((_, delta)) = deltas.get_mut(&parent) {
+                delta.removed += 1;
+            }
+        }
+
+        for (node_id, delta) in deltas.into_values() {
+            if delta.is_change() {
+                tree[node_id].get_mut().set_delta(delta);
+            }
+        }
+    }
+
+    /// Function to remove everything that isn't a match according to `is_match` or an ancestor of
+    /// one. Descendants of matching directories that aren't matches themselves are removed as well.
+    fn prune_unmatched(
+        root_id: NodeId,
+        tree: &mut Arena<Node>,
+        is_match: impl Fn(&DirEntry) -> bool,
+    ) {
+        let mut to_keep = HashSet::from([root_id]);
+
+        for node_id in root_id.descendants(tree).skip(1) {
+            if !is_match(tree[node_id].get().dir_entry()) {
+                continue;
+            }
+
+            for ancestor_id in node_id.ancestors(tree) {
+                if !to_keep.insert(ancestor_id) {
+                    break;
+                }
+            }
+        }
+
+        // Only the topmost of the nodes to be removed need to be detached.
+        let to_detach = root_id
             .descendants(tree)
             .skip(1)
             .filter(|node_id| {
-                tree[*node_id]
-                    .get()
-                    .is_dir()
-                    .then(|| node_id.children(tree).count() == 0)
-                    .unwrap_or(false)
+                !to_keep.contains(node_id)
+                    && tree[*node_id]
+                        .parent()
+                        .is_some_and(|parent_id| to_keep.contains(&parent_id))
             })
             .collect::<Vec<_>>();
 
-        if to_prune.is_empty() {
+        for node_id in to_detach {
+            node_id.detach(tree);
+        }
+    }
+
+    /// Function to remove directories, excluding the root, whose aggregate size is below
+    /// `min_size`. Directories are removed bottom-up so that nested directories are dealt with
+    /// before their ancestors.
+    fn prune_small_directories(root_id: NodeId, tree: &mut Arena<Node>, min_size: u64) {
+        let mut to_prune = root_id
+            .descendants(tree)
+            .skip(1)
+            .filter(|node_id| {
+                let node = tree[*node_id].get();
+                node.is_dir() && node.file_size().map_or(0, FileSize::value) < min_size
+            })
+            .collect::<Vec<_>>();
+
+        while let Some(node_id) = to_prune.pop() {
+            node_id.remove_subtree(tree);
+        }
+    }
+
+    /// Keeps only the first `--limit` children of each directory in sorted order. The first child
+    /// beyond the limit stands in for the rest: its own children are removed and it takes on the
+    /// aggregate size and count of everything that is hidden so that it may be rendered as a
+    /// trailing `… N more` line.
+    fn limit_children(root_id: NodeId, tree: &mut Arena<Node>, ctx: &Context) {
+        let Some(limit) = ctx.limit.map(NonZeroUsize::get) else {
+            return;
+        };
+
+        let dirs = root_id
+            .descendants(tree)
+            .filter(|&node_id| node_id.children(tree).nth(limit).is_some())
+            .collect::<Vec<_>>();
+
+        for dir_id in dirs {
+            // Directories beneath those that were hidden are already removed.
+            if tree[dir_id].is_removed() {
+                continue;
+            }
+
+            let hidden = dir_id.children(tree).skip(limit).collect::<Vec<_>>();
+
+            let file_count = hidden
+                .iter()
+                .flat_map(|node_id| node_id.descendants(tree))
+                .fold(FileCount::default(), |count, node_id| {
+                    count + tree[node_id].get()
+                });
+
+            let mut file_size = FileSize::from(ctx);
+            let mut other_file_size = ctx.both_sizes.then(|| FileSize::other_from(ctx));
+
+            for node_id in &hidden {
+                let node = tree[*node_id].get();
+
+                if let Some(size) = node.file_size() {
+                    file_size += size;
+                }
+
+                if let (Some(total), Some(size)) =
+                    (other_file_size.as_mut(), node.other_file_size())
+                {
+                    *total += size;
+                }
+            }
+
+            let Some((&stand_in_id, rest)) = hidden.split_first() else {
+                continue;
+            };
+
+            let grandchildren = stand_in_id.children(tree).collect::<Vec<_>>();
+
+            for node_id in rest.iter().chain(&grandchildren) {
+                node_id.remove_subtree(tree);
+            }
+
+            let stand_in = tree[stand_in_id].get_mut();
+
+            stand_in.set_overflow(Overflow {
+                entries: hidden.len(),
+                file_count,
+            });
+
+            stand_in.set_file_size(file_size);
+
+            if let Some(other_file_size) = other_file_size {
+                stand_in.set_other_file_size(other_file_size);
+            }
+        }
+    }
+
+    /// Computes the fraction of its parent directory's aggregate disk usage that each [Node],
+    /// excluding the root, accounts for if `--bars` is enabled. Nodes without a size or whose
+    /// parent has a size of zero are left without a share.
+    fn compute_shares(root_id: NodeId, tree: &mut Arena<Node>, ctx: &Context) {
+        if !ctx.bars {
             return;
         }
 
-        to_prune
+        let shares = root_id
+            .descendants(tree)
+            .skip(1)
+            .filter_map(|node_id| {
+                let parent_id = tree[node_id].parent()?;
+                let size = tree[node_id].get().file_size().map(FileSize::value)?;
+                let total = tree[parent_id]
+                    .get()
+                    .file_size()
+                    .map(FileSize::value)
+                    .filter(|total| *total > 0)?;
+
+                Some((node_id, size as f64 / total as f64))
+            })
+            .collect::<Vec<_>>();
+
+        for (node_id, share) in shares {
+            tree[node_id].get_mut().set_share(share);
+        }
+    }
+
+    /// Computes where each [Node] lies on the `--heat` gradient. See [`Self::compute_size_heat`]
+    /// and [`Self::compute_age_heat`].
+    fn compute_heat(root_id: NodeId, tree: &mut Arena<Node>, ctx: &Context) {
+        let heats = match ctx.heat {
+            Some(heat::Metric::Age) => Self::compute_age_heat(root_id, tree, ctx.heat_scope),
+            _ if ctx.size_gradient => Self::compute_size_heat(root_id, tree, ctx.heat_scope),
+            _ => return,
+        };
+
+        for (node_id, heat) in heats {
+            tree[node_id].get_mut().set_heat(heat);
+        }
+    }
+
+    /// Computes the disk usage of each [Node] relative to the largest of its siblings or to the
+    /// root's total. Nothing is computed for [`heat::Scope::Largest`] as the largest entry is only
+    /// known once what is to be displayed has been determined.
+    fn compute_size_heat(
+        root_id: NodeId,
+        tree: &Arena<Node>,
+        scope: heat::Scope,
+    ) -> Vec<(NodeId, f64)> {
+        let mut heats = vec![];
+
+        if scope == heat::Scope::Largest {
+            return heats;
+        }
+
+        let size_of = |node_id: NodeId| tree[node_id].get().file_size().map_or(0, FileSize::value);
+        let total = size_of(root_id);
+
+        for parent_id in root_id.descendants(tree) {
+            let children = parent_id
+                .children(tree)
+                .map(|node_id| (node_id, size_of(node_id)))
+                .collect::<Vec<_>>();
+
+            let max = match scope {
+                heat::Scope::Siblings => children.iter().map(|(_, size)| *size).max().unwrap_or(0),
+                _ => total,
+            };
+
+            heats.extend(children.into_iter().map(|(node_id, size)| {
+                let heat = if max == 0 {
+                    0.0
+                } else {
+                    size as f64 / max as f64
+                };
+
+                (node_id, heat)
+            }));
+        }
+
+        heats
+    }
+
+    /// Computes how long ago each [Node] was modified relative to the oldest of its siblings or
+    /// of the whole tree. Directories are as recent as the newest entry beneath them. Ages are
+    /// compared on a logarithmic scale so that an hour-old file still stands apart from a
+    /// minute-old one in a tree that spans years.
+    fn compute_age_heat(
+        root_id: NodeId,
+        tree: &Arena<Node>,
+        scope: heat::Scope,
+    ) -> Vec<(NodeId, f64)> {
+        let now = SystemTime::now();
+        let node_ids = root_id.descendants(tree).collect::<Vec<_>>();
+
+        let mut ages = node_ids
             .iter()
-            .for_each(|node_id| node_id.remove_subtree(tree));
+            .map(|node_id| {
+                let modified = tree[*node_id].get().modified().unwrap_or(UNIX_EPOCH);
+                let age = now.duration_since(modified).unwrap_or_default();
+                (*node_id, age.as_secs_f64().ln_1p())
+            })
+            .collect::<HashMap<_, _>>();
+
+        // Descendants come after their ancestors so walking backwards visits children first.
+        for node_id in node_ids.iter().rev().filter(|id| **id != root_id) {
+            let Some(parent_id) = tree[*node_id].parent() else {
+                continue;
+            };
+
+            let age = ages[node_id];
+            ages.entry(parent_id)
+                .and_modify(|parent| *parent = parent.min(age));
+        }
+
+        let oldest = |node_ids: &[NodeId]| {
+            node_ids
+                .iter()
+                .map(|node_id| ages[node_id])
+                .fold(0.0, f64::max)
+        };
+
+        let ratio = |node_id: NodeId, max: f64| if max > 0.0 { ages[&node_id] / max } else { 0.0 };
+        let tree_oldest = oldest(&node_ids);
+
+        // The root has no siblings so it's always measured against the whole tree.
+        let mut heats = vec![(root_id, ratio(root_id, tree_oldest))];
+
+        for parent_id in &node_ids {
+            let children = parent_id.children(tree).collect::<Vec<_>>();
+
+            let max = match scope {
+                heat::Scope::Siblings => oldest(&children),
+                _ => tree_oldest,
+            };
+
+            heats.extend(
+                children
+                    .into_iter()
+                    .map(|node_id| (node_id, ratio(node_id, max))),
+            );
+        }
+
+        heats
+    }
+
+    /// Assigns each [Node] its git status. Nodes that aren't part of a repository or that have no
+    /// changes are marked clean.
+    fn apply_git_statuses(root_id: NodeId, tree: &mut Arena<Node>, statuses: &git::Statuses) {
+        let node_ids = root_id.descendants(tree).collect::<Vec<_>>();
+
+        for node_id in node_ids {
+            let node = tree[node_id].get_mut();
+            let status = statuses.get(node.path()).unwrap_or_default();
+            node.set_git_status(status);
+        }
+    }
+
+    /// Merges each chain of directories that contain nothing but a single directory into the
+    /// deepest directory of the chain which takes the place of the top-most one. The deepest
+    /// directory keeps its own depth and aggregate size, is labeled with the path of the chain
+    /// relative to the top-most directory's parent, e.g. `main/java/com/example`, and keeps track
+    /// of how many directories were merged into it so that they're still counted. The root is
+    /// never collapsed.
+    fn collapse_directories(root_id: NodeId, tree: &mut Arena<Node>) {
+        let sole_child_dir = |node_id: NodeId, tree: &Arena<Node>| {
+            let mut children = node_id.children(tree);
+
+            match (children.next(), children.next()) {
+                (Some(child_id), None) if tree[child_id].get().is_dir() => Some(child_id),
+                _ => None,
+            }
+        };
+
+        let dirs = root_id
+            .descendants(tree)
+            .skip(1)
+            .filter(|&node_id| tree[node_id].get().is_dir())
+            .collect::<Vec<_>>();
+
+        for top_id in dirs {
+            // Directories in the middle of a chain that was already collapsed are removed.
+            if tree[top_id].is_removed() {
+                continue;
+            }
+
+            let Some(mut bottom_id) = sole_child_dir(top_id, tree) else {
+                continue;
+            };
+
+            let mut merged = 1;
+
+            while let Some(child_id) = sole_child_dir(bottom_id, tree) {
+                bottom_id = child_id;
+                merged += 1;
+            }
+
+            tree[bottom_id].get_mut().set_collapsed(merged);
+
+            let Some(parent_id) = tree[top_id].parent() else {
+                continue;
+            };
+
+            let label = tree[bottom_id]
+                .get()
+                .path()
+                .strip_prefix(tree[parent_id].get().path())
+                .map(|path| path.as_os_str().to_owned());
+
+            if let Ok(label) = label {
+                tree[bottom_id].get_mut().set_alias(label);
+            }
+
+            bottom_id.detach(tree);
+            top_id.insert_before(bottom_id, tree);
+            top_id.remove_subtree(tree);
+        }
+    }
+
+    /// Hides everything shallower than `--min-depth` besides the root, as well as all
+    /// directories besides the root if `--files-only` is provided. What remains within `--level`
+    /// is moved beneath the root, sorted anew, and labeled by its path relative to the root. Sizes
+    /// were already aggregated so the root's is unaffected.
+    fn surface_entries(
+        root_id: NodeId,
+        tree: &mut Arena<Node>,
+        node_comparator: &NodeComparator,
+        ctx: &Context,
+    ) {
+        let min_depth = ctx.min_depth.unwrap_or_default();
+
+        if min_depth <= 1 && !ctx.files_only {
+            return;
+        }
+
+        let depth_of = |id: NodeId, tree: &Arena<Node>| tree[id].get().depth();
+
+        // Depths may jump by more than one level if `--collapse` merged a chain of directories.
+        let mut surfaced = root_id
+            .descendants(tree)
+            .skip(1)
+            .filter(|&node_id| {
+                let depth = depth_of(node_id, tree);
+
+                if depth < min_depth || depth > ctx.level() {
+                    return false;
+                }
+
+                if ctx.files_only {
+                    return !tree[node_id].get().is_dir();
+                }
+
+                tree[node_id]
+                    .parent()
+                    .is_some_and(|parent_id| depth_of(parent_id, tree) < min_depth)
+            })
+            .collect::<Vec<_>>();
+
+        surfaced.sort_by(|&id_a, &id_b| node_comparator(tree[id_a].get(), tree[id_b].get()));
+
+        let root_path = tree[root_id].get().path().to_path_buf();
+
+        for &node_id in &surfaced {
+            let label = tree[node_id]
+                .get()
+                .path()
+                .strip_prefix(&root_path)
+                .map(|path| path.as_os_str().to_owned());
+
+            if let Ok(label) = label {
+                tree[node_id].get_mut().set_alias(label);
+            }
+
+            node_id.detach(tree);
+        }
+
+        let hidden = root_id.children(tree).collect::<Vec<_>>();
+
+        for node_id in hidden {
+            node_id.remove_subtree(tree);
+        }
+
+        for node_id in surfaced {
+            root_id.append(node_id, tree);
+        }
+    }
+
+    /// Counts everything beneath each directory at a depth of `level` whose contents would
+    /// otherwise go unseen.
+    fn summarize_directories(root_id: NodeId, tree: &mut Arena<Node>, level: usize) {
+        let summaries = root_id
+            .descendants(tree)
+            .filter(|node_id| {
+                let node = tree[*node_id].get();
+                node.is_dir() && node.depth() == level && node_id.children(tree).next().is_some()
+            })
+            .map(|node_id| {
+                let summary = node_id
+                    .descendants(tree)
+                    .skip(1)
+                    .fold(FileCount::default(), |count, id| count + tree[id].get());
+
+                (node_id, summary)
+            })
+            .collect::<Vec<_>>();
 
-        Self::prune_directories(root_id, tree);
+        for (node_id, summary) in summaries {
+            tree[node_id].get_mut().set_summary(summary);
+        }
     }
 
     /// Filter `arena` for only directories.
@@ -316,42 +1076,80 @@ impl Tree {
         to_detach.iter().for_each(|node_id| node_id.detach(tree));
     }
 
-    /// Compute total number of files for a single directory without recurring into child
-    /// directories. Files are grouped into three categories: directories, regular files, and
-    /// symlinks.
-    pub fn compute_file_count(node_id: NodeId, tree: &Arena<Node>) -> FileCount {
-        node_id
-            .children(tree)
-            .map(|child_id| tree[child_id].get())
-            .fold(FileCount::default(), |acc, node| acc + node)
+    /// Widens `max_size_width` and `max_unit_width` if necessary so that `file_size` fits.
+    fn update_size_widths(
+        file_size: &FileSize,
+        max_size_width: &mut usize,
+        max_unit_width: &mut usize,
+        ctx: &Context,
+    ) {
+        if ctx.byte_metric() && (ctx.human || ctx.group_digits) {
+            let out = format!("{file_size}");
+            let [size, unit]: [&str; 2] = out.split(' ').collect::<Vec<&str>>().try_into().unwrap();
+
+            *max_size_width = (*max_size_width).max(size.len());
+            *max_unit_width = (*max_unit_width).max(unit.len());
+        } else {
+            // A size of zero still takes up a column.
+            let size_cols = utils::num_integral(file_size.value()).max(1);
+
+            *max_size_width = (*max_size_width).max(size_cols);
+        }
     }
 
     /// Updates [`column::Properties`] with provided [`Node`].
     #[cfg(unix)]
     fn update_column_properties(col_props: &mut column::Properties, node: &Node, ctx: &Context) {
         if let Some(file_size) = node.file_size() {
-            if ctx.byte_metric() && ctx.human {
-                let out = format!("{file_size}");
-                let [size, unit]: [&str; 2] =
-                    out.split(' ').collect::<Vec<&str>>().try_into().unwrap();
+            Self::update_size_widths(
+                file_size,
+                &mut col_props.max_size_width,
+                &mut col_props.max_size_unit_width,
+                ctx,
+            );
+        }
 
-                let file_size_cols = size.len();
-                let file_size_unit_cols = unit.len();
+        if let Some(other_file_size) = node.other_file_size() {
+            Self::update_size_widths(
+                other_file_size,
+                &mut col_props.max_other_size_width,
+                &mut col_props.max_size_unit_width,
+                ctx,
+            );
+        }
 
-                if file_size_cols > col_props.max_size_width {
-                    col_props.max_size_width = file_size_cols;
-                }
+        if ctx.count {
+            let count_cols = utils::num_integral(node.file_count().unwrap_or(1) as u64).max(1);
 
-                if file_size_unit_cols > col_props.max_size_unit_width {
-                    col_props.max_size_unit_width = file_size_unit_cols;
-                }
-            } else {
-                let file_size_cols = utils::num_integral(file_size.value());
+            if count_cols > col_props.max_count_width {
+                col_props.max_count_width = count_cols;
+            }
+        }
+
+        if ctx.size_gradient && node.depth() > 0 {
+            let size = node.file_size().map_or(0, FileSize::value);
 
-                if file_size_cols > col_props.max_size_width {
-                    col_props.max_size_width = file_size_cols;
+            if size > col_props.max_file_size {
+                col_props.max_file_size = size;
+            }
+        }
+
+        if ctx.ext_column {
+            let ext_cols = node.extension_label().chars().count();
+
+            if ext_cols > col_props.max_ext_width {
+                col_props.max_ext_width = ext_cols;
+            }
+        }
+
+        if ctx.ino {
+            if let Some(ino) = node.ino() {
+                let ino_num_integral = utils::num_integral(ino);
+
+                if ino_num_integral > col_props.max_ino_width {
+                    col_props.max_ino_width = ino_num_integral;
                 }
-            };
+            }
         }
 
         if ctx.long {
@@ -371,14 +1169,6 @@ impl Tree {
                 }
             }
 
-            if let Some(ino) = node.ino() {
-                let ino_num_integral = utils::num_integral(ino);
-
-                if ino_num_integral > col_props.max_ino_width {
-                    col_props.max_ino_width = ino_num_integral;
-                }
-            }
-
             if let Some(nlink) = node.nlink() {
                 let nlink_num_integral = utils::num_integral(nlink);
 
@@ -394,6 +1184,19 @@ impl Tree {
                     col_props.max_block_width = blocks_num_integral;
                 }
             }
+
+            let time_len = node
+                .timestamp(ctx.time())
+                .map_or(PLACEHOLDER.len(), |stamp| {
+                    ctx.time_format()
+                        .apply(DateTime::<Local>::from(stamp))
+                        .chars()
+                        .count()
+                });
+
+            if time_len > col_props.max_time_width {
+                col_props.max_time_width = time_len;
+            }
         }
     }
 
@@ -401,28 +1204,78 @@ impl Tree {
     #[cfg(not(unix))]
     fn update_column_properties(col_props: &mut column::Properties, node: &Node, ctx: &Context) {
         if let Some(file_size) = node.file_size() {
-            if ctx.byte_metric() && ctx.human {
-                let out = format!("{file_size}");
-                let [size, unit]: [&str; 2] =
-                    out.split(' ').collect::<Vec<&str>>().try_into().unwrap();
+            Self::update_size_widths(
+                file_size,
+                &mut col_props.max_size_width,
+                &mut col_props.max_size_unit_width,
+                ctx,
+            );
+        }
 
-                let file_size_cols = size.len();
-                let file_size_unit_cols = unit.len();
+        if let Some(other_file_size) = node.other_file_size() {
+            Self::update_size_widths(
+                other_file_size,
+                &mut col_props.max_other_size_width,
+                &mut col_props.max_size_unit_width,
+                ctx,
+            );
+        }
 
-                if file_size_cols > col_props.max_size_width {
-                    col_props.max_size_width = file_size_cols;
-                }
+        if ctx.count {
+            let count_cols = utils::num_integral(node.file_count().unwrap_or(1) as u64).max(1);
 
-                if file_size_unit_cols > col_props.max_size_unit_width {
-                    col_props.max_size_unit_width = file_size_unit_cols;
-                }
-            } else {
-                let file_size_cols = utils::num_integral(file_size.value());
+            if count_cols > col_props.max_count_width {
+                col_props.max_count_width = count_cols;
+            }
+        }
+
+        if ctx.size_gradient && node.depth() > 0 {
+            let size = node.file_size().map_or(0, FileSize::value);
+
+            if size > col_props.max_file_size {
+                col_props.max_file_size = size;
+            }
+        }
+
+        if ctx.ext_column {
+            let ext_cols = node.extension_label().chars().count();
+
+            if ext_cols > col_props.max_ext_width {
+                col_props.max_ext_width = ext_cols;
+            }
+        }
+
+        if ctx.ino {
+            if let Some(ino) = node.ino() {
+                let ino_num_integral = utils::num_integral(ino);
 
-                if file_size_cols > col_props.max_size_width {
-                    col_props.max_size_width = file_size_cols;
+                if ino_num_integral > col_props.max_ino_width {
+                    col_props.max_ino_width = ino_num_integral;
                 }
-            };
+            }
+        }
+    }
+}
+
+impl Frame {
+    fn new(
+        tree: &Arena<Node>,
+        node_id: NodeId,
+        branches: &mut HashMap<PathBuf, Vec<NodeId>>,
+        ctx: &Context,
+    ) -> Self {
+        let children = branches
+            .remove(tree[node_id].get().path())
+            .unwrap_or_default();
+
+        Self {
+            node_id,
+            children,
+            cursor: 0,
+            descended: false,
+            dir_size: FileSize::from(ctx),
+            other_dir_size: ctx.both_sizes.then(|| FileSize::other_from(ctx)),
+            file_count: 0,
         }
     }
 }
@@ -441,8 +1294,10 @@ impl TryFrom<&Context> for WalkParallel {
         builder
             .follow_links(ctx.follow)
             .git_ignore(!ctx.no_ignore)
+            .git_exclude(!ctx.no_ignore)
+            .git_global(!ctx.no_ignore && !ctx.no_global_ignore)
             .hidden(!ctx.hidden)
-            .overrides(ctx.no_git_override()?)
+            .overrides(ctx.overrides()?)
             .same_file_system(ctx.same_fs)
             .threads(ctx.threads);
 
@@ -450,14 +1305,68 @@ impl TryFrom<&Context> for WalkParallel {
             builder.max_depth(Some(1)).threads(1);
         }
 
-        if ctx.pattern.is_some() {
+        let mut predicates = vec![];
+
+        if !ctx.pattern.is_empty() {
             if ctx.glob || ctx.iglob {
-                builder.filter_entry(ctx.glob_predicate()?);
+                predicates.push(ctx.glob_predicate()?);
             } else {
-                builder.filter_entry(ctx.regex_predicate()?);
+                predicates.push(ctx.regex_predicate()?);
             }
         }
 
+        if !ctx.extension.is_empty() {
+            predicates.push(ctx.extension_predicate());
+        }
+
+        if ctx.broken_only {
+            predicates.push(Context::broken_predicate());
+        }
+
+        if ctx.stdin {
+            predicates.push(ctx.stdin_predicate());
+        }
+
+        if ctx.has_exclusions() {
+            predicates.push(ctx.exclude_predicate()?);
+        }
+
+        if !predicates.is_empty() {
+            builder.filter_entry(move |dir_entry| predicates.iter().all(|pred| pred(dir_entry)));
+        }
+
         Ok(builder.build_parallel())
     }
 }
+
+#[test]
+fn test_render() {
+    use clap::Parser;
+
+    let ctx = Context::try_parse_from([
+        "erd",
+        "--threads",
+        "1",
+        "--disk-usage",
+        "logical",
+        "--sort",
+        "name",
+        "--level",
+        "1",
+        "--color",
+        "none",
+        "tests/data",
+    ])
+    .unwrap();
+
+    crate::styles::init(ctx.no_color(), ctx.color_icons, ctx.ascii, &ctx.theme);
+
+    let (tree, ctx) = Tree::try_init(ctx, None).unwrap();
+
+    let mut out = vec![];
+    tree.render(&mut out, &ctx).unwrap();
+
+    let out = String::from_utf8(out).unwrap();
+
+    assert!(out.ends_with("1241 B data\n\n3 directories, 6 files\n"));
+}
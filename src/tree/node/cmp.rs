@@ -7,19 +7,39 @@ pub type NodeComparator = dyn Fn(&Node, &Node) -> Ordering;
 
 /// Yields function pointer to the appropriate `Node` comparator.
 pub fn comparator(ctx: &Context) -> Box<NodeComparator> {
-    let sort_type = ctx.sort;
+    let sort_comparator = sort_comparator(&ctx.sort, ctx.reverse);
 
     match ctx.dir_order {
-        dir::Order::First => {
-            Box::new(move |a, b| dir_first_comparator(a, b, base_comparator(sort_type)))
-        },
-        dir::Order::Last => {
-            Box::new(move |a, b| dir_last_comparator(a, b, base_comparator(sort_type)))
-        },
-        dir::Order::None => base_comparator(sort_type),
+        dir::Order::First => Box::new(move |a, b| dir_first_comparator(a, b, &sort_comparator)),
+        dir::Order::Last => Box::new(move |a, b| dir_last_comparator(a, b, &sort_comparator)),
+        dir::Order::None => sort_comparator,
     }
 }
 
+/// Chains the comparators for each of `sort_types` such that each one breaks the ties of those
+/// before it, with any remaining ties broken by file name. The ordering of the whole is inverted if
+/// `reverse` is `true`.
+fn sort_comparator(sort_types: &[sort::Type], reverse: bool) -> Box<NodeComparator> {
+    let bases = sort_types
+        .iter()
+        .copied()
+        .map(base_comparator)
+        .collect::<Vec<_>>();
+
+    let chained = move |a: &Node, b: &Node| {
+        bases
+            .iter()
+            .fold(Ordering::Equal, |ord, base| ord.then_with(|| base(a, b)))
+            .then_with(|| naming::comparator(a, b))
+    };
+
+    if reverse {
+        return Box::new(move |a, b| chained(a, b).reverse());
+    }
+
+    Box::new(chained)
+}
+
 /// Orders directories first. Provides a fallback if inputs are not directories.
 fn dir_first_comparator(
     a: &Node,
@@ -66,54 +86,72 @@ mod time_stamping {
     pub mod accessed {
         use crate::tree::node::Node;
         use core::cmp::Ordering;
-        use std::time::SystemTime;
 
         /// Comparator that sorts [Node]s by Last Access timestamp, newer to older.
         pub fn comparator(a: &Node, b: &Node) -> Ordering {
-            let a_stamp = a.accessed().unwrap_or_else(SystemTime::now);
-            let b_stamp = b.accessed().unwrap_or_else(SystemTime::now);
-            a_stamp.cmp(&b_stamp)
+            cmp_stamps(a, b)
         }
 
         /// Comparator that sorts [Node]s by Access timestamp, older to newer.
         pub fn rev_comparator(a: &Node, b: &Node) -> Ordering {
-            comparator(b, a)
+            cmp_stamps(b, a)
+        }
+
+        /// Compares timestamps. Nodes whose timestamp is unavailable, e.g. because the platform
+        /// doesn't support it, are treated as being the newest.
+        fn cmp_stamps(a: &Node, b: &Node) -> Ordering {
+            match (a.accessed(), b.accessed()) {
+                (Some(a_stamp), Some(b_stamp)) => a_stamp.cmp(&b_stamp),
+                (a_stamp, b_stamp) => a_stamp.is_none().cmp(&b_stamp.is_none()),
+            }
         }
     }
 
     pub mod created {
         use crate::tree::node::Node;
         use core::cmp::Ordering;
-        use std::time::SystemTime;
 
         /// Comparator that sorts [Node]s by Creation timestamp, newer to older.
         pub fn comparator(a: &Node, b: &Node) -> Ordering {
-            let a_stamp = a.created().unwrap_or_else(SystemTime::now);
-            let b_stamp = b.created().unwrap_or_else(SystemTime::now);
-            a_stamp.cmp(&b_stamp)
+            cmp_stamps(a, b)
         }
 
         /// Comparator that sorts [Node]s by Creation timestamp, older to newer.
         pub fn rev_comparator(a: &Node, b: &Node) -> Ordering {
-            comparator(b, a)
+            cmp_stamps(b, a)
+        }
+
+        /// Compares timestamps. Nodes whose timestamp is unavailable, e.g. because the platform
+        /// doesn't support it, are treated as being the newest.
+        fn cmp_stamps(a: &Node, b: &Node) -> Ordering {
+            match (a.created(), b.created()) {
+                (Some(a_stamp), Some(b_stamp)) => a_stamp.cmp(&b_stamp),
+                (a_stamp, b_stamp) => a_stamp.is_none().cmp(&b_stamp.is_none()),
+            }
         }
     }
 
     pub mod modified {
         use crate::tree::node::Node;
         use core::cmp::Ordering;
-        use std::time::SystemTime;
 
         /// Comparator that sorts [Node]s by Alteration timestamp, newer to older.
         pub fn comparator(a: &Node, b: &Node) -> Ordering {
-            let a_stamp = a.modified().unwrap_or_else(SystemTime::now);
-            let b_stamp = b.modified().unwrap_or_else(SystemTime::now);
-            a_stamp.cmp(&b_stamp)
+            cmp_stamps(a, b)
         }
 
         /// Comparator that sorts [Node]s by Alteration timestamp, older to newer.
         pub fn rev_comparator(a: &Node, b: &Node) -> Ordering {
-            comparator(b, a)
+            cmp_stamps(b, a)
+        }
+
+        /// Compares timestamps. Nodes whose timestamp is unavailable, e.g. because the platform
+        /// doesn't support it, are treated as being the newest.
+        fn cmp_stamps(a: &Node, b: &Node) -> Ordering {
+            match (a.modified(), b.modified()) {
+                (Some(a_stamp), Some(b_stamp)) => a_stamp.cmp(&b_stamp),
+                (a_stamp, b_stamp) => a_stamp.is_none().cmp(&b_stamp.is_none()),
+            }
         }
     }
 }
@@ -1,25 +1,27 @@
 use crate::{
     context::Context,
-    disk_usage::file_size::{byte, line_count, word_count, DiskUsage, FileSize},
+    disk_usage::file_size::{byte, file_count, line_count, word_count, DiskUsage, FileSize},
     fs::inode::Inode,
-    icons,
-    styles::get_ls_colors,
-    tree::error::Error,
+    git, icons,
+    snapshot::Delta,
+    styles,
+    tree::{count::FileCount, error::Error},
 };
 use ansi_term::Style;
-use ignore::DirEntry;
-use lscolors::Style as LS_Style;
+use ignore::{DirEntry, WalkBuilder};
 use std::{
     borrow::Cow,
+    cell::OnceCell,
     convert::TryFrom,
-    ffi::OsStr,
-    fs::{FileType, Metadata},
+    ffi::{OsStr, OsString},
+    fs::{self, FileType, Metadata},
     path::{Path, PathBuf},
     time::SystemTime,
 };
 
 #[cfg(unix)]
 use crate::{
+    context::time,
     disk_usage::file_size::block,
     fs::permissions::{FileMode, SymbolicNotation},
 };
@@ -32,49 +34,148 @@ pub mod cmp;
 pub mod unix;
 
 /// A node of [`Tree`] that can be created from a [`DirEntry`]. Any filesystem I/O and
-/// relevant system calls are expected to complete after initialization. A `Node` when `Display`ed
-/// uses ANSI colors determined by the file-type and `LS_COLORS`.
+/// relevant system calls are expected to complete after initialization with the exception of
+/// [`Metadata`] which is only queried up front if sizes, `--long`, or colors call for it and is
+/// otherwise fetched on first use. A `Node` when `Display`ed uses ANSI colors determined by the
+/// file-type and `LS_COLORS`.
 ///
 /// [`Tree`]: super::Tree
 pub struct Node {
     dir_entry: DirEntry,
-    metadata: Metadata,
+    metadata: OnceCell<Metadata>,
     file_size: Option<FileSize>,
+    other_file_size: Option<FileSize>,
     style: Option<Style>,
     symlink_target: Option<PathBuf>,
-    inode: Option<Inode>,
+    file_count: Option<usize>,
+    share: Option<f64>,
+    heat: Option<f64>,
+    git_status: Option<git::Status>,
+    summary: Option<FileCount>,
+    delta: Option<Delta>,
+    depth: usize,
+    cyclic: bool,
+    revisit: bool,
+    mount_point: bool,
+    overflow: Option<Overflow>,
+    alias: Option<OsString>,
+    collapsed: usize,
 
     #[cfg(unix)]
     unix_attrs: unix::Attrs,
 }
 
+/// The children of a directory beyond `--limit`, which are rendered as a single trailing line in
+/// their stead. See [`Node::overflow`].
+#[derive(Clone, Copy)]
+pub struct Overflow {
+    /// How many children are hidden.
+    pub entries: usize,
+
+    /// Everything that is hidden, including the contents of hidden directories.
+    pub file_count: FileCount,
+}
+
 impl Node {
     /// Initializes a new [Node].
-    pub const fn new(
+    pub fn new(
         dir_entry: DirEntry,
-        metadata: Metadata,
+        metadata: OnceCell<Metadata>,
         file_size: Option<FileSize>,
+        other_file_size: Option<FileSize>,
         style: Option<Style>,
         symlink_target: Option<PathBuf>,
-        inode: Option<Inode>,
         #[cfg(unix)] unix_attrs: unix::Attrs,
     ) -> Self {
+        let depth = dir_entry.depth();
+
         Self {
             dir_entry,
             metadata,
             file_size,
+            other_file_size,
             style,
             symlink_target,
-            inode,
+            file_count: None,
+            share: None,
+            heat: None,
+            git_status: None,
+            summary: None,
+            delta: None,
+            depth,
+            cyclic: false,
+            revisit: false,
+            mount_point: false,
+            overflow: None,
+            alias: None,
+            collapsed: 0,
             #[cfg(unix)]
             unix_attrs,
         }
     }
 
+    /// Initializes a [Node] for a symlink at `path` whose target is one of its own ancestors. The
+    /// link itself is not followed so that it may be displayed without being descended into.
+    /// `depth` is the depth at which the traversal encountered the link.
+    pub fn cyclic(path: &Path, depth: usize, ctx: &Context) -> Result<Self, Error> {
+        let dir_entry = WalkBuilder::new(path)
+            .follow_links(false)
+            .standard_filters(false)
+            .max_depth(Some(0))
+            .build()
+            .next()
+            .ok_or(Error::MissingRoot)??;
+
+        let mut node = Self::try_from((dir_entry, ctx))?;
+        node.depth = depth;
+        node.mark_cyclic();
+
+        Ok(node)
+    }
+
+    /// Initializes a [Node] for a symlink at `path` whose target doesn't exist, which the
+    /// traversal reports as an error rather than an entry when following links. The link is
+    /// looked up from its parent as the walker would otherwise try to read its target.
+    pub fn broken(path: &Path, depth: usize, ctx: &Context) -> Result<Self, Error> {
+        let parent = path.parent().ok_or(Error::MissingRoot)?;
+
+        let dir_entry = WalkBuilder::new(parent)
+            .follow_links(false)
+            .standard_filters(false)
+            .max_depth(Some(1))
+            .build()
+            .filter_map(Result::ok)
+            .find(|dir_entry| dir_entry.depth() == 1 && dir_entry.path() == path)
+            .ok_or(Error::MissingRoot)?;
+
+        let mut node = Self::try_from((dir_entry, ctx))?;
+        node.depth = depth;
+
+        Ok(node)
+    }
+
     /// Returns a reference to `file_name`. If file is a symlink then `file_name` is the name of
     /// the symlink not the target.
     pub fn file_name(&self) -> &OsStr {
-        self.dir_entry.file_name()
+        self.alias
+            .as_deref()
+            .unwrap_or_else(|| self.dir_entry.file_name())
+    }
+
+    /// Sets the name to display in place of the file name, e.g. the name of a symlink that was
+    /// dereferenced in order to traverse its target.
+    pub fn set_alias(&mut self, alias: OsString) {
+        self.alias = Some(alias);
+    }
+
+    /// Gets how many directories above [Node] were merged into it by `--collapse`.
+    pub const fn collapsed(&self) -> usize {
+        self.collapsed
+    }
+
+    /// Sets `collapsed`.
+    pub fn set_collapsed(&mut self, count: usize) {
+        self.collapsed = count;
     }
 
     pub const fn dir_entry(&self) -> &DirEntry {
@@ -82,8 +183,8 @@ impl Node {
     }
 
     /// Get depth level of [Node].
-    pub fn depth(&self) -> usize {
-        self.dir_entry.depth()
+    pub const fn depth(&self) -> usize {
+        self.depth
     }
 
     /// Gets the number of blocks used by the underlying [`DirEntry`]. Returns `None` in the case of
@@ -92,7 +193,7 @@ impl Node {
     pub fn blocks(&self) -> Option<u64> {
         use std::os::unix::fs::MetadataExt;
 
-        let blocks = self.metadata.blocks();
+        let blocks = self.metadata()?.blocks();
 
         if blocks == 0 {
             None
@@ -101,44 +202,63 @@ impl Node {
         }
     }
 
+    /// The size of the underlying [`DirEntry`] itself as reported by its metadata. Unlike
+    /// `file_size` this is never aggregated for directories.
+    pub fn apparent_size(&self) -> u64 {
+        self.metadata().map_or(0, Metadata::len)
+    }
+
     /// Timestamp of when file was last modified.
     pub fn modified(&self) -> Option<SystemTime> {
-        self.metadata.modified().ok()
+        self.metadata()?.modified().ok()
     }
 
     /// Timestamp of when file was created.
     pub fn created(&self) -> Option<SystemTime> {
-        self.metadata.created().ok()
+        self.metadata()?.created().ok()
     }
 
     /// Timestamp of when file was last accessed.
     pub fn accessed(&self) -> Option<SystemTime> {
-        self.metadata.accessed().ok()
+        self.metadata()?.accessed().ok()
+    }
+
+    /// The timestamp of the given kind, as selected by `--time`.
+    #[cfg(unix)]
+    pub fn timestamp(&self, stamp: time::Stamp) -> Option<SystemTime> {
+        match stamp {
+            time::Stamp::Create => self.created(),
+            time::Stamp::Access => self.accessed(),
+            time::Stamp::Mod => self.modified(),
+        }
+    }
+
+    /// Gets the [`Metadata`] of the underlying [`DirEntry`], querying it if it wasn't already.
+    /// Returns `None` if the query fails, e.g. if the entry was removed since it was visited.
+    pub fn metadata(&self) -> Option<&Metadata> {
+        if let Some(metadata) = self.metadata.get() {
+            return Some(metadata);
+        }
+
+        let metadata = self.dir_entry.metadata().ok()?;
+
+        Some(self.metadata.get_or_init(|| metadata))
     }
 
     /// Gets the underlying [Inode] of the entry.
-    pub const fn inode(&self) -> Option<Inode> {
-        self.inode
+    pub fn inode(&self) -> Option<Inode> {
+        self.metadata().and_then(|md| Inode::try_from(md).ok())
     }
 
     /// Returns the underlying `ino` of the [`DirEntry`].
-    #[cfg(unix)]
-    pub const fn ino(&self) -> Option<u64> {
-        if let Some(inode) = self.inode {
-            Some(inode.ino)
-        } else {
-            None
-        }
+    pub fn ino(&self) -> Option<u64> {
+        self.inode().map(|inode| inode.ino)
     }
 
     /// Returns the underlying `nlink` of the [`DirEntry`].
     #[cfg(unix)]
-    pub const fn nlink(&self) -> Option<u64> {
-        if let Some(inode) = self.inode {
-            Some(inode.nlink)
-        } else {
-            None
-        }
+    pub fn nlink(&self) -> Option<u64> {
+        self.inode().map(|inode| inode.nlink)
     }
 
     /// Returns `true` if node is a directory.
@@ -151,6 +271,105 @@ impl Node {
         self.symlink_target.is_some()
     }
 
+    /// A short identifier for the type of file the [Node] represents, e.g. `"directory"`.
+    pub fn file_type_identifier(&self) -> &'static str {
+        if self.is_symlink() {
+            return "symlink";
+        }
+
+        match self.file_type() {
+            Some(ft) if ft.is_dir() => "directory",
+            Some(ft) if ft.is_file() => "file",
+            _ => "other",
+        }
+    }
+
+    /// Whether or not [Node] is a symlink to a directory that is one of its own ancestors, which
+    /// would result in a cycle if followed. Ancestors are compared by their [Inode]; if the inode
+    /// isn't available on the current platform then canonicalized paths are compared instead.
+    pub fn links_to_ancestor(&self) -> bool {
+        if self.cyclic {
+            return true;
+        }
+
+        if !self.is_dir() || !self.dir_entry.path_is_symlink() {
+            return false;
+        }
+
+        let mut ancestors = self.path().ancestors().skip(1);
+
+        if let Some(inode) = self.inode() {
+            return ancestors
+                .filter_map(|ancestor| fs::metadata(ancestor).ok())
+                .filter_map(|md| Inode::try_from(&md).ok())
+                .any(|ancestor_inode| ancestor_inode == inode);
+        }
+
+        let Ok(target) = fs::canonicalize(self.path()) else {
+            return false;
+        };
+
+        ancestors.any(|ancestor| fs::canonicalize(ancestor).is_ok_and(|a| a == target))
+    }
+
+    /// Whether or not [Node] is a symlink that was left unfollowed because it would result in a
+    /// cycle. See [`Node::cyclic`].
+    pub const fn is_cyclic(&self) -> bool {
+        self.cyclic
+    }
+
+    /// Marks [Node] as a symlink that mustn't be followed as it would result in a cycle.
+    pub fn mark_cyclic(&mut self) {
+        self.cyclic = true;
+    }
+
+    /// Whether or not [Node] is a symlink that was left unfollowed because its target is shown
+    /// by way of another path.
+    pub const fn is_revisit(&self) -> bool {
+        self.revisit
+    }
+
+    /// Marks [Node] as a symlink whose contents are left out as its target is shown elsewhere.
+    pub fn mark_revisit(&mut self) {
+        self.revisit = true;
+    }
+
+    /// Whether or not [Node] is a symlink that was left unfollowed, either on account of a cycle
+    /// or because its target is visited elsewhere.
+    pub const fn is_unfollowed(&self) -> bool {
+        self.cyclic || self.revisit
+    }
+
+    /// Gets the hidden siblings that [Node] stands in for if it was the first child of its parent
+    /// to exceed `--limit`. Such a [Node] is rendered as `… N more` rather than as itself.
+    pub const fn overflow(&self) -> Option<Overflow> {
+        self.overflow
+    }
+
+    /// Turns [Node] into a stand-in for its hidden siblings. Annotations of its own are dropped.
+    pub fn set_overflow(&mut self, overflow: Overflow) {
+        self.overflow = Some(overflow);
+        self.summary = None;
+        self.file_count = None;
+        self.alias = None;
+    }
+
+    /// Whether or not [Node] is a directory on a different filesystem than the root that was left
+    /// undescended on account of `--one-file-system`.
+    pub const fn is_mount_point(&self) -> bool {
+        self.mount_point
+    }
+
+    /// Marks [Node] as a directory on a different filesystem than the root.
+    pub fn mark_mount_point(&mut self) {
+        self.mount_point = true;
+    }
+
+    /// Whether or not [Node] is a symlink whose target doesn't exist.
+    pub fn is_broken_link(&self) -> bool {
+        self.dir_entry.path_is_symlink() && fs::metadata(self.path()).is_err()
+    }
+
     /// Path to symlink target.
     pub fn symlink_target_path(&self) -> Option<&Path> {
         self.symlink_target.as_deref()
@@ -166,6 +385,18 @@ impl Node {
         self.dir_entry.file_type()
     }
 
+    /// Returns the file extension as it should appear in the extension column. Directories are
+    /// represented by a placeholder while files without an extension are labeled as such.
+    pub fn extension_label(&self) -> Cow<'_, str> {
+        if self.is_dir() {
+            return Cow::from(styles::PLACEHOLDER);
+        }
+
+        self.path()
+            .extension()
+            .map_or_else(|| Cow::from(styles::NO_EXTENSION), OsStr::to_string_lossy)
+    }
+
     /// Returns the path to the [Node]'s parent, if any.
     pub fn parent_path(&self) -> Option<&Path> {
         self.path().parent()
@@ -187,10 +418,85 @@ impl Node {
         self.file_size = Some(size);
     }
 
+    /// Gets the size in whichever of logical or physical bytes isn't reported by `file_size`.
+    /// Only computed if `--both-sizes` is enabled.
+    pub const fn other_file_size(&self) -> Option<&FileSize> {
+        self.other_file_size.as_ref()
+    }
+
+    /// Sets `other_file_size`.
+    pub fn set_other_file_size(&mut self, size: FileSize) {
+        self.other_file_size = Some(size);
+    }
+
+    /// Gets the total number of non-directory descendants if [Node] is a directory and it was
+    /// computed.
+    pub const fn file_count(&self) -> Option<usize> {
+        self.file_count
+    }
+
+    /// Sets `file_count`.
+    pub fn set_file_count(&mut self, count: usize) {
+        self.file_count = Some(count);
+    }
+
+    /// Gets the fraction of the parent directory's disk usage that belongs to [Node] if it was
+    /// computed.
+    pub const fn share(&self) -> Option<f64> {
+        self.share
+    }
+
+    /// Sets `share`.
+    pub fn set_share(&mut self, share: f64) {
+        self.share = Some(share);
+    }
+
+    /// Gets where [Node] lies on the `--heat` gradient, from 0 to 1, if it was computed relative
+    /// to something other than the largest entry.
+    pub const fn heat(&self) -> Option<f64> {
+        self.heat
+    }
+
+    /// Sets `heat`.
+    pub fn set_heat(&mut self, heat: f64) {
+        self.heat = Some(heat);
+    }
+
+    /// Gets the git status of [Node] if it was computed.
+    pub const fn git_status(&self) -> Option<git::Status> {
+        self.git_status
+    }
+
+    /// Sets `git_status`.
+    pub fn set_git_status(&mut self, status: git::Status) {
+        self.git_status = Some(status);
+    }
+
+    /// Gets the count of everything beneath [Node] if it's a directory that was summarized due to
+    /// `--summarize`.
+    pub const fn summary(&self) -> Option<FileCount> {
+        self.summary
+    }
+
+    /// Sets `summary`.
+    pub fn set_summary(&mut self, summary: FileCount) {
+        self.summary = Some(summary);
+    }
+
+    /// Gets how [Node] changed since the snapshot provided via `--diff` was taken, if it did.
+    pub const fn delta(&self) -> Option<Delta> {
+        self.delta
+    }
+
+    /// Sets `delta`.
+    pub fn set_delta(&mut self, delta: Delta) {
+        self.delta = Some(delta);
+    }
+
     /// Attempts to return an instance of [`FileMode`] for the display of symbolic permissions.
     #[cfg(unix)]
     pub fn mode(&self) -> Result<FileMode, Error> {
-        let permissions = self.metadata.permissions();
+        let permissions = self.metadata().ok_or(Error::MissingMetadata)?.permissions();
         let file_mode = permissions.try_mode_symbolic_notation()?;
         Ok(file_mode)
     }
@@ -236,30 +542,42 @@ impl TryFrom<(DirEntry, &Context)> for Node {
 
         let path = dir_entry.path();
 
-        let link_target = crate::fs::symlink_target(&dir_entry);
+        let file_type = dir_entry.file_type();
 
-        let metadata = dir_entry.metadata()?;
+        // Colors, sizes, and `--long` can't do without metadata so it's queried right away in
+        // their presence which also weeds out entries that can't be queried. Otherwise querying it
+        // is deferred to whatever needs it, which is nothing for a plain listing.
+        let metadata = OnceCell::new();
 
-        let style = get_ls_colors().ok().map(|ls_colors| {
-            ls_colors
-                .style_for_path_with_metadata(path, Some(&metadata))
-                .map_or_else(Style::default, LS_Style::to_ansi_term_style)
-        });
+        if !ctx.suppress_size || ctx.long || styles::get_ls_colors().is_ok() {
+            let _ = metadata.set(dir_entry.metadata()?);
+        }
 
-        let file_type = dir_entry.file_type();
+        let link_target = if ctx.resolve_links && file_type.is_some_and(|ft| ft.is_dir()) {
+            crate::fs::symlink_target(&dir_entry)
+                .and_then(|target| fs::canonicalize(path).ok().or(Some(target)))
+        } else {
+            crate::fs::symlink_target(&dir_entry)
+        };
 
-        let file_size = match file_type {
-            Some(ref ft)
+        let style = metadata
+            .get()
+            .and_then(|md| styles::get_file_style(path, md));
+
+        let file_size = match (file_type, metadata.get()) {
+            (Some(ref ft), Some(metadata))
                 if !ctx.suppress_size && (ft.is_file() || ft.is_symlink() && !ctx.follow) =>
             {
                 match ctx.disk_usage {
                     DiskUsage::Logical => {
-                        let metric = byte::Metric::init_logical(&metadata, ctx.unit, ctx.human);
+                        let metric = byte::Metric::init_logical(metadata, ctx.unit, ctx.human)
+                            .with_grouping(ctx.group_digits);
                         Some(FileSize::Byte(metric))
                     },
                     DiskUsage::Physical => {
                         let metric =
-                            byte::Metric::init_physical(path, &metadata, ctx.unit, ctx.human);
+                            byte::Metric::init_physical(path, metadata, ctx.unit, ctx.human)
+                                .with_grouping(ctx.group_digits);
                         Some(FileSize::Byte(metric))
                     },
                     DiskUsage::Line => {
@@ -270,10 +588,11 @@ impl TryFrom<(DirEntry, &Context)> for Node {
                         let metric = word_count::Metric::init(path);
                         metric.map(FileSize::Word)
                     },
+                    DiskUsage::File => Some(FileSize::File(file_count::Metric::init())),
 
                     #[cfg(unix)]
                     DiskUsage::Block => {
-                        let metric = block::Metric::init(&metadata);
+                        let metric = block::Metric::init(metadata);
                         Some(FileSize::Block(metric))
                     },
                 }
@@ -281,22 +600,33 @@ impl TryFrom<(DirEntry, &Context)> for Node {
             _ => None,
         };
 
-        let inode = Inode::try_from(&metadata).ok();
+        let other_file_size = if let (Some(FileSize::Byte(_)), Some(metadata)) =
+            (&file_size, metadata.get().filter(|_| ctx.both_sizes))
+        {
+            let metric = if matches!(ctx.disk_usage, DiskUsage::Logical) {
+                byte::Metric::init_physical(path, metadata, ctx.unit, ctx.human)
+            } else {
+                byte::Metric::init_logical(metadata, ctx.unit, ctx.human)
+            };
 
-        #[cfg(unix)]
-        let unix_attrs = if ctx.long {
-            unix::Attrs::from((&metadata, &dir_entry))
+            Some(FileSize::Byte(metric.with_grouping(ctx.group_digits)))
         } else {
-            unix::Attrs::default()
+            None
+        };
+
+        #[cfg(unix)]
+        let unix_attrs = match metadata.get() {
+            Some(metadata) if ctx.long => unix::Attrs::from((metadata, &dir_entry)),
+            _ => unix::Attrs::default(),
         };
 
         Ok(Self::new(
             dir_entry,
             metadata,
             file_size,
+            other_file_size,
             style,
             link_target,
-            inode,
             #[cfg(unix)]
             unix_attrs,
         ))
@@ -35,11 +35,9 @@ impl Attrs {
 impl From<(&Metadata, &DirEntry)> for Attrs {
     fn from((md, entry): (&Metadata, &DirEntry)) -> Self {
         let has_xattrs = entry.has_xattrs();
+        let owner = md.owner_or_uid();
+        let group = md.group_or_gid();
 
-        if let Ok((o, g)) = md.try_get_owner_and_group() {
-            return Self::new(has_xattrs, Some(o), Some(g));
-        }
-
-        Self::new(has_xattrs, None, None)
+        Self::new(has_xattrs, Some(owner), Some(group))
     }
 }
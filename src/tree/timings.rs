@@ -0,0 +1,77 @@
+use crate::{progress::Tally, utils};
+use std::{
+    fmt::{self, Display},
+    ops::AddAssign,
+    time::Duration,
+};
+
+/// How long it took to build and render [`Tree`]s along with how many entries were visited and
+/// how many errors were encountered in the process, reported on stderr with `--timings`.
+///
+/// `traversal` spans from the start of the walk until the last entry was received whereas
+/// `aggregation` is everything that comes after, i.e. assembling the [`Tree`], computing sizes,
+/// sorting, and filtering.
+///
+/// [`Tree`]: super::Tree
+#[derive(Clone, Copy, Default)]
+pub struct Timings {
+    pub traversal: Duration,
+    pub aggregation: Duration,
+    pub rendering: Duration,
+    pub entries: u64,
+    pub errors: u64,
+}
+
+impl Timings {
+    /// Initializes [Timings] for a single [`super::Tree`] that took `total` to build, `traversal`
+    /// of which was spent walking the file-system. Entries and errors are read from `tally`.
+    pub fn new(traversal: Duration, total: Duration, tally: Option<&Tally>) -> Self {
+        Self {
+            traversal,
+            aggregation: total.saturating_sub(traversal),
+            rendering: Duration::ZERO,
+            entries: tally.map_or(0, Tally::entries),
+            errors: tally.map_or(0, Tally::errors),
+        }
+    }
+
+    /// Entries visited per second of traversal.
+    fn throughput(&self) -> u64 {
+        let secs = self.traversal.as_secs_f64();
+
+        if secs > 0.0 {
+            (self.entries as f64 / secs) as u64
+        } else {
+            0
+        }
+    }
+}
+
+/// Accumulates [Timings] across multiple roots.
+impl AddAssign for Timings {
+    fn add_assign(&mut self, rhs: Self) {
+        self.traversal += rhs.traversal;
+        self.aggregation += rhs.aggregation;
+        self.rendering += rhs.rendering;
+        self.entries += rhs.entries;
+        self.errors += rhs.errors;
+    }
+}
+
+impl Display for Timings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = self.traversal + self.aggregation + self.rendering;
+
+        writeln!(f, "traversal    {:.3} s", self.traversal.as_secs_f64())?;
+        writeln!(f, "aggregation  {:.3} s", self.aggregation.as_secs_f64())?;
+        writeln!(f, "rendering    {:.3} s", self.rendering.as_secs_f64())?;
+        writeln!(f, "total        {:.3} s", total.as_secs_f64())?;
+        writeln!(
+            f,
+            "entries      {} ({}/s)",
+            utils::group_digits(self.entries),
+            utils::group_digits(self.throughput())
+        )?;
+        write!(f, "errors       {}", utils::group_digits(self.errors))
+    }
+}
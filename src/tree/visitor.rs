@@ -1,6 +1,12 @@
-use std::sync::mpsc::Sender;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{mpsc::Sender, Arc},
+};
 
-use crate::{tree::node::Node, Context};
+use crate::{
+    disk_usage::file_size::FileSize, fs::inode::Inode, progress::Tally, tree::node::Node, Context,
+};
 use ignore::{DirEntry, Error as IgnoreError, ParallelVisitor, ParallelVisitorBuilder, WalkState};
 
 pub enum TraversalState {
@@ -8,25 +14,61 @@ pub enum TraversalState {
     Done,
 }
 
+/// Visits entries on one of the traversal's worker threads. If a progress indicator or
+/// `--timings` is active, `tally` is shared between all workers and keeps track of what has been
+/// visited so far. `root_dev` is the device of `root` if mount points are to be shown rather than
+/// skipped by the walker.
 pub struct Branch<'a> {
     ctx: &'a Context,
     tx: Sender<TraversalState>,
+    tally: Option<Arc<Tally>>,
+    root: PathBuf,
+    root_dev: Option<u64>,
 }
 
 pub struct BranchVisitorBuilder<'a> {
     ctx: &'a Context,
     tx: Sender<TraversalState>,
+    tally: Option<Arc<Tally>>,
+    root: PathBuf,
+    root_dev: Option<u64>,
 }
 
 impl<'a> BranchVisitorBuilder<'a> {
-    pub fn new(ctx: &'a Context, tx: Sender<TraversalState>) -> Self {
-        Self { ctx, tx }
+    pub fn new(ctx: &'a Context, tx: Sender<TraversalState>, tally: Option<Arc<Tally>>) -> Self {
+        let root = ctx.dir_canonical();
+
+        let root_dev = (ctx.same_fs && ctx.show_mounts)
+            .then(|| fs::metadata(&root).ok())
+            .flatten()
+            .and_then(|md| Inode::try_from(&md).ok())
+            .map(|inode| inode.dev);
+
+        Self {
+            ctx,
+            tx,
+            tally,
+            root,
+            root_dev,
+        }
     }
 }
 
 impl<'a> Branch<'a> {
-    pub fn new(ctx: &'a Context, tx: Sender<TraversalState>) -> Self {
-        Self { ctx, tx }
+    pub const fn new(
+        ctx: &'a Context,
+        tx: Sender<TraversalState>,
+        tally: Option<Arc<Tally>>,
+        root: PathBuf,
+        root_dev: Option<u64>,
+    ) -> Self {
+        Self {
+            ctx,
+            tx,
+            tally,
+            root,
+            root_dev,
+        }
     }
 }
 
@@ -36,27 +78,148 @@ impl From<Node> for TraversalState {
     }
 }
 
+impl Branch<'_> {
+    /// Informs the progress indicator or `--timings`, if either is active, of `node`. Only the
+    /// sizes of files measured in bytes are accumulated.
+    fn record(&self, node: &Node) {
+        let Some(ref tally) = self.tally else {
+            return;
+        };
+
+        let bytes = node
+            .file_size()
+            .filter(|_| self.ctx.byte_metric() && !node.is_dir())
+            .map_or(0, FileSize::value);
+
+        tally.record(node.path(), bytes);
+    }
+
+    /// Informs the progress indicator or `--timings`, if either is active, of an error.
+    fn record_error(&self) {
+        if let Some(ref tally) = self.tally {
+            tally.record_error();
+        }
+    }
+
+    /// Whether or not `node` is a directory on a different filesystem than the root.
+    fn crosses_mount(&self, node: &Node) -> bool {
+        let Some(root_dev) = self.root_dev else {
+            return false;
+        };
+
+        node.is_dir() && node.inode().is_some_and(|inode| inode.dev != root_dev)
+    }
+
+    /// Symlink cycles and broken symlinks detected by the walker when following links are
+    /// reported as errors rather than entries; the offending link is still sent so that it is
+    /// displayed, but it is never descended into. Only broken symlinks are of interest with
+    /// `--broken-only` and hidden ones are left out unless `--hidden` is enabled as they aren't
+    /// subject to the walker's filters. All other errors are skipped.
+    fn visit_err(&self, err: IgnoreError) -> WalkState {
+        let node = match err {
+            IgnoreError::WithDepth { depth, err } => match *err {
+                IgnoreError::Loop { ref child, .. } if !self.ctx.broken_only => {
+                    Node::cyclic(child, depth, self.ctx)
+                },
+                IgnoreError::Loop { .. } => return WalkState::Skip,
+                _ => {
+                    self.record_error();
+                    return WalkState::Skip;
+                },
+            },
+
+            // Reported without a depth so it's derived from the path.
+            IgnoreError::WithPath { ref path, .. } if is_broken_link(path) => {
+                let hidden = path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+
+                if hidden && !self.ctx.hidden {
+                    return WalkState::Skip;
+                }
+
+                let depth = path
+                    .strip_prefix(&self.root)
+                    .map_or(0, |rel| rel.components().count());
+
+                Node::broken(path, depth, self.ctx)
+            },
+
+            _ => {
+                self.record_error();
+                return WalkState::Skip;
+            },
+        };
+
+        let Ok(node) = node else {
+            self.record_error();
+            return WalkState::Skip;
+        };
+
+        if self.tx.send(TraversalState::from(node)).is_err() {
+            return WalkState::Quit;
+        }
+
+        WalkState::Skip
+    }
+}
+
 impl ParallelVisitor for Branch<'_> {
+    #[allow(clippy::single_match_else)]
     fn visit(&mut self, entry: Result<DirEntry, IgnoreError>) -> WalkState {
-        let Ok(dir_entry) = entry else {
-            return WalkState::Skip;
+        let dir_entry = match entry {
+            Ok(dir_entry) => dir_entry,
+            Err(err) => return self.visit_err(err),
         };
 
         match Node::try_from((dir_entry, self.ctx)) {
-            Ok(node) => {
+            Ok(mut node) => {
+                self.record(&node);
+
+                // Don't descend into symlinks that point back to an ancestor when following links.
+                if self.ctx.follow && node.links_to_ancestor() {
+                    node.mark_cyclic();
+                }
+
+                // `root_dev` is only known with `--show-mounts`.
+                if self.crosses_mount(&node) {
+                    node.mark_mount_point();
+                }
+
+                let skip = node.is_unfollowed() || node.is_mount_point();
+
                 if self.tx.send(TraversalState::from(node)).is_err() {
                     return WalkState::Quit;
                 }
-                WalkState::Continue
+
+                if skip {
+                    WalkState::Skip
+                } else {
+                    WalkState::Continue
+                }
+            },
+            _ => {
+                self.record_error();
+                WalkState::Skip
             },
-            _ => WalkState::Skip,
         }
     }
 }
 
+/// Whether or not `path` is a symlink whose target doesn't exist.
+fn is_broken_link(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|md| md.is_symlink()) && fs::metadata(path).is_err()
+}
+
 impl<'s> ParallelVisitorBuilder<'s> for BranchVisitorBuilder<'s> {
     fn build(&mut self) -> Box<dyn ParallelVisitor + 's> {
-        let visitor = Branch::new(self.ctx, self.tx.clone());
+        let visitor = Branch::new(
+            self.ctx,
+            self.tx.clone(),
+            self.tally.clone(),
+            self.root.clone(),
+            self.root_dev,
+        );
         Box::new(visitor)
     }
 }
@@ -1,6 +1,6 @@
 #![allow(clippy::module_name_repetitions)]
 use crossterm::{cursor, ExecutableCommand};
-use std::io::{stdin, stdout, IsTerminal};
+use std::io::{stderr, stdin, stdout, IsTerminal};
 
 #[cfg(windows)]
 mod windows;
@@ -19,9 +19,20 @@ pub fn stdout_is_tty() -> bool {
     stdout().is_terminal()
 }
 
-/// Restore terminal settings.
+/// Is stderr connected to a tty? The progress indicator is only shown if so.
+pub fn stderr_is_tty() -> bool {
+    stderr().is_terminal()
+}
+
+/// Restore terminal settings, namely the cursor hidden by the progress indicator which is written
+/// to stderr. Nothing is written if stderr isn't a tty so that output that is redirected isn't
+/// polluted with escape sequences.
 pub fn restore_tty() {
-    stdout()
+    if !stderr_is_tty() {
+        return;
+    }
+
+    stderr()
         .execute(cursor::Show)
         .expect("Failed to restore cursor");
 }
@@ -27,3 +27,26 @@ pub const fn num_integral(value: u64) -> usize {
     }
     value.ilog10() as usize + 1
 }
+
+/// Separates every three digits of `value` with a comma e.g. `1,492`.
+pub fn group_digits(value: u64) -> String {
+    let digits = value.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+
+    grouped
+}
+
+#[test]
+fn test_group_digits() {
+    assert_eq!(group_digits(0), "0");
+    assert_eq!(group_digits(128), "128");
+    assert_eq!(group_digits(1492), "1,492");
+    assert_eq!(group_digits(1_000_000), "1,000,000");
+}
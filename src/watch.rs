@@ -0,0 +1,45 @@
+use crate::{context::Context, tree::Tree};
+use crossterm::{
+    cursor,
+    terminal::{self, ClearType},
+    QueueableCommand,
+};
+use std::{
+    error::Error,
+    io::{stdout, Write},
+    thread,
+    time::Duration,
+};
+
+/// How long to wait after a traversal before starting the next one.
+const INTERVAL: Duration = Duration::from_secs(1);
+
+/// Traverses the directory over and over, redrawing the screen whenever the output differs from
+/// what was last drawn so that unchanged trees don't flicker. Errors such as the root having been
+/// removed are shown in place of the tree rather than ending the session. Runs until interrupted.
+pub fn run(ctx: &Context) -> Result<(), Box<dyn Error>> {
+    let mut out = stdout();
+    let mut last_drawn = None;
+
+    loop {
+        let mut output = vec![];
+
+        match Tree::try_init(ctx.clone(), None) {
+            Ok((tree, ctx)) => tree.render(&mut output, &ctx)?,
+            Err(err) => writeln!(output, "{err}")?,
+        }
+
+        if last_drawn.as_ref() != Some(&output) {
+            out.queue(terminal::Clear(ClearType::All))?
+                .queue(terminal::Clear(ClearType::Purge))?
+                .queue(cursor::MoveTo(0, 0))?;
+
+            out.write_all(&output)?;
+            out.flush()?;
+
+            last_drawn = Some(output);
+        }
+
+        thread::sleep(INTERVAL);
+    }
+}
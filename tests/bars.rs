@@ -0,0 +1,69 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn bars() {
+    assert_eq!(
+        utils::run_cmd(&["--bars", "tests/data"]),
+        indoc!(
+            "143 B [##########]    ┌─ cassildas_song.md
+             143 B [#---------] ┌─ the_yellow_king
+             100 B [#---------] ├─ nylarlathotep.txt
+             161 B [#---------] ├─ nemesis.txt
+              83 B [#---------] ├─ necronomicon.txt
+             446 B [##########] │  ┌─ lipsum.txt
+             446 B [####------] ├─ lipsum
+             308 B [##########] │  ┌─ polaris.txt
+             308 B [##--------] ├─ dream_cycle
+            1241 B [----------] data
+
+            3 directories, 6 files"
+        )
+    )
+}
+
+#[test]
+fn bars_size_position_right() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--bars",
+            "--layout",
+            "flat",
+            "--size-position",
+            "right",
+            "--level",
+            "1",
+            "tests/data"
+        ]),
+        indoc!(
+            "[#---------]   the_yellow_king (143 B)
+            [#---------]   nylarlathotep.txt (100 B)
+            [#---------]   nemesis.txt (161 B)
+            [#---------]   necronomicon.txt (83 B)
+            [####------]   lipsum (446 B)
+            [##--------]   dream_cycle (308 B)
+            [----------]   data (1241 B)
+
+            3 directories, 6 files"
+        )
+    )
+}
+
+#[test]
+fn bars_width() {
+    assert_eq!(
+        utils::run_cmd(&["--bar", "--bar-width", "4", "--level", "1", "tests/data"]),
+        indoc!(
+            "143 B [----] ┌─ the_yellow_king
+             100 B [----] ├─ nylarlathotep.txt
+             161 B [#---] ├─ nemesis.txt
+              83 B [----] ├─ necronomicon.txt
+             446 B [#---] ├─ lipsum
+             308 B [#---] ├─ dream_cycle
+            1241 B [----] data
+
+            3 directories, 6 files"
+        )
+    )
+}
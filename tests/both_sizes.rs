@@ -0,0 +1,31 @@
+use indoc::indoc;
+use std::{error::Error, fs::File};
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn both_sizes() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path();
+
+    // Sparse file which doesn't have any blocks allocated to it.
+    File::create(root.join("sparse"))?.set_len(1024 * 1024)?;
+
+    let root_name = root.file_name().unwrap().to_string_lossy();
+
+    let out =
+        utils::run_cmd(&["--both-sizes", &root.to_string_lossy()]).replace(&*root_name, "root");
+
+    assert_eq!(
+        out,
+        indoc!(
+            "1048576 B 0 B ┌─ sparse
+            1048576 B   - root
+
+            1 file"
+        )
+    );
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use indoc::indoc;
+use std::{error::Error, fs};
+use tempfile::TempDir;
+
+mod utils;
+
+/// Sets up a linear chain of directories that only contain a single directory, ending in a
+/// directory with a file, next to a directory that contains a file and a directory.
+fn setup() -> Result<TempDir, Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path();
+
+    let chain = root
+        .join("src")
+        .join("main")
+        .join("java")
+        .join("com")
+        .join("example");
+
+    fs::create_dir_all(&chain)?;
+    fs::create_dir_all(root.join("docs").join("img"))?;
+    fs::write(chain.join("App.java"), "class App {}\n")?;
+    fs::write(root.join("docs").join("index.md"), "# docs\n")?;
+    fs::write(root.join("docs").join("img").join("logo.svg"), "<svg/>\n")?;
+
+    Ok(tmp)
+}
+
+fn run(tmp: &TempDir, args: &[&str]) -> String {
+    let root = tmp.path().to_string_lossy();
+    let root_name = tmp.path().file_name().unwrap().to_string_lossy();
+
+    let mut all_args = args.to_vec();
+    all_args.push(&root);
+
+    utils::run_cmd(&all_args).replace(root_name.as_ref(), "root")
+}
+
+#[test]
+fn collapse() -> Result<(), Box<dyn Error>> {
+    let tmp = setup()?;
+
+    assert_eq!(
+        run(&tmp, &["--collapse"]),
+        indoc!(
+            "13 B    ┌─ App.java
+            13 B ┌─ src/main/java/com/example
+             7 B │  ┌─ index.md
+             7 B │  │  ┌─ logo.svg
+             7 B │  ├─ img
+            14 B ├─ docs
+            27 B root
+
+            7 directories, 3 files"
+        ),
+        "Only the chain of directories with a single directory should be collapsed"
+    );
+
+    assert_eq!(
+        run(&tmp, &["--collapse", "--level", "4"]),
+        indoc!(
+            "7 B │  ┌─ index.md
+             7 B │  │  ┌─ logo.svg
+             7 B │  ├─ img
+            14 B ├─ docs
+            27 B root
+
+            7 directories, 3 files"
+        ),
+        "A collapsed chain should be as deep as its deepest directory"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+mod utils;
+
+#[test]
+fn completions_zsh() {
+    let out = utils::run_cmd(&["--completions", "zsh"]);
+
+    assert!(
+        out.contains(
+            "::dirs -- Directories to traverse, each rendered as its own tree; defaults to current working directory:_files -/"
+        ),
+        "Expected directory completions for positional argument"
+    );
+
+    assert!(
+        out.contains("(name\\:\"Sort entries by file name in lexicographical order\""),
+        "Expected enum variants to be completed for '--sort'"
+    );
+}
+
+#[test]
+fn completions_bash() {
+    let out = utils::run_cmd(&["--completions", "bash"]);
+
+    assert!(
+        out.contains(r#"--sort)
+                    COMPREPLY=($(compgen -W "name rname size rsize access raccess create rcreate mod rmod" -- "${cur}"))"#),
+        "Expected enum variants to be completed for '--sort'"
+    );
+
+    assert!(
+        out.contains(
+            r#"--unit)
+                    COMPREPLY=($(compgen -W "bin si bytes" -- "${cur}"))"#
+        ),
+        "Expected enum variants to be completed for '--unit'"
+    );
+}
@@ -0,0 +1,282 @@
+use indoc::indoc;
+use std::{error::Error, io::Write};
+use tempfile::{Builder, NamedTempFile};
+
+mod utils;
+
+fn toml_config(contents: &str) -> Result<NamedTempFile, Box<dyn Error>> {
+    let mut config_file = Builder::new()
+        .prefix(".erdtree")
+        .suffix(".toml")
+        .tempfile()?;
+
+    config_file.write_all(contents.as_bytes())?;
+
+    Ok(config_file)
+}
+
+#[test]
+fn config_only() -> Result<(), Box<dyn Error>> {
+    let config = toml_config(
+        r#"
+        disk_usage = "logical"
+        sort = "name"
+        level = 1
+        "#,
+    )?;
+
+    assert_eq!(
+        utils::run_cmd_with_config(&["tests/data"], config.path()),
+        indoc!(
+            "143 B ┌─ the_yellow_king
+             100 B ├─ nylarlathotep.txt
+             161 B ├─ nemesis.txt
+              83 B ├─ necronomicon.txt
+             446 B ├─ lipsum
+             308 B ├─ dream_cycle
+            1241 B data
+
+            3 directories, 6 files"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn cli_only() -> Result<(), Box<dyn Error>> {
+    let config = toml_config("")?;
+
+    assert_eq!(
+        utils::run_cmd_with_config(
+            &[
+                "--disk-usage",
+                "logical",
+                "--sort",
+                "name",
+                "--level",
+                "1",
+                "tests/data"
+            ],
+            config.path()
+        ),
+        indoc!(
+            "143 B ┌─ the_yellow_king
+             100 B ├─ nylarlathotep.txt
+             161 B ├─ nemesis.txt
+              83 B ├─ necronomicon.txt
+             446 B ├─ lipsum
+             308 B ├─ dream_cycle
+            1241 B data
+
+            3 directories, 6 files"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn cli_overrides_config_value() -> Result<(), Box<dyn Error>> {
+    let config = toml_config(
+        r#"
+        disk_usage = "logical"
+        sort = "size"
+        level = 2
+        "#,
+    )?;
+
+    assert_eq!(
+        utils::run_cmd_with_config(&["--sort", "name", "-L", "1", "tests/data"], config.path()),
+        indoc!(
+            "143 B ┌─ the_yellow_king
+             100 B ├─ nylarlathotep.txt
+             161 B ├─ nemesis.txt
+              83 B ├─ necronomicon.txt
+             446 B ├─ lipsum
+             308 B ├─ dream_cycle
+            1241 B data
+
+            3 directories, 6 files"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn dirs_last_with_config() -> Result<(), Box<dyn Error>> {
+    let config = toml_config(
+        r#"
+        disk_usage = "logical"
+        sort = "name"
+        level = 1
+        "#,
+    )?;
+
+    assert_eq!(
+        utils::run_cmd_with_config(&["--dirs-last", "tests/data"], config.path()),
+        indoc!(
+            "100 B ┌─ nylarlathotep.txt
+             161 B ├─ nemesis.txt
+              83 B ├─ necronomicon.txt
+             143 B ├─ the_yellow_king
+             446 B ├─ lipsum
+             308 B ├─ dream_cycle
+            1241 B data
+
+            3 directories, 6 files"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn cli_and_config_flags() -> Result<(), Box<dyn Error>> {
+    let config = toml_config(
+        r#"
+        disk_usage = "logical"
+        sort = "name"
+        dirs_only = true
+        prune = false
+        "#,
+    )?;
+
+    assert_eq!(
+        utils::run_cmd_with_config(&["tests/data"], config.path()),
+        indoc!(
+            "143 B ┌─ the_yellow_king
+             446 B ├─ lipsum
+             308 B ├─ dream_cycle
+            1241 B data
+
+            3 directories"
+        )
+    );
+
+    assert_eq!(
+        utils::run_cmd_with_config(
+            &["--prune", "--pattern", "polaris", "tests/data"],
+            config.path()
+        ),
+        indoc!(
+            "308 B ┌─ dream_cycle
+            308 B data
+
+            1 directory"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn no_config() -> Result<(), Box<dyn Error>> {
+    let config = toml_config(
+        r#"
+        disk_usage = "physical"
+        level = 1
+        dirs_only = true
+        "#,
+    )?;
+
+    assert_eq!(
+        utils::run_cmd_with_config(
+            &[
+                "--no-config",
+                "--disk-usage",
+                "logical",
+                "--sort",
+                "name",
+                "tests/data"
+            ],
+            config.path()
+        ),
+        indoc!(
+            "143 B    ┌─ cassildas_song.md
+             143 B ┌─ the_yellow_king
+             100 B ├─ nylarlathotep.txt
+             161 B ├─ nemesis.txt
+              83 B ├─ necronomicon.txt
+             446 B │  ┌─ lipsum.txt
+             446 B ├─ lipsum
+             308 B │  ┌─ polaris.txt
+             308 B ├─ dream_cycle
+            1241 B data
+
+            3 directories, 6 files"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn config_paths() -> Result<(), Box<dyn Error>> {
+    let config = toml_config(
+        r#"
+        disk_usage = "logical"
+        sort = "name"
+        layout = "flat"
+        paths = "absolute"
+        level = 1
+        "#,
+    )?;
+
+    let data_dir = std::fs::canonicalize("tests/data")?;
+
+    assert_eq!(
+        utils::run_cmd_with_config(&["tests/data"], config.path()),
+        indoc::formatdoc!(
+            "143 B   {data}/the_yellow_king
+             100 B   {data}/nylarlathotep.txt
+             161 B   {data}/nemesis.txt
+              83 B   {data}/necronomicon.txt
+             446 B   {data}/lipsum
+             308 B   {data}/dream_cycle
+            1241 B   {data}
+
+            3 directories, 6 files",
+            data = data_dir.display()
+        )
+    );
+
+    assert_eq!(
+        utils::run_cmd_with_config(&["--paths", "relative", "tests/data"], config.path()),
+        indoc!(
+            "143 B   the_yellow_king
+             100 B   nylarlathotep.txt
+             161 B   nemesis.txt
+              83 B   necronomicon.txt
+             446 B   lipsum
+             308 B   dream_cycle
+            1241 B   data
+
+            3 directories, 6 files"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn print_command() -> Result<(), Box<dyn Error>> {
+    let config = toml_config(
+        r#"
+        disk_usage = "logical"
+        sort = "name"
+        level = 1
+        "#,
+    )?;
+
+    let out = utils::run_cmd_with_config(&["--print-command", "tests/data"], config.path());
+    let header = out.lines().next().unwrap();
+
+    // `--threads` may or may not be included depending on the default for the machine.
+    assert!(header.starts_with("# erd --disk-usage logical --level 1 --sort name"));
+    assert!(header.ends_with(" tests/data"));
+    assert!(!header.contains("--print-command"));
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn count() {
+    assert_eq!(
+        utils::run_cmd(&["--count", "tests/data"]),
+        indoc!(
+            "1    ┌─ cassildas_song.md
+            1 ┌─ the_yellow_king
+            1 ├─ nylarlathotep.txt
+            1 ├─ nemesis.txt
+            1 ├─ necronomicon.txt
+            1 │  ┌─ lipsum.txt
+            1 ├─ lipsum
+            1 │  ┌─ polaris.txt
+            1 ├─ dream_cycle
+            6 data
+
+            3 directories, 6 files"
+        )
+    )
+}
+
+#[test]
+fn count_pattern() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--count",
+            "--glob",
+            "--pattern",
+            "*.txt",
+            "--prune",
+            "tests/data"
+        ]),
+        indoc!(
+            "1 ┌─ nylarlathotep.txt
+            1 ├─ nemesis.txt
+            1 ├─ necronomicon.txt
+            1 │  ┌─ lipsum.txt
+            1 ├─ lipsum
+            1 │  ┌─ polaris.txt
+            1 ├─ dream_cycle
+            5 data
+
+            2 directories, 5 files"
+        )
+    )
+}
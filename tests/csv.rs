@@ -0,0 +1,69 @@
+use std::{error::Error, fs};
+use tempfile::TempDir;
+
+mod utils;
+
+/// Drops the `ino` column which varies from system to system and makes paths relative to `root`.
+fn normalize(out: &str, root: &str) -> Vec<String> {
+    out.lines()
+        .map(|line| {
+            let mut fields = line.rsplitn(3, ',');
+            let nlink = fields.next().unwrap();
+            let _ino = fields.next();
+            let rest = fields.next().unwrap();
+
+            format!("{},{nlink}", rest.replace(root, "root"))
+        })
+        .collect()
+}
+
+#[test]
+fn csv() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().canonicalize()?;
+
+    fs::create_dir(root.join("dir"))?;
+    fs::write(root.join("dir").join("a,b.txt"), "erdtree\n")?;
+    fs::write(root.join("say \"hi\".txt"), "hi\n")?;
+
+    let root = root.to_string_lossy();
+    let out = utils::run_cmd(&["--csv", &root]);
+
+    assert_eq!(
+        normalize(&out, &root),
+        [
+            "depth,path,file_type,size,nlink",
+            "0,root,directory,11,3",
+            "1,root/dir,directory,8,2",
+            "2,\"root/dir/a,b.txt\",file,8,1",
+            "1,\"root/say \"\"hi\"\".txt\",file,3,1",
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn csv_no_header_level() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().canonicalize()?;
+
+    fs::create_dir(root.join("dir"))?;
+    fs::write(root.join("dir").join("a.txt"), "erdtree\n")?;
+
+    let root = root.to_string_lossy();
+    let out = utils::run_cmd(&["--csv", "--no-header", "--level", "1", &root]);
+
+    assert_eq!(
+        normalize(&out, &root),
+        ["0,root,directory,8,3", "1,root/dir,directory,8,2"]
+    );
+
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn csv_conflicts_with_json() {
+    utils::run_cmd(&["--csv", "--json", "tests/data"]);
+}
@@ -0,0 +1,42 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn dir_stats() {
+    assert_eq!(
+        utils::run_cmd(&["--dir-stats", "--dirs-only", "tests/data"]),
+        indoc!(
+            "143 B ┌─ the_yellow_king (1 file, 143 B)
+ 446 B ├─ lipsum (1 file, 446 B)
+ 308 B ├─ dream_cycle (1 file, 308 B)
+1241 B data (6 files, 1241 B)
+
+3 directories"
+        )
+    )
+}
+
+#[test]
+fn dir_stats_with_pattern() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--dir-stats",
+            "--pattern",
+            r"\.txt$",
+            "--level",
+            "1",
+            "tests/data"
+        ]),
+        indoc!(
+            "100 B ┌─ nylarlathotep.txt
+ 161 B ├─ nemesis.txt
+  83 B ├─ necronomicon.txt
+ 446 B ├─ lipsum (1 file, 446 B)
+ 308 B ├─ dream_cycle (1 file, 308 B)
+1098 B data (5 files, 1098 B)
+
+2 directories, 5 files"
+        )
+    )
+}
@@ -0,0 +1,155 @@
+use indoc::indoc;
+use std::{error::Error, fs, io::Write};
+use tempfile::{NamedTempFile, TempDir};
+
+mod utils;
+
+#[test]
+fn exclude_pattern() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--exclude-pattern",
+            "lipsum",
+            "--exclude-pattern",
+            r"^ne",
+            "tests/data"
+        ]),
+        indoc!(
+            "143 B    ┌─ cassildas_song.md
+            143 B ┌─ the_yellow_king
+            100 B ├─ nylarlathotep.txt
+                - ├─ lipsum
+            308 B │  ┌─ polaris.txt
+            308 B ├─ dream_cycle
+            551 B data
+
+            3 directories, 3 files"
+        )
+    )
+}
+
+#[test]
+fn exclude_pattern_file() -> Result<(), Box<dyn Error>> {
+    let mut pattern_file = NamedTempFile::new()?;
+
+    writeln!(pattern_file, "# comments and blank lines are ignored")?;
+    writeln!(pattern_file, "lipsum")?;
+    writeln!(pattern_file)?;
+    writeln!(pattern_file, "nemesis")?;
+
+    let pattern_file_path = pattern_file.path().to_string_lossy().into_owned();
+
+    let out = utils::run_cmd(&[
+        "--exclude-pattern-file",
+        &pattern_file_path,
+        "--exclude-pattern",
+        "polaris",
+        "tests/data",
+    ]);
+
+    assert_eq!(
+        out,
+        indoc!(
+            "143 B    ┌─ cassildas_song.md
+            143 B ┌─ the_yellow_king
+            100 B ├─ nylarlathotep.txt
+             83 B ├─ necronomicon.txt
+                - ├─ lipsum
+                - ├─ dream_cycle
+            326 B data
+
+            3 directories, 3 files"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn exclude_glob() {
+    assert_eq!(
+        utils::run_cmd(&["--exclude", "lipsum", "--exclude", "*.md", "tests/data"]),
+        indoc!(
+            "- ┌─ the_yellow_king
+            100 B ├─ nylarlathotep.txt
+            161 B ├─ nemesis.txt
+             83 B ├─ necronomicon.txt
+            308 B │  ┌─ polaris.txt
+            308 B ├─ dream_cycle
+            652 B data
+
+            2 directories, 4 files"
+        )
+    )
+}
+
+#[test]
+fn exclude_dir() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path();
+
+    fs::create_dir(root.join("build"))?;
+    fs::create_dir(root.join("src"))?;
+    fs::write(root.join("build").join("out.o"), "a\n")?;
+    fs::write(root.join("src").join("build"), "bb\n")?;
+    fs::write(root.join("src").join("main.rs"), "c\n")?;
+
+    let root_name = root.file_name().unwrap().to_string_lossy();
+
+    let out = utils::run_cmd(&["--exclude-dir", "build", &root.to_string_lossy()])
+        .replace(&*root_name, "root");
+
+    assert_eq!(
+        out,
+        indoc!(
+            "2 B    ┌─ main.rs
+            3 B    ├─ build
+            5 B ┌─ src
+            5 B root
+
+            1 directory, 2 files"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn ignore_file() -> Result<(), Box<dyn Error>> {
+    let mut first = NamedTempFile::new()?;
+    writeln!(first, "# comments and blank lines are ignored")?;
+    writeln!(first, "lipsum")?;
+    writeln!(first)?;
+    writeln!(first, "*.md")?;
+
+    let mut second = NamedTempFile::new()?;
+    writeln!(second, "nemesis.txt")?;
+
+    let first_path = first.path().to_string_lossy().into_owned();
+    let second_path = second.path().to_string_lossy().into_owned();
+
+    let out = utils::run_cmd(&[
+        "--ignore-file",
+        &first_path,
+        "--ignore-file",
+        &second_path,
+        "--no-ignore",
+        "tests/data",
+    ]);
+
+    assert_eq!(
+        out,
+        indoc!(
+            "- ┌─ the_yellow_king
+            100 B ├─ nylarlathotep.txt
+             83 B ├─ necronomicon.txt
+            308 B │  ┌─ polaris.txt
+            308 B ├─ dream_cycle
+            491 B data
+
+            2 directories, 3 files"
+        )
+    );
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use indoc::indoc;
+use std::{error::Error, fs};
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn file_count() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path();
+
+    fs::create_dir_all(root.join("node_modules").join("left-pad"))?;
+    fs::create_dir(root.join("src"))?;
+
+    for file in ["index.js", "package.json"] {
+        fs::write(root.join("node_modules").join("left-pad").join(file), "")?;
+    }
+
+    fs::write(root.join("node_modules").join(".package-lock.json"), "")?;
+    fs::write(root.join("src").join("main.js"), "")?;
+    fs::write(root.join("README.md"), "")?;
+
+    let root_name = root.file_name().unwrap().to_string_lossy();
+
+    let out = utils::run_cmd(&["--disk-usage", "file", "--hidden", &root.to_string_lossy()])
+        .replace(&*root_name, "root");
+
+    assert_eq!(
+        out,
+        indoc!(
+            "1    ┌─ main.js
+            1 ┌─ src
+            1 │     ┌─ package.json
+            1 │     ├─ index.js
+            2 │  ┌─ left-pad
+            1 │  ├─ .package-lock.json
+            3 ├─ node_modules
+            1 ├─ README.md
+            5 root
+
+            3 directories, 5 files"
+        )
+    );
+
+    Ok(())
+}
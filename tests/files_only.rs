@@ -0,0 +1,36 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn files_only() {
+    assert_eq!(
+        utils::run_cmd(&["--files-only", "--sort", "name", "tests/data"]),
+        indoc!(
+            "308 B ┌─ dream_cycle/polaris.txt
+ 100 B ├─ nylarlathotep.txt
+ 161 B ├─ nemesis.txt
+  83 B ├─ necronomicon.txt
+ 446 B ├─ lipsum/lipsum.txt
+ 143 B ├─ the_yellow_king/cassildas_song.md
+1241 B data
+
+6 files"
+        )
+    )
+}
+
+#[test]
+fn files_only_level() {
+    assert_eq!(
+        utils::run_cmd(&["--files-only", "--level", "1", "tests/data"]),
+        indoc!(
+            "100 B ┌─ nylarlathotep.txt
+ 161 B ├─ nemesis.txt
+  83 B ├─ necronomicon.txt
+1241 B data
+
+3 files"
+        )
+    )
+}
@@ -61,3 +61,66 @@ fn flat_with_level() {
         )
     )
 }
+
+#[test]
+fn flat_ext_column() {
+    assert_eq!(
+        utils::run_cmd(&["--layout", "flat", "--ext-column", "--hidden", "tests/data"]),
+        indoc!(
+            "143 B   md       the_yellow_king/cassildas_song.md
+ 143 B   -        the_yellow_king
+ 100 B   txt      nylarlathotep.txt
+ 161 B   txt      nemesis.txt
+  83 B   txt      necronomicon.txt
+ 446 B   txt      lipsum/lipsum.txt
+ 446 B   -        lipsum
+ 308 B   txt      dream_cycle/polaris.txt
+ 308 B   -        dream_cycle
+  86 B   (none)   .dagon
+1327 B   -        data
+
+3 directories, 7 files"
+        )
+    )
+}
+
+#[test]
+fn flat_no_count() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--layout",
+            "flat",
+            "--no-count",
+            "--level",
+            "1",
+            "tests/data"
+        ]),
+        indoc!(
+            "143 B   the_yellow_king
+ 100 B   nylarlathotep.txt
+ 161 B   nemesis.txt
+  83 B   necronomicon.txt
+ 446 B   lipsum
+ 308 B   dream_cycle
+1241 B   data"
+        )
+    )
+}
+
+#[test]
+fn flat_raw() {
+    assert_eq!(
+        utils::run_cmd(&["--layout", "flat", "--raw", "--level", "1", "tests/data"]),
+        indoc!(
+            "143   the_yellow_king
+ 100   nylarlathotep.txt
+ 161   nemesis.txt
+  83   necronomicon.txt
+ 446   lipsum
+ 308   dream_cycle
+1241   data
+
+3 directories, 6 files"
+        )
+    )
+}
@@ -0,0 +1,60 @@
+use indoc::indoc;
+use std::{error::Error, fs, path::Path, process::Command};
+use tempfile::TempDir;
+
+mod utils;
+
+fn git(repo: &Path, args: &[&str]) -> Result<(), Box<dyn Error>> {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["-c", "user.name=erd", "-c", "user.email=erd@example.com"])
+        .args(args)
+        .status()?;
+
+    assert!(status.success(), "git {args:?} failed");
+    Ok(())
+}
+
+#[test]
+fn git_status() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path();
+
+    fs::create_dir(root.join("src"))?;
+    fs::create_dir(root.join("docs"))?;
+    fs::write(root.join("src").join("main.rs"), "a\n")?;
+    fs::write(root.join("src").join("lib.rs"), "b\n")?;
+    fs::write(root.join("docs").join("guide.md"), "c\n")?;
+
+    git(root, &["init", "--quiet"])?;
+    git(root, &["add", "."])?;
+    git(root, &["commit", "--quiet", "--message", "init"])?;
+
+    fs::write(root.join("src").join("main.rs"), "aa\n")?;
+    git(root, &["add", "src/main.rs"])?;
+    fs::write(root.join("src").join("lib.rs"), "bb\n")?;
+    fs::write(root.join("notes.txt"), "d\n")?;
+
+    let root_name = root.file_name().unwrap().to_string_lossy();
+
+    let out =
+        utils::run_cmd(&["--git-status", &root.to_string_lossy()]).replace(&*root_name, "root");
+
+    assert_eq!(
+        out,
+        indoc!(
+            "3 B M-    ┌─ main.rs
+             3 B -M    ├─ lib.rs
+             6 B MM ┌─ src
+             2 B ?? ├─ notes.txt
+             2 B -- │  ┌─ guide.md
+             2 B -- ├─ docs
+            10 B MM root
+
+            2 directories, 4 files"
+        )
+    );
+
+    Ok(())
+}
@@ -119,3 +119,44 @@ fn glob_empty_set_dir() {
 fn glob_empty_set_file() {
     utils::run_cmd(&["--glob", "--pattern", "*weewoo*", "tests/data"]);
 }
+
+#[test]
+fn glob_match_dirs() {
+    assert_eq!(
+        utils::run_cmd(&["--glob", "--pattern", "*m*", "--match-dirs", "tests/data"]),
+        indoc!(
+            "161 B ┌─ nemesis.txt
+ 83 B ├─ necronomicon.txt
+446 B │  ┌─ lipsum.txt
+446 B ├─ lipsum
+308 B │  ┌─ polaris.txt
+308 B ├─ dream_cycle
+998 B data
+
+2 directories, 4 files"
+        )
+    );
+}
+
+#[test]
+fn glob_multiple() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--glob",
+            "--pattern",
+            "*.md",
+            "--pattern",
+            "lip*",
+            "tests/data"
+        ]),
+        indoc!(
+            "143 B    ┌─ cassildas_song.md
+            143 B ┌─ the_yellow_king
+            446 B │  ┌─ lipsum.txt
+            446 B ├─ lipsum
+            589 B data
+
+            2 directories, 2 files"
+        )
+    );
+}
@@ -0,0 +1,89 @@
+use indoc::indoc;
+use std::{error::Error, fs};
+use tempfile::TempDir;
+
+mod utils;
+
+/// Sets up a home directory whose git config points `core.excludesFile` at a file ignoring swap
+/// files, as well as a repository that ignores logs via `.git/info/exclude` and a directory via
+/// `.gitignore`.
+fn setup() -> Result<TempDir, Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let home = tmp.path().join("home");
+    let repo = tmp.path().join("repo");
+
+    fs::create_dir(&home)?;
+    fs::create_dir_all(repo.join(".git").join("info"))?;
+    fs::create_dir(repo.join("tmp"))?;
+
+    let excludes_file = home.join("excludes");
+    fs::write(&excludes_file, "*.swp\n")?;
+    fs::write(
+        home.join(".gitconfig"),
+        format!("[core]\n\texcludesFile = {}\n", excludes_file.display()),
+    )?;
+
+    fs::write(repo.join(".git").join("info").join("exclude"), "*.log\n")?;
+    fs::write(repo.join(".gitignore"), "tmp/\n")?;
+    fs::write(repo.join("notes.txt"), "a\n")?;
+    fs::write(repo.join("notes.txt.swp"), "b\n")?;
+    fs::write(repo.join("debug.log"), "c\n")?;
+    fs::write(repo.join("tmp").join("scratch.txt"), "d\n")?;
+
+    Ok(tmp)
+}
+
+fn run(tmp: &TempDir, args: &[&str]) -> String {
+    let repo = tmp.path().join("repo");
+    let repo = repo.to_string_lossy();
+
+    let mut all_args = args.to_vec();
+    all_args.push(&repo);
+
+    utils::run_cmd_with_home(&all_args, &tmp.path().join("home"))
+}
+
+#[test]
+fn global_ignore() -> Result<(), Box<dyn Error>> {
+    let tmp = setup()?;
+
+    assert_eq!(
+        run(&tmp, &[]),
+        indoc!(
+            "2 B ┌─ notes.txt
+            2 B repo
+
+            1 file"
+        ),
+        "core.excludesFile, .git/info/exclude, and .gitignore should all be respected"
+    );
+
+    assert_eq!(
+        run(&tmp, &["--no-global-ignore"]),
+        indoc!(
+            "2 B ┌─ notes.txt.swp
+            2 B ├─ notes.txt
+            4 B repo
+
+            2 files"
+        ),
+        "'--no-global-ignore' should only disregard core.excludesFile"
+    );
+
+    assert_eq!(
+        run(&tmp, &["--no-ignore"]),
+        indoc!(
+            "2 B    ┌─ scratch.txt
+            2 B ┌─ tmp
+            2 B ├─ notes.txt.swp
+            2 B ├─ notes.txt
+            2 B ├─ debug.log
+            8 B repo
+
+            1 directory, 4 files"
+        ),
+        "'--no-ignore' should disregard every source of ignore rules"
+    );
+
+    Ok(())
+}
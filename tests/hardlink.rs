@@ -1,5 +1,6 @@
 use indoc::indoc;
 use std::{env::current_dir, error::Error, fs};
+use tempfile::TempDir;
 
 mod utils;
 
@@ -36,3 +37,122 @@ fn hardlink() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[test]
+fn dedupe_hardlinks() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path();
+
+    fs::write(root.join("kadath.txt"), "Kadath in the Cold Waste\n")?;
+    fs::hard_link(root.join("kadath.txt"), root.join("leng.txt"))?;
+    fs::hard_link(root.join("kadath.txt"), root.join("ngranek.txt"))?;
+
+    let root_str = root.to_string_lossy();
+    let root_name = root.file_name().unwrap().to_string_lossy();
+
+    let out = utils::run_cmd(&["--layout", "flat", "--dedupe-hardlinks", &root_str])
+        .replace(&*root_name, "root");
+
+    assert_eq!(
+        out,
+        indoc!(
+            "25 B   ngranek.txt (+2 links)
+25 B   root
+
+3 files"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn count_hard_links() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path();
+
+    fs::write(root.join("kadath.txt"), "Kadath in the Cold Waste\n")?;
+    fs::hard_link(root.join("kadath.txt"), root.join("leng.txt"))?;
+    fs::hard_link(root.join("kadath.txt"), root.join("ngranek.txt"))?;
+
+    let root_str = root.to_string_lossy();
+    let root_name = root.file_name().unwrap().to_string_lossy();
+
+    let deduped = utils::run_cmd(&[&root_str]).replace(&*root_name, "root");
+
+    assert_eq!(
+        deduped,
+        indoc!(
+            "25 B ┌─ ngranek.txt
+25 B ├─ leng.txt
+25 B ├─ kadath.txt
+25 B root
+
+3 files"
+        )
+    );
+
+    let counted = utils::run_cmd(&["--count-hard-links", &root_str]).replace(&*root_name, "root");
+
+    assert_eq!(
+        counted,
+        indoc!(
+            "25 B ┌─ ngranek.txt
+25 B ├─ leng.txt
+25 B ├─ kadath.txt
+75 B root
+
+3 files"
+        )
+    );
+
+    assert_eq!(
+        utils::run_cmd(&["--no-dedupe-links", &root_str]).replace(&*root_name, "root"),
+        counted,
+        "'--no-dedupe-links' should be an alias of '--count-hard-links'"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn count_agrees_with_file_count() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path();
+
+    fs::write(root.join("kadath.txt"), "Kadath in the Cold Waste\n")?;
+    fs::hard_link(root.join("kadath.txt"), root.join("leng.txt"))?;
+    fs::write(root.join("ulthar.txt"), "Cats of Ulthar\n")?;
+
+    let root_str = root.to_string_lossy();
+    let root_name = root.file_name().unwrap().to_string_lossy();
+
+    let count = utils::run_cmd(&["--count", &root_str]).replace(&*root_name, "root");
+
+    assert_eq!(
+        count,
+        indoc!(
+            "1 ┌─ ulthar.txt
+1 ├─ leng.txt
+1 ├─ kadath.txt
+2 root
+
+3 files"
+        )
+    );
+
+    assert_eq!(
+        utils::run_cmd(&["--disk-usage", "file", &root_str]).replace(&*root_name, "root"),
+        count,
+        "'--count' and '--disk-usage file' should count hard links alike"
+    );
+
+    assert!(
+        utils::run_cmd(&["--count", "--count-hard-links", &root_str])
+            .replace(&*root_name, "root")
+            .contains("3 root"),
+        "Every hard link should be counted with '--count-hard-links'"
+    );
+
+    Ok(())
+}
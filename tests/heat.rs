@@ -0,0 +1,7 @@
+mod utils;
+
+#[test]
+#[should_panic]
+fn heat_age_size_gradient() {
+    utils::run_cmd(&["--heat", "age", "--size-gradient", "tests/data"]);
+}
@@ -0,0 +1,91 @@
+use indoc::indoc;
+use std::{error::Error, fs};
+use tempfile::TempDir;
+
+mod utils;
+
+/// Sets up a directory that looks like a git repository containing a dotfile as well as a
+/// directory that is ignored via `.gitignore`.
+fn setup() -> Result<TempDir, Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path();
+
+    fs::create_dir(root.join(".git"))?;
+    fs::create_dir(root.join("build"))?;
+    fs::write(root.join(".gitignore"), "build/\n")?;
+    fs::write(root.join(".env"), "SECRET=1\n")?;
+    fs::write(root.join("build").join("artifact.txt"), "a\n")?;
+    fs::write(root.join("visible.txt"), "b\n")?;
+
+    Ok(tmp)
+}
+
+fn run(tmp: &TempDir, args: &[&str]) -> String {
+    let root = tmp.path().to_string_lossy();
+    let root_name = tmp.path().file_name().unwrap().to_string_lossy();
+
+    let mut all_args = args.to_vec();
+    all_args.push(&root);
+
+    // The name of the temporary root directory is random so replace it.
+    utils::run_cmd(&all_args).replace(root_name.as_ref(), "root")
+}
+
+#[test]
+fn hidden_and_ignore_matrix() -> Result<(), Box<dyn Error>> {
+    let tmp = setup()?;
+
+    assert_eq!(
+        run(&tmp, &[]),
+        indoc!(
+            "2 B ┌─ visible.txt
+            2 B root
+
+            1 file"
+        ),
+        "Dotfiles and ignored files should be hidden by default"
+    );
+
+    assert_eq!(
+        run(&tmp, &["--hidden", "--no-git"]),
+        indoc!(
+            "2 B ┌─ visible.txt
+             7 B ├─ .gitignore
+             9 B ├─ .env
+            18 B root
+
+            3 files"
+        ),
+        "'--hidden' should reveal dotfiles but still respect .gitignore"
+    );
+
+    assert_eq!(
+        run(&tmp, &["--no-ignore"]),
+        indoc!(
+            "2 B ┌─ visible.txt
+            2 B │  ┌─ artifact.txt
+            2 B ├─ build
+            4 B root
+
+            1 directory, 2 files"
+        ),
+        "'--no-ignore' should reveal ignored files but not dotfiles"
+    );
+
+    assert_eq!(
+        run(&tmp, &["--hidden", "--no-git", "--no-ignore"]),
+        indoc!(
+            "2 B ┌─ visible.txt
+             2 B │  ┌─ artifact.txt
+             2 B ├─ build
+             7 B ├─ .gitignore
+             9 B ├─ .env
+            20 B root
+
+            1 directory, 4 files"
+        ),
+        "'--hidden' and '--no-ignore' together should reveal everything"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use indoc::indoc;
+use std::{error::Error, fs};
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn html() {
+    let out = utils::run_cmd(&[
+        "--html",
+        "--color",
+        "none",
+        "--level",
+        "1",
+        "--dirs-only",
+        "tests/data",
+    ]);
+
+    assert!(out.starts_with("<!DOCTYPE html>"));
+    assert!(out.ends_with("</html>"));
+
+    let body = out
+        .split_once("<body>\n")
+        .and_then(|(_, rest)| rest.split_once("\n</body>"))
+        .map(|(body, _)| body)
+        .unwrap();
+
+    assert_eq!(
+        body,
+        indoc!(
+            r#"<ul class="tree">
+              <li class="directory"><details open><summary><span class="size">1241 B</span><span class="name">data</span></summary>
+              <ul>
+                <li class="directory"><span class="size">308 B</span><span class="name">dream_cycle</span></li>
+                <li class="directory"><span class="size">446 B</span><span class="name">lipsum</span></li>
+                <li class="directory"><span class="size">143 B</span><span class="name">the_yellow_king</span></li>
+              </ul>
+              </details></li>
+            </ul>"#
+        )
+    );
+}
+
+#[test]
+fn html_escape() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    fs::write(tmp.path().join("<a&b>.txt"), "")?;
+
+    let out = utils::run_cmd(&[
+        "--html",
+        "--color",
+        "none",
+        "--suppress-size",
+        &tmp.path().to_string_lossy(),
+    ]);
+
+    assert!(out.contains(r#"<li class="file"><span class="name">&lt;a&amp;b&gt;.txt</span></li>"#));
+
+    Ok(())
+}
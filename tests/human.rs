@@ -0,0 +1,94 @@
+use indoc::indoc;
+use std::{error::Error, fs};
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn human_tree() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path();
+
+    // Directory totaling exactly 1KiB.
+    fs::create_dir(root.join("s"))?;
+    fs::write(root.join("s").join("a"), vec![0; 1000])?;
+    fs::write(root.join("s").join("b"), vec![0; 24])?;
+
+    let root_name = root.file_name().unwrap().to_string_lossy();
+
+    // The name of the temporary root directory is random so replace it.
+    let out = utils::run_cmd(&["--human", &root.to_string_lossy()]).replace(&*root_name, "root");
+
+    assert_eq!(
+        out,
+        indoc!(
+            "24   B    ┌─ b
+            1000   B    ├─ a
+             1.0 KiB ┌─ s
+             1.0 KiB root
+
+            1 directory, 2 files"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn unit_bytes() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path();
+
+    fs::write(root.join("a"), vec![0; 1024 * 1024])?;
+
+    let root_name = root.file_name().unwrap().to_string_lossy();
+
+    // Plain byte counts win over human-readable output.
+    let out = utils::run_cmd(&["--unit", "bytes", "--human", &root.to_string_lossy()])
+        .replace(&*root_name, "root");
+
+    assert_eq!(
+        out,
+        indoc!(
+            "1048576 ┌─ a
+            1048576 root
+
+            1 file"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn unit_none_group_digits() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path();
+
+    fs::write(root.join("a"), vec![0; 1024 * 1024])?;
+    fs::write(root.join("b"), vec![0; 24])?;
+
+    let root_name = root.file_name().unwrap().to_string_lossy();
+
+    let out = utils::run_cmd(&[
+        "--unit",
+        "none",
+        "--group-digits",
+        "--stats",
+        &root.to_string_lossy(),
+    ])
+    .replace(&*root_name, "root");
+
+    assert_eq!(
+        out,
+        indoc!(
+            "24 ┌─ b
+            1,048,576 ├─ a
+            1,048,600 root
+
+            2 files, 1,048,600 B total"
+        )
+    );
+
+    Ok(())
+}
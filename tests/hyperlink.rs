@@ -0,0 +1,24 @@
+use std::{error::Error, process::Command};
+
+#[test]
+fn hyperlink_when_piped() -> Result<(), Box<dyn Error>> {
+    let output = Command::new(env!("CARGO_BIN_EXE_erd"))
+        .args([
+            "--hyperlink",
+            "--color",
+            "none",
+            "--no-config",
+            "tests/data/lipsum",
+        ])
+        .output()?;
+
+    let stdout = String::from_utf8(output.stdout)?;
+
+    assert!(
+        stdout.contains("/lipsum.txt\u{1b}\\lipsum.txt\u{1b}]8;;\u{1b}\\"),
+        "--hyperlink should be honored when stdout isn't a terminal"
+    );
+    assert!(stdout.contains("\u{1b}]8;;file://"));
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+mod utils;
+
+#[cfg(unix)]
+mod test {
+    use indoc::formatdoc;
+    use std::{error::Error, fs, os::unix::fs::MetadataExt};
+    use tempfile::TempDir;
+
+    #[test]
+    fn ino() -> Result<(), Box<dyn Error>> {
+        let tmp = TempDir::new()?;
+        let root = tmp.path();
+
+        fs::write(root.join("kadath.txt"), "Kadath in the Cold Waste\n")?;
+        fs::hard_link(root.join("kadath.txt"), root.join("leng.txt"))?;
+
+        let root_ino = fs::metadata(root)?.ino();
+        let file_ino = fs::metadata(root.join("kadath.txt"))?.ino();
+        let width = root_ino.to_string().len().max(file_ino.to_string().len());
+
+        let root_name = root.file_name().unwrap().to_string_lossy();
+
+        let expected = formatdoc!(
+            "{file_ino:>width$} 25 B ┌─ leng.txt
+            {file_ino:>width$} 25 B ├─ kadath.txt
+            {root_ino:>width$} 25 B root
+
+            2 files"
+        );
+
+        for flag in ["--ino", "--inode"] {
+            let out = super::utils::run_cmd(&[flag, &root.to_string_lossy()])
+                .replace(&*root_name, "root");
+
+            assert_eq!(
+                out, expected,
+                "Hardlinks should share an inode number with or without '--long'"
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use serde_json::Value;
+
+mod utils;
+
+#[test]
+fn json() {
+    let out = utils::run_cmd(&["--json", "tests/data"]);
+    let root: Value = serde_json::from_str(&out).unwrap();
+
+    assert_eq!(root["file_name"], "data");
+    assert_eq!(root["file_type"], "directory");
+    assert_eq!(root["depth"], 0);
+    assert_eq!(root["symlink_target"], Value::Null);
+    assert_eq!(root["size"], 1241);
+    assert_eq!(root["formatted_size"], "1241 B");
+
+    let children = root["children"].as_array().unwrap();
+
+    let names = children
+        .iter()
+        .map(|child| child["file_name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        names,
+        [
+            "dream_cycle",
+            "lipsum",
+            "necronomicon.txt",
+            "nemesis.txt",
+            "nylarlathotep.txt",
+            "the_yellow_king"
+        ]
+    );
+
+    let lipsum = &children[1];
+
+    assert_eq!(lipsum["children"][0]["file_name"], "lipsum.txt");
+    assert_eq!(lipsum["children"][0]["file_type"], "file");
+    assert_eq!(lipsum["children"][0]["depth"], 2);
+    assert_eq!(lipsum["children"][0]["size"], 446);
+}
+
+#[cfg(unix)]
+#[test]
+fn json_symlink() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = tempfile::TempDir::new()?;
+    let target = std::path::Path::new("./tests/data/nemesis.txt").canonicalize()?;
+
+    std::os::unix::fs::symlink(&target, tmp.path().join("nemesis_link"))?;
+
+    let out = utils::run_cmd(&["--json", &tmp.path().to_string_lossy()]);
+    let root: Value = serde_json::from_str(&out)?;
+
+    let link = &root["children"][0];
+
+    assert_eq!(link["file_name"], "nemesis_link");
+    assert_eq!(link["file_type"], "symlink");
+    assert_eq!(link["depth"], 1);
+    assert_eq!(link["symlink_target"], target.to_string_lossy().as_ref());
+
+    Ok(())
+}
+
+#[test]
+fn json_level_suppress_size() {
+    let out = utils::run_cmd(&["--json", "--level", "1", "--suppress-size", "tests/data"]);
+    let root: Value = serde_json::from_str(&out).unwrap();
+
+    assert!(root.get("size").is_none());
+
+    let lipsum = &root["children"][1];
+
+    assert_eq!(lipsum["file_name"], "lipsum");
+    assert_eq!(lipsum["children"].as_array().unwrap().len(), 0);
+}
@@ -20,3 +20,43 @@ fn level() {
         "Failed to print at max level of 1."
     )
 }
+
+#[test]
+fn level_summarize() {
+    assert_eq!(
+        utils::run_cmd(&["--level", "1", "--summarize", "tests/data"]),
+        indoc!(
+            "143 B ┌─ the_yellow_king (1 file, 0 dirs, 143 B)
+ 100 B ├─ nylarlathotep.txt
+ 161 B ├─ nemesis.txt
+  83 B ├─ necronomicon.txt
+ 446 B ├─ lipsum (1 file, 0 dirs, 446 B)
+ 308 B ├─ dream_cycle (1 file, 0 dirs, 308 B)
+1241 B data
+
+3 directories, 6 files"
+        )
+    )
+}
+
+#[test]
+#[should_panic]
+fn level_summarize_dir_stats() {
+    utils::run_cmd(&["--level", "1", "--summarize", "--dir-stats", "tests/data"]);
+}
+
+#[test]
+fn min_depth() {
+    assert_eq!(
+        utils::run_cmd(&["--min-depth", "2", "tests/data"]),
+        indoc!(
+            "308 B ┌─ dream_cycle/polaris.txt
+             446 B ├─ lipsum/lipsum.txt
+             143 B ├─ the_yellow_king/cassildas_song.md
+            1241 B data
+
+            3 files"
+        ),
+        "Failed to hide levels shallower than 2."
+    )
+}
@@ -0,0 +1,35 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn limit() {
+    assert_eq!(
+        utils::run_cmd(&["--limit", "2", "tests/data"]),
+        indoc!(
+            "487 B ┌─ … 4 more
+             446 B │  ┌─ lipsum.txt
+             446 B ├─ lipsum
+             308 B │  ┌─ polaris.txt
+             308 B ├─ dream_cycle
+            1241 B data
+
+            3 directories, 6 files"
+        )
+    )
+}
+
+#[test]
+fn limit_flat() {
+    assert_eq!(
+        utils::run_cmd(&["--limit", "1", "--layout", "flat", "tests/data"]),
+        indoc!(
+            "933 B   … 5 more
+             308 B   dream_cycle/polaris.txt
+             308 B   dream_cycle
+            1241 B   data
+
+            3 directories, 6 files"
+        )
+    )
+}
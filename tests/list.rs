@@ -0,0 +1,84 @@
+use indoc::formatdoc;
+use std::{error::Error, path::Path, process::Command};
+
+mod utils;
+
+#[test]
+fn list() -> Result<(), Box<dyn Error>> {
+    let root = Path::new("tests/data").canonicalize()?;
+    let root = root.display();
+
+    assert_eq!(
+        utils::run_cmd(&["--list", "tests/data"]),
+        formatdoc!(
+            "{root}
+            {root}/dream_cycle
+            {root}/dream_cycle/polaris.txt
+            {root}/lipsum
+            {root}/lipsum/lipsum.txt
+            {root}/necronomicon.txt
+            {root}/nemesis.txt
+            {root}/nylarlathotep.txt
+            {root}/the_yellow_king
+            {root}/the_yellow_king/cassildas_song.md"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn list_dirs_only_level() -> Result<(), Box<dyn Error>> {
+    let root = Path::new("tests/data").canonicalize()?;
+    let root = root.display();
+
+    assert_eq!(
+        utils::run_cmd(&["--list", "--dirs-only", "--level", "1", "tests/data"]),
+        formatdoc!(
+            "{root}
+            {root}/dream_cycle
+            {root}/lipsum
+            {root}/the_yellow_king"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn list_size() -> Result<(), Box<dyn Error>> {
+    let root = Path::new("tests/data").canonicalize()?;
+    let root = root.display();
+
+    // The binary is invoked directly as the test harness strips tabs, which separate the size from
+    // the path.
+    let output = Command::new(env!("CARGO_BIN_EXE_erd"))
+        .args([
+            "--threads",
+            "1",
+            "--disk-usage",
+            "logical",
+            "--sort",
+            "name",
+            "--no-config",
+            "--list",
+            "--list-size",
+            "--dirs-only",
+            "tests/data",
+        ])
+        .output()?;
+
+    assert!(output.status.success());
+
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        format!(
+            "1241 B\t{root}\n\
+            308 B\t{root}/dream_cycle\n\
+            446 B\t{root}/lipsum\n\
+            143 B\t{root}/the_yellow_king\n"
+        )
+    );
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn markdown() {
+    assert_eq!(
+        utils::run_cmd(&["--markdown", "tests/data"]),
+        indoc!(
+            r"
+            - `1241 B` data/
+              - `308 B` dream\_cycle/
+                - `308 B` polaris.txt
+              - `446 B` lipsum/
+                - `446 B` lipsum.txt
+              - `83 B` necronomicon.txt
+              - `161 B` nemesis.txt
+              - `100 B` nylarlathotep.txt
+              - `143 B` the\_yellow\_king/
+                - `143 B` cassildas\_song.md"
+        )
+    )
+}
+
+#[test]
+fn markdown_suppress_size() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--markdown",
+            "--suppress-size",
+            "--level",
+            "1",
+            "tests/data"
+        ]),
+        indoc!(
+            r"
+            - data/
+              - dream\_cycle/
+              - lipsum/
+              - necronomicon.txt
+              - nemesis.txt
+              - nylarlathotep.txt
+              - the\_yellow\_king/"
+        )
+    )
+}
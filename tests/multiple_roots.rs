@@ -0,0 +1,57 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn multiple_roots() {
+    assert_eq!(
+        utils::run_cmd(&["tests/data/lipsum", "tests/data/dream_cycle"]),
+        indoc!(
+            "446 B ┌─ lipsum.txt
+            446 B lipsum
+
+            1 file
+
+            308 B ┌─ polaris.txt
+            308 B dream_cycle
+
+            1 file
+
+            754 B total"
+        )
+    );
+}
+
+#[test]
+fn multiple_roots_nested() {
+    assert_eq!(
+        utils::run_cmd(&["tests/data/lipsum", "tests/data", "tests/data/lipsum"])
+            .lines()
+            .last(),
+        Some("1241 B total"),
+        "Roots nested inside of or the same as another should be counted once"
+    );
+}
+
+#[test]
+fn multiple_roots_suppress_size() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--suppress-size",
+            "tests/data/lipsum",
+            "tests/data/dream_cycle"
+        ]),
+        indoc!(
+            "┌─ lipsum.txt
+ lipsum
+
+1 file
+
+ ┌─ polaris.txt
+ dream_cycle
+
+1 file"
+        ),
+        "The grand total should be omitted along with sizes"
+    );
+}
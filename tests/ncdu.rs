@@ -0,0 +1,53 @@
+use serde_json::Value;
+
+mod utils;
+
+#[test]
+fn ncdu() {
+    let out = utils::run_cmd(&["--ncdu", "tests/data"]);
+    let export: Value = serde_json::from_str(&out).unwrap();
+
+    assert_eq!(export[0], 1);
+    assert_eq!(export[1], 2);
+    assert_eq!(export[2]["progname"], "erdtree");
+
+    let root = export[3].as_array().unwrap();
+    let root_name = root[0]["name"].as_str().unwrap();
+
+    assert!(
+        root_name.ends_with("data"),
+        "root should be named by its full path"
+    );
+    assert!(std::path::Path::new(root_name).is_absolute());
+
+    let names = root[1..]
+        .iter()
+        .map(|entry| entry.get(0).unwrap_or(entry)["name"].as_str().unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        names,
+        [
+            "dream_cycle",
+            "lipsum",
+            "necronomicon.txt",
+            "nemesis.txt",
+            "nylarlathotep.txt",
+            "the_yellow_king"
+        ]
+    );
+
+    let lipsum = root[2].as_array().unwrap();
+
+    assert_eq!(lipsum[1]["name"], "lipsum.txt");
+    assert_eq!(lipsum[1]["asize"], 446);
+    assert!(lipsum[1].get("notreg").is_none());
+}
+
+#[test]
+fn ncdu_ignores_level() {
+    let out = utils::run_cmd(&["--ncdu", "--level", "1", "tests/data"]);
+    let export: Value = serde_json::from_str(&out).unwrap();
+
+    assert_eq!(export[3][2][1]["name"], "lipsum.txt");
+}
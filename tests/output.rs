@@ -0,0 +1,59 @@
+use indoc::indoc;
+use std::{error::Error, fs, process::Command};
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn output() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let path = tmp.path().join("tree.txt");
+    let path_str = path.to_string_lossy();
+
+    let stdout = utils::run_cmd(&["--output", &path_str, "--level", "1", "tests/data"]);
+
+    assert_eq!(stdout, "");
+
+    assert_eq!(
+        fs::read_to_string(&path)?,
+        indoc!(
+            " 143 B ┌─ the_yellow_king
+             100 B ├─ nylarlathotep.txt
+             161 B ├─ nemesis.txt
+              83 B ├─ necronomicon.txt
+             446 B ├─ lipsum
+             308 B ├─ dream_cycle
+            1241 B data
+
+            3 directories, 6 files
+            "
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn output_invalid_path() {
+    utils::run_cmd(&["--output", "tests/data/nonexistent/tree.txt", "tests/data"]);
+}
+
+#[test]
+fn output_untouched_on_error() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let path = tmp.path().join("tree.txt");
+    fs::write(&path, "previous")?;
+
+    let status = Command::new(env!("CARGO_BIN_EXE_erd"))
+        .args(["--no-config", "--output"])
+        .arg(&path)
+        .arg("tests/data/nonexistent")
+        .output()?
+        .status;
+
+    assert!(!status.success());
+    assert_eq!(fs::read_to_string(&path)?, "previous");
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
 use indoc::indoc;
+use std::{error::Error, fs::File};
+use tempfile::TempDir;
 
 mod utils;
 
@@ -20,3 +22,80 @@ fn prune() {
         )
     );
 }
+
+#[test]
+fn prune_min_dir_size() {
+    assert_eq!(
+        utils::run_cmd(&["--min-dir-size", "300B", "tests/data"]),
+        indoc!(
+            "100 B ┌─ nylarlathotep.txt
+ 161 B ├─ nemesis.txt
+  83 B ├─ necronomicon.txt
+ 446 B │  ┌─ lipsum.txt
+ 446 B ├─ lipsum
+ 308 B │  ┌─ polaris.txt
+ 308 B ├─ dream_cycle
+1241 B data
+
+2 directories, 5 files"
+        )
+    );
+}
+
+#[test]
+fn prune_du_threshold() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path();
+
+    std::fs::create_dir(root.join("big"))?;
+    std::fs::create_dir(root.join("small"))?;
+
+    // Sparse files so as to not actually consume disk space; sizes are logical in tests.
+    File::create(root.join("big").join("a.bin"))?.set_len(2 * 1024 * 1024 * 1024)?;
+    File::create(root.join("small").join("b.bin"))?.set_len(5 * 1024 * 1024)?;
+
+    let root_name = root.file_name().unwrap().to_string_lossy();
+
+    let out = utils::run_cmd(&["--du-threshold", "1G", &root.to_string_lossy()])
+        .replace(&*root_name, "root");
+
+    assert_eq!(
+        out,
+        indoc!(
+            "2147483648 B    ┌─ a.bin
+            2147483648 B ┌─ big
+            2152726528 B root
+
+            1 directory, 1 file"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn prune_nested_empty_directories() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path();
+
+    std::fs::create_dir_all(root.join("a").join("b").join("c").join("d"))?;
+    std::fs::create_dir_all(root.join("e").join("f"))?;
+    std::fs::write(root.join("e").join("g.txt"), "g\n")?;
+
+    let root_name = root.file_name().unwrap().to_string_lossy();
+
+    let out = utils::run_cmd(&["--prune", &root.to_string_lossy()]).replace(&*root_name, "root");
+
+    assert_eq!(
+        out,
+        indoc!(
+            "2 B    ┌─ g.txt
+            2 B ┌─ e
+            2 B root
+
+            1 directory, 1 file"
+        )
+    );
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
 use indoc::indoc;
+use std::{error::Error, fs};
+use tempfile::TempDir;
 
 mod utils;
 
@@ -67,3 +69,106 @@ fn regex_empty_set_file() {
 fn invalid_regex() {
     utils::run_cmd(&["--pattern", "*.txt", "tests/data"]);
 }
+
+#[test]
+fn regex_match_dirs() {
+    assert_eq!(
+        utils::run_cmd(&["--pattern", r"^d|\.txt$", "--match-dirs", "tests/data"]),
+        indoc!(
+            "100 B ┌─ nylarlathotep.txt
+161 B ├─ nemesis.txt
+ 83 B ├─ necronomicon.txt
+308 B │  ┌─ polaris.txt
+308 B ├─ dream_cycle
+652 B data
+
+1 directory, 4 files"
+        )
+    );
+}
+
+#[test]
+fn regex_match_dirs_keeps_contents() {
+    assert_eq!(
+        utils::run_cmd(&["--pattern", "dream", "--match-dirs", "tests/data"]),
+        indoc!(
+            "308 B    ┌─ polaris.txt
+308 B ┌─ dream_cycle
+308 B data
+
+1 directory, 1 file"
+        )
+    );
+}
+
+#[test]
+fn regex_matches_only() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--pattern",
+            "^d",
+            "--file-type",
+            "dir",
+            "--matches-only",
+            "tests/data"
+        ]),
+        indoc!(
+            "308 B ┌─ dream_cycle
+1241 B data
+
+1 directory"
+        )
+    );
+}
+
+#[test]
+fn regex_multiple() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--pattern",
+            "^lipsum",
+            "--pattern",
+            "^polaris",
+            "tests/data"
+        ]),
+        indoc!(
+            "446 B    ┌─ lipsum.txt
+            446 B ┌─ lipsum
+            308 B │  ┌─ polaris.txt
+            308 B ├─ dream_cycle
+            754 B data
+
+            2 directories, 2 files"
+        )
+    );
+}
+
+#[should_panic]
+#[test]
+fn invalid_regex_multiple() {
+    utils::run_cmd(&["--pattern", "^lipsum", "--pattern", "*.txt", "tests/data"]);
+}
+
+#[test]
+fn iregex() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path();
+
+    fs::write(root.join("README.md"), "# erdtree\n")?;
+    fs::write(root.join("notes.txt"), "notes\n")?;
+
+    let root_name = root.file_name().unwrap().to_string_lossy();
+
+    assert_eq!(
+        utils::run_cmd(&["--iregex", "--pattern", "^readme", &root.to_string_lossy()])
+            .replace(&*root_name, "root"),
+        indoc!(
+            "10 B ┌─ README.md
+            10 B root
+
+            1 file"
+        )
+    );
+
+    Ok(())
+}
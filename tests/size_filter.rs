@@ -0,0 +1,65 @@
+use indoc::indoc;
+use std::{error::Error, fs};
+use tempfile::TempDir;
+
+mod utils;
+
+/// Sets up a directory with files that are 1KiB, 100KiB, and 10MiB in size, with the smallest of
+/// them nested in a subdirectory.
+fn setup() -> Result<TempDir, Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path();
+
+    fs::create_dir(root.join("small"))?;
+    fs::write(root.join("small").join("tiny.bin"), vec![0; 1024])?;
+    fs::write(root.join("medium.bin"), vec![0; 100 * 1024])?;
+    fs::write(root.join("large.bin"), vec![0; 10 * 1024 * 1024])?;
+
+    Ok(tmp)
+}
+
+fn run(tmp: &TempDir, args: &[&str]) -> String {
+    let root = tmp.path().to_string_lossy();
+    let root_name = tmp.path().file_name().unwrap().to_string_lossy();
+
+    let mut all_args = args.to_vec();
+    all_args.push(&root);
+
+    // The name of the temporary root directory is random so replace it.
+    utils::run_cmd(&all_args).replace(&*root_name, "root")
+}
+
+#[test]
+fn min_size() -> Result<(), Box<dyn Error>> {
+    let tmp = setup()?;
+
+    assert_eq!(
+        run(&tmp, &["--min-size", "50K"]),
+        indoc!(
+            "102400 B ┌─ medium.bin
+            10485760 B ├─ large.bin
+            10588160 B root
+
+            2 files"
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn min_and_max_size() -> Result<(), Box<dyn Error>> {
+    let tmp = setup()?;
+
+    assert_eq!(
+        run(&tmp, &["--min-size", "50K", "--max-size", "1M"]),
+        indoc!(
+            "102400 B ┌─ medium.bin
+            102400 B root
+
+            1 file"
+        )
+    );
+
+    Ok(())
+}
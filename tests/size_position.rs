@@ -0,0 +1,45 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn size_position_right() {
+    assert_eq!(
+        utils::run_cmd(&["--size-position", "right", "tests/data"]),
+        indoc!(
+            "┌─ cassildas_song.md (143 B)
+            ┌─ the_yellow_king (143 B)
+            ├─ nylarlathotep.txt (100 B)
+            ├─ nemesis.txt (161 B)
+            ├─ necronomicon.txt (83 B)
+            │  ┌─ lipsum.txt (446 B)
+            ├─ lipsum (446 B)
+            │  ┌─ polaris.txt (308 B)
+            ├─ dream_cycle (308 B)
+            data (1241 B)
+
+            3 directories, 6 files"
+        )
+    )
+}
+
+#[test]
+fn size_position_right_flat() {
+    assert_eq!(
+        utils::run_cmd(&["--size-position", "right", "--layout", "flat", "tests/data"]),
+        indoc!(
+            "the_yellow_king/cassildas_song.md (143 B)
+            the_yellow_king (143 B)
+            nylarlathotep.txt (100 B)
+            nemesis.txt (161 B)
+            necronomicon.txt (83 B)
+            lipsum/lipsum.txt (446 B)
+            lipsum (446 B)
+            dream_cycle/polaris.txt (308 B)
+            dream_cycle (308 B)
+            data (1241 B)
+
+            3 directories, 6 files"
+        )
+    )
+}
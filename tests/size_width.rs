@@ -0,0 +1,35 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn size_width() {
+    assert_eq!(
+        utils::run_cmd(&["--size-width", "6", "--dirs-only", "tests/data"]),
+        indoc!(
+            "143 B ┌─ the_yellow_king
+               446 B ├─ lipsum
+               308 B ├─ dream_cycle
+              1241 B data
+
+            3 directories"
+        ),
+        "Disk usage should be padded to the given width"
+    )
+}
+
+#[test]
+fn size_width_overflow() {
+    assert_eq!(
+        utils::run_cmd(&["--du-width", "3", "--dirs-only", "tests/data"]),
+        indoc!(
+            "143 B ┌─ the_yellow_king
+            446 B ├─ lipsum
+            308 B ├─ dream_cycle
+            1241 B data
+
+            3 directories"
+        ),
+        "Disk usage wider than the given width should be printed in full"
+    )
+}
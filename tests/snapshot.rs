@@ -0,0 +1,47 @@
+use indoc::indoc;
+use std::{error::Error, fs};
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn snapshot_diff() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path().join("root");
+    let snapshot = tmp.path().join("root.erd");
+
+    fs::create_dir_all(root.join("src"))?;
+    fs::create_dir_all(root.join("build"))?;
+    fs::write(root.join("src").join("main.rs"), "hello\n")?;
+    fs::write(root.join("src").join("old.rs"), "a\n")?;
+    fs::write(root.join("build").join("out.o"), "b\n")?;
+    fs::write(root.join("notes.txt"), "c\n")?;
+
+    let root = root.to_string_lossy();
+    let snapshot = snapshot.to_string_lossy();
+
+    utils::run_cmd(&["--snapshot", &snapshot, &root]);
+
+    fs::remove_dir_all(tmp.path().join("root").join("build"))?;
+    fs::remove_file(tmp.path().join("root").join("src").join("old.rs"))?;
+    fs::write(
+        tmp.path().join("root").join("src").join("main.rs"),
+        "hello world\n",
+    )?;
+    fs::write(tmp.path().join("root").join("new.txt"), "d\n")?;
+
+    assert_eq!(
+        utils::run_cmd(&["--diff", &snapshot, &root]),
+        indoc!(
+            "12 B    ┌─ main.rs (+6 B)
+            12 B ┌─ src (+4 B, 1 removed)
+             2 B ├─ notes.txt
+             2 B ├─ new.txt (new)
+            16 B root (+4 B, 1 removed)
+
+            1 directory, 3 files"
+        )
+    );
+
+    Ok(())
+}
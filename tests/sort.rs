@@ -63,6 +63,38 @@ fn sort_name_dir_order() {
     );
 }
 
+#[test]
+fn sort_name_dirs_last() {
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", "--dirs-last", "tests/data"]),
+        utils::run_cmd(&["--sort", "name", "--dir-order", "last", "tests/data"]),
+        "'--dirs-last' should be equivalent to '--dir-order last'"
+    );
+
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--dirs-last",
+            "--level",
+            "1",
+            "tests/data"
+        ]),
+        indoc!(
+            "100 B ┌─ nylarlathotep.txt
+ 161 B ├─ nemesis.txt
+  83 B ├─ necronomicon.txt
+ 143 B ├─ the_yellow_king
+ 446 B ├─ lipsum
+ 308 B ├─ dream_cycle
+1241 B data
+
+3 directories, 6 files"
+        ),
+        "Failed to sort directories below files"
+    )
+}
+
 #[test]
 fn sort_size() {
     assert_eq!(
@@ -84,3 +116,149 @@ fn sort_size() {
         "Failed to sort by descending size"
     )
 }
+
+#[test]
+fn sort_name_reverse() {
+    assert_eq!(
+        utils::run_cmd(&["--sort", "name", "--reverse", "tests/data"]),
+        indoc!(
+            "308 B    ┌─ polaris.txt
+ 308 B ┌─ dream_cycle
+ 446 B │  ┌─ lipsum.txt
+ 446 B ├─ lipsum
+  83 B ├─ necronomicon.txt
+ 161 B ├─ nemesis.txt
+ 100 B ├─ nylarlathotep.txt
+ 143 B │  ┌─ cassildas_song.md
+ 143 B ├─ the_yellow_king
+1241 B data
+
+3 directories, 6 files"
+        ),
+        "Failed to sort by file name in reverse lexicographical order"
+    )
+}
+
+#[test]
+fn sort_name_dir_order_reverse() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--sort",
+            "name",
+            "--dir-order",
+            "first",
+            "--reverse",
+            "tests/data"
+        ]),
+        indoc!(
+            "308 B    ┌─ polaris.txt
+ 308 B ┌─ dream_cycle
+ 446 B │  ┌─ lipsum.txt
+ 446 B ├─ lipsum
+ 143 B │  ┌─ cassildas_song.md
+ 143 B ├─ the_yellow_king
+  83 B ├─ necronomicon.txt
+ 161 B ├─ nemesis.txt
+ 100 B ├─ nylarlathotep.txt
+1241 B data
+
+3 directories, 6 files"
+        ),
+        "Failed to keep directories grouped first when reversing"
+    )
+}
+
+/// Sets the modification time of `path` using `touch` with a timestamp of the form
+/// `[[CC]YY]MMDDhhmm`.
+#[cfg(unix)]
+fn set_mtime(path: &std::path::Path, stamp: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let status = std::process::Command::new("touch")
+        .arg("-t")
+        .arg(stamp)
+        .arg(path)
+        .status()?;
+    assert!(status.success());
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn sort_mtime_ties() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = tempfile::TempDir::new()?;
+    let root = tmp.path();
+
+    for (file_name, stamp) in [
+        ("azathoth.txt", "202001010000"),
+        ("cthulhu.txt", "201001010000"),
+        ("bokrug.txt", "201001010000"),
+    ] {
+        let path = root.join(file_name);
+        std::fs::write(&path, "Ph'nglui\n")?;
+        set_mtime(&path, stamp)?;
+    }
+
+    let root_str = root.to_string_lossy();
+    let root_name = root.file_name().unwrap().to_string_lossy();
+
+    let out = utils::run_cmd(&["--sort", "mtime", &root_str]).replace(&*root_name, "root");
+
+    assert_eq!(
+        out,
+        indoc!(
+            "9 B ┌─ azathoth.txt
+ 9 B ├─ cthulhu.txt
+ 9 B ├─ bokrug.txt
+27 B root
+
+3 files"
+        ),
+        "Failed to sort by modification time with ties broken by file name"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn sort_create_alias() {
+    assert_eq!(
+        utils::run_cmd(&["--sort", "btime", "tests/data"]),
+        utils::run_cmd(&["--sort", "create", "tests/data"]),
+        "'btime' should be an alias of 'create'"
+    );
+}
+
+#[test]
+fn sort_size_then_rname() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = tempfile::TempDir::new()?;
+    let root = tmp.path();
+
+    for (file_name, contents) in [
+        ("azathoth.txt", "aa\n"),
+        ("bokrug.txt", "aa\n"),
+        ("cthulhu.txt", "a\n"),
+        ("dagon.txt", "a\n"),
+    ] {
+        std::fs::write(root.join(file_name), contents)?;
+    }
+
+    let root_str = root.to_string_lossy();
+    let root_name = root.file_name().unwrap().to_string_lossy();
+
+    let out = utils::run_cmd(&["--sort", "size,rname", &root_str]).replace(&*root_name, "root");
+
+    assert_eq!(
+        out,
+        indoc!(
+            "2 B ┌─ cthulhu.txt
+ 2 B ├─ dagon.txt
+ 3 B ├─ azathoth.txt
+ 3 B ├─ bokrug.txt
+10 B root
+
+4 files"
+        ),
+        "Failed to break ties in size by reversed file name"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn stats() {
+    assert_eq!(
+        utils::run_cmd(&["--stats", "tests/data"]),
+        indoc!(
+            "143 B    ┌─ cassildas_song.md
+             143 B ┌─ the_yellow_king
+             100 B ├─ nylarlathotep.txt
+             161 B ├─ nemesis.txt
+              83 B ├─ necronomicon.txt
+             446 B │  ┌─ lipsum.txt
+             446 B ├─ lipsum
+             308 B │  ┌─ polaris.txt
+             308 B ├─ dream_cycle
+            1241 B data
+
+            3 directories, 6 files, 1241 B total"
+        )
+    )
+}
+
+#[test]
+fn stats_pattern() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--stats",
+            "--pattern",
+            "lipsum",
+            "--layout",
+            "flat",
+            "tests/data"
+        ]),
+        indoc!(
+            "446 B   lipsum/lipsum.txt
+            446 B   lipsum
+            446 B   data
+
+            1 directory, 1 file, 446 B total"
+        )
+    )
+}
+
+#[test]
+fn no_summary() {
+    assert_eq!(
+        utils::run_cmd(&["--no-summary", "--level", "1", "tests/data"]),
+        indoc!(
+            "143 B ┌─ the_yellow_king
+             100 B ├─ nylarlathotep.txt
+             161 B ├─ nemesis.txt
+              83 B ├─ necronomicon.txt
+             446 B ├─ lipsum
+             308 B ├─ dream_cycle
+            1241 B data"
+        )
+    )
+}
+
+#[test]
+fn timings_only_on_stderr() {
+    assert_eq!(
+        utils::run_cmd(&["--timings", "--level", "1", "tests/data"]),
+        indoc!(
+            "143 B ┌─ the_yellow_king
+             100 B ├─ nylarlathotep.txt
+             161 B ├─ nemesis.txt
+              83 B ├─ necronomicon.txt
+             446 B ├─ lipsum
+             308 B ├─ dream_cycle
+            1241 B data
+
+            3 directories, 6 files"
+        )
+    )
+}
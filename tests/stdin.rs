@@ -0,0 +1,79 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn stdin() {
+    let input = indoc!(
+        "tests/data/the_yellow_king/cassildas_song.md
+        tests/data/nemesis.txt
+        tests/data/nosuch.txt"
+    );
+
+    assert_eq!(
+        utils::run_cmd_with_stdin(&["--stdin", "tests/data"], input),
+        indoc!(
+            "143 B    ┌─ cassildas_song.md
+            143 B ┌─ the_yellow_king
+            161 B ├─ nemesis.txt
+            304 B data
+
+            1 directory, 2 files"
+        )
+    )
+}
+
+#[test]
+fn stdin_nul_separated() {
+    let input = "tests/data/lipsum/lipsum.txt\0tests/data/necronomicon.txt\0";
+
+    assert_eq!(
+        utils::run_cmd_with_stdin(&["--stdin", "tests/data"], input),
+        indoc!(
+            "83 B ┌─ necronomicon.txt
+            446 B │  ┌─ lipsum.txt
+            446 B ├─ lipsum
+            529 B data
+
+            1 directory, 2 files"
+        )
+    )
+}
+
+#[cfg(unix)]
+#[test]
+fn stdin_symlinked_root() -> Result<(), Box<dyn std::error::Error>> {
+    use std::{fs, os::unix::fs::symlink, path::Path};
+
+    let tmp = tempfile::TempDir::new()?;
+
+    fs::create_dir(tmp.path().join("real"))?;
+    fs::create_dir(tmp.path().join("other"))?;
+    fs::write(tmp.path().join("real").join("a.txt"), "erdtree\n")?;
+    fs::write(tmp.path().join("real").join("c.txt"), "erdtree\n")?;
+    fs::write(tmp.path().join("other").join("b.txt"), "erdtree\n")?;
+
+    symlink(Path::new("../other"), tmp.path().join("real").join("inner"))?;
+    symlink(Path::new("real"), tmp.path().join("link"))?;
+
+    let link = tmp.path().join("link");
+    let input = format!(
+        "{}\n{}",
+        link.join("a.txt").display(),
+        link.join("inner").join("b.txt").display()
+    );
+
+    assert_eq!(
+        utils::run_cmd_with_stdin(&["--stdin", "--follow", &link.to_string_lossy()], &input),
+        indoc!(
+            "8 B    ┌─ b.txt
+            8 B ┌─ inner → ../other
+            8 B ├─ a.txt
+           16 B link
+
+           1 directory, 2 files"
+        )
+    );
+
+    Ok(())
+}
@@ -2,7 +2,7 @@ mod utils;
 
 #[cfg(unix)]
 mod test {
-    use indoc::indoc;
+    use indoc::{formatdoc, indoc};
     use std::os::unix::fs::symlink;
     use std::path::Path;
     use tempfile::TempDir;
@@ -36,4 +36,252 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn root_link() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let target = Path::new("./tests/data/the_yellow_king").canonicalize()?;
+        let link = tmp.path().canonicalize()?.join("the_yellow_link");
+
+        symlink(target, &link)?;
+
+        let out = super::utils::run_cmd(&["--sort", "name", &link.to_string_lossy()]);
+
+        assert_eq!(
+            out,
+            indoc!(
+                "143 B ┌─ cassildas_song.md
+                143 B the_yellow_link
+
+                1 file"
+            ),
+            "Failed to traverse symlinked root"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn root_link_file() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let target = Path::new("./tests/data/nemesis.txt").canonicalize()?;
+        let link = tmp.path().canonicalize()?.join("nemesis_link");
+
+        symlink(target, &link)?;
+
+        let out = super::utils::run_cmd(&["--sort", "name", &link.to_string_lossy()]);
+
+        assert_eq!(out, "161 B nemesis_link", "Failed to print symlinked file");
+
+        Ok(())
+    }
+
+    #[test]
+    fn resolve_links() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let real = tmp.path().join("real").join("x");
+        let root = tmp.path().join("root");
+
+        std::fs::create_dir_all(&real)?;
+        std::fs::create_dir(&root)?;
+        std::fs::write(real.join("a.txt"), "erdtree\n")?;
+
+        // The target lies outside of the root so that it is followed rather than revisited.
+        symlink(Path::new("../real/./x"), root.join("rel"))?;
+
+        let tmp_canonical = tmp.path().canonicalize()?;
+
+        let out = super::utils::run_cmd(&[
+            "--follow",
+            "--resolve-links",
+            &root.canonicalize()?.to_string_lossy(),
+        ]);
+
+        assert_eq!(
+            out,
+            formatdoc!(
+                "8 B    ┌─ a.txt
+                8 B ┌─ rel → {}
+                8 B root
+
+                1 directory, 1 file",
+                tmp_canonical.join("real").join("x").display(),
+            ),
+            "Failed to resolve symlink target"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn follow_revisit() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let outside = TempDir::new()?;
+
+        std::fs::create_dir(tmp.path().join("real"))?;
+        std::fs::write(tmp.path().join("real").join("a.txt"), "erdtree\n")?;
+        std::fs::write(outside.path().join("b.txt"), "erdtree\n")?;
+
+        symlink(Path::new("real"), tmp.path().join("inner"))?;
+        symlink(outside.path(), tmp.path().join("outer"))?;
+        symlink(outside.path(), tmp.path().join("outer_again"))?;
+
+        let tmp_canonical = tmp.path().canonicalize()?;
+        let tmp_name = tmp_canonical.file_name().unwrap().to_string_lossy();
+
+        let out = super::utils::run_cmd(&["--follow", &tmp_canonical.to_string_lossy()])
+            .replace(&*tmp_name, "root");
+
+        let revisits = out
+            .lines()
+            .filter(|line| line.ends_with("(already shown)"))
+            .count();
+
+        assert!(
+            out.contains("inner → real (already shown)"),
+            "Expected the symlink into the root to not be followed"
+        );
+
+        assert!(
+            !out.contains("(cycle)"),
+            "Expected symlinks to directories shown elsewhere to not be mistaken for cycles"
+        );
+
+        assert_eq!(
+            revisits, 2,
+            "Expected only one of the symlinks to the same directory to be followed"
+        );
+
+        assert!(
+            out.contains(&format!(
+                "outer_again → {} (already shown)",
+                outside.path().display()
+            )),
+            "Expected the first symlink to the directory in sorted order to be followed"
+        );
+
+        assert!(
+            out.ends_with("16 B root\n\n2 directories, 2 files, 2 links"),
+            "Expected every file to be counted exactly once"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn follow_unshown_target() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+
+        std::fs::create_dir(tmp.path().join(".secret"))?;
+        std::fs::create_dir(tmp.path().join("ign"))?;
+        std::fs::write(tmp.path().join(".secret").join("s.txt"), "erdtree\n")?;
+        std::fs::write(tmp.path().join("ign").join("i.txt"), "erdtree\n")?;
+
+        symlink(Path::new(".secret"), tmp.path().join("link"))?;
+        symlink(Path::new("ign"), tmp.path().join("link2"))?;
+
+        let tmp_canonical = tmp.path().canonicalize()?;
+        let tmp_name = tmp_canonical.file_name().unwrap().to_string_lossy();
+
+        let out = super::utils::run_cmd(&[
+            "--follow",
+            "--exclude",
+            "ign",
+            &tmp_canonical.to_string_lossy(),
+        ])
+        .replace(&*tmp_name, "root");
+
+        assert_eq!(
+            out,
+            indoc!(
+                "8 B    ┌─ i.txt
+                8 B ┌─ link2 → ign
+                8 B │  ┌─ s.txt
+                8 B ├─ link → .secret
+               16 B root
+
+               2 directories, 2 files"
+            ),
+            "Expected symlinks to directories that aren't shown to be followed"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn follow_cycle() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+        let dir = tmp.path().join("a").join("b");
+
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(tmp.path().join("a").join("f.txt"), "erdtree\n")?;
+
+        symlink(Path::new(".."), dir.join("up"))?;
+
+        let tmp_canonical = tmp.path().canonicalize()?;
+        let tmp_name = tmp_canonical.file_name().unwrap().to_string_lossy();
+
+        let out = super::utils::run_cmd(&["--follow", &tmp_canonical.to_string_lossy()]);
+
+        assert_eq!(
+            out,
+            formatdoc!(
+                "8 B    ┌─ f.txt
+                  -    │  ┌─ up → .. (cycle)
+                  -    ├─ b
+                8 B ┌─ a
+                8 B {}
+
+                2 directories, 1 file, 1 link",
+                tmp_name
+            ),
+            "Failed to terminate on symlink cycle"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn broken() -> Result<(), Box<dyn std::error::Error>> {
+        let tmp = TempDir::new()?;
+
+        std::fs::create_dir(tmp.path().join("d"))?;
+        std::fs::write(tmp.path().join("f.txt"), "erdtree\n")?;
+
+        symlink(Path::new("nowhere"), tmp.path().join("gone"))?;
+        symlink(Path::new("../f.txt"), tmp.path().join("d").join("fine"))?;
+        symlink(Path::new("nope"), tmp.path().join("d").join("missing"))?;
+
+        let tmp_canonical = tmp.path().canonicalize()?;
+        let tmp_name = tmp_canonical.file_name().unwrap().to_string_lossy();
+
+        for follow in [false, true] {
+            let mut args = vec!["--broken-only", "--suppress-size"];
+
+            if follow {
+                args.push("--follow");
+            }
+
+            let root = tmp_canonical.to_string_lossy();
+            args.push(&root);
+
+            let out = super::utils::run_cmd(&args).replace(&*tmp_name, "root");
+
+            assert_eq!(
+                out,
+                [
+                    "┌─ gone → nowhere ⇥ missing",
+                    " │  ┌─ missing → nope ⇥ missing",
+                    " ├─ d",
+                    " root",
+                    "",
+                    "1 directory, 2 links",
+                ]
+                .join("\n"),
+                "Expected only broken symlinks and their ancestors with follow={follow}"
+            );
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,77 @@
+mod utils;
+
+#[cfg(unix)]
+mod test {
+    use indoc::indoc;
+    use std::{error::Error, fs, process::Command};
+    use tempfile::TempDir;
+
+    /// Sets up a directory with a file that was just modified and two files, one of them nested in
+    /// a subdirectory, that were last modified at the start of the year 2000.
+    fn setup() -> Result<TempDir, Box<dyn Error>> {
+        let tmp = TempDir::new()?;
+        let root = tmp.path();
+
+        fs::create_dir(root.join("old"))?;
+        fs::write(root.join("old").join("a.txt"), "a\n")?;
+        fs::write(root.join("b.txt"), "b\n")?;
+        fs::write(root.join("c.txt"), "c\n")?;
+
+        let status = Command::new("touch")
+            .args(["-t", "200001010000"])
+            .arg(root.join("old").join("a.txt"))
+            .arg(root.join("b.txt"))
+            .status()?;
+
+        assert!(status.success());
+
+        Ok(tmp)
+    }
+
+    fn run(tmp: &TempDir, args: &[&str]) -> String {
+        let root = tmp.path().to_string_lossy();
+        let root_name = tmp.path().file_name().unwrap().to_string_lossy();
+
+        let mut all_args = args.to_vec();
+        all_args.push(&root);
+
+        // The name of the temporary root directory is random so replace it.
+        super::utils::run_cmd(&all_args).replace(&*root_name, "root")
+    }
+
+    #[test]
+    fn newer() -> Result<(), Box<dyn Error>> {
+        let tmp = setup()?;
+
+        assert_eq!(
+            run(&tmp, &["--newer", "7d"]),
+            indoc!(
+                "2 B ┌─ c.txt
+                2 B root
+
+                1 file"
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn older() -> Result<(), Box<dyn Error>> {
+        let tmp = setup()?;
+
+        assert_eq!(
+            run(&tmp, &["--older", "7d"]),
+            indoc!(
+                "2 B    ┌─ a.txt
+                2 B ┌─ old
+                2 B ├─ b.txt
+                4 B root
+
+                1 directory, 2 files"
+            )
+        );
+
+        Ok(())
+    }
+}
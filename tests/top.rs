@@ -0,0 +1,70 @@
+use indoc::indoc;
+use std::{error::Error, fs};
+use tempfile::TempDir;
+
+mod utils;
+
+#[test]
+fn top() {
+    assert_eq!(
+        utils::run_cmd(&["--top", "3", "tests/data"]),
+        indoc!(
+            "446 B   lipsum/lipsum.txt
+             308 B   dream_cycle/polaris.txt
+             161 B   nemesis.txt
+
+            3 files"
+        )
+    )
+}
+
+#[test]
+fn top_exceeds_file_count() {
+    assert_eq!(
+        utils::run_cmd(&["--top", "100", "--extension", "txt", "tests/data"]),
+        indoc!(
+            "446 B   lipsum/lipsum.txt
+             308 B   dream_cycle/polaris.txt
+             161 B   nemesis.txt
+             100 B   nylarlathotep.txt
+              83 B   necronomicon.txt
+
+            5 files"
+        )
+    )
+}
+
+#[test]
+fn top_line_count() {
+    assert_eq!(
+        utils::run_cmd(&["--top", "2", "--disk-usage", "line", "tests/data"]),
+        indoc!(
+            "10   dream_cycle/polaris.txt
+             6   the_yellow_king/cassildas_song.md
+
+            2 files"
+        )
+    )
+}
+
+#[test]
+fn top_ties() -> Result<(), Box<dyn Error>> {
+    let tmp = TempDir::new()?;
+    let root = tmp.path();
+
+    for name in ["c.txt", "a.txt", "d.txt", "b.txt"] {
+        fs::write(root.join(name), "x\n")?;
+    }
+
+    assert_eq!(
+        utils::run_cmd(&["--top", "2", &root.to_string_lossy()]),
+        indoc!(
+            "2 B   a.txt
+            2 B   b.txt
+
+            2 files"
+        )
+    );
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use indoc::indoc;
+
+mod utils;
+
+#[test]
+fn truncate_render_width() {
+    assert_eq!(
+        utils::run_cmd(&["--truncate", "--render-width", "20", "tests/data"]),
+        indoc!(
+            "143 B    ┌─ cassil…
+ 143 B ┌─ the_yello…
+ 100 B ├─ nylarlath…
+ 161 B ├─ nemesis.t…
+  83 B ├─ necronomi…
+ 446 B │  ┌─ lipsum…
+ 446 B ├─ lipsum
+ 308 B │  ┌─ polari…
+ 308 B ├─ dream_cyc…
+1241 B data
+
+3 directories, 6 files"
+        )
+    )
+}
+
+#[test]
+fn truncate_size_right() {
+    assert_eq!(
+        utils::run_cmd(&[
+            "--truncate",
+            "--render-width",
+            "20",
+            "--size-position",
+            "right",
+            "tests/data"
+        ]),
+        indoc!(
+            "┌─ cassi… (143 B)
+            ┌─ the_yell… (143 B)
+            ├─ nylarlat… (100 B)
+            ├─ nemesis.… (161 B)
+            ├─ necronomi… (83 B)
+            │  ┌─ lipsu… (446 B)
+            ├─ lipsum (446 B)
+            │  ┌─ polar… (308 B)
+            ├─ dream_cy… (308 B)
+            data (1241 B)
+
+            3 directories, 6 files"
+        )
+    )
+}
@@ -1,3 +1,7 @@
+#![allow(dead_code)]
+
+use std::io::Write;
+use std::path::Path;
 use std::process::Command;
 use std::process::Stdio;
 use strip_ansi_escapes::strip as strip_ansi_escapes;
@@ -20,15 +24,99 @@ pub fn run_cmd(args: &[&str]) -> String {
         cmd.arg(arg);
     }
 
-    let output = cmd
-        .stdin(Stdio::null())
+    run(cmd)
+}
+
+/// Like [`run_cmd`] but reads in `.erdtree.toml` from `config` rather than passing `--no-config`.
+/// Only `--threads` is set by default so that config reconciliation can be tested in isolation.
+/// The binary is invoked directly so that other config locations can be removed from the
+/// environment.
+pub fn run_cmd_with_config(args: &[&str], config: &Path) -> String {
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_erd"));
+    cmd.args(["--threads", "1"]);
+
+    for arg in args {
+        cmd.arg(arg);
+    }
+
+    cmd.env("ERDTREE_TOML_PATH", config)
+        .env_remove("ERDTREE_CONFIG_PATH")
+        .env_remove("XDG_CONFIG_HOME")
+        .env_remove("HOME");
+
+    run(cmd)
+}
+
+/// Like [`run_cmd`] but with `home` as the user's home directory so that user-level git
+/// configuration such as `core.excludesFile` can be tested in isolation.
+pub fn run_cmd_with_home(args: &[&str], home: &Path) -> String {
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_erd"));
+    cmd.args([
+        "--threads",
+        "1",
+        "--disk-usage",
+        "logical",
+        "--sort",
+        "name",
+        "--no-config",
+    ]);
+
+    for arg in args {
+        cmd.arg(arg);
+    }
+
+    cmd.env("HOME", home)
+        .env_remove("XDG_CONFIG_HOME")
+        .env_remove("GIT_CONFIG_GLOBAL");
+
+    run(cmd)
+}
+
+/// Like [`run_cmd`] but with `input` piped into stdin.
+pub fn run_cmd_with_stdin(args: &[&str], input: &str) -> String {
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_erd"));
+    cmd.args([
+        "--threads",
+        "1",
+        "--disk-usage",
+        "logical",
+        "--sort",
+        "name",
+        "--no-config",
+    ]);
+
+    for arg in args {
+        cmd.arg(arg);
+    }
+
+    run_with_input(cmd, Some(input))
+}
+
+fn run(cmd: Command) -> String {
+    run_with_input(cmd, None)
+}
+
+fn run_with_input(mut cmd: Command, input: Option<&str>) -> String {
+    let stdin = if input.is_some() {
+        Stdio::piped()
+    } else {
+        Stdio::null()
+    };
+
+    let mut child = cmd
+        .stdin(stdin)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
-        .unwrap()
-        .wait_with_output()
         .unwrap();
 
+    if let Some(input) = input {
+        let mut stdin = child.stdin.take().unwrap();
+        stdin.write_all(input.as_bytes()).unwrap();
+    }
+
+    let output = child.wait_with_output().unwrap();
+
     if !output.status.success() {
         let err_msg = String::from_utf8(output.stderr).unwrap();
         panic!("{err_msg}");